@@ -0,0 +1,162 @@
+use std::convert::TryFrom;
+use std::fmt;
+
+use crate::{json, Vector2};
+
+/// Answer to a single part of a puzzle
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    /// The part has no answer, e.g. the second part of day 25
+    None,
+    Int(i64),
+    Str(String),
+    /// A coordinate, displayed as `x,y`
+    Point(Vector2),
+    /// A tuple of integers, displayed as `a,b,c`
+    Tuple(Vec<i64>),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::None => write!(f, "-"),
+            Answer::Int(v) => write!(f, "{}", v),
+            Answer::Str(s) => write!(f, "{}", s),
+            Answer::Point(p) => write!(f, "{},{}", p.x, p.y),
+            Answer::Tuple(values) => {
+                for (i, v) in values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", v)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Answer {
+    /// JSON value of the answer, integers are numbers, `None` is `null` and
+    /// the rest are strings as they are displayed
+    pub fn to_json(&self) -> String {
+        match self {
+            Answer::None => "null".to_owned(),
+            Answer::Int(v) => v.to_string(),
+            _ => json::string(&self.to_string()),
+        }
+    }
+}
+
+impl From<()> for Answer {
+    fn from(_: ()) -> Self {
+        Answer::None
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(v: i32) -> Self {
+        Answer::Int(i64::from(v))
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(v: u32) -> Self {
+        Answer::Int(i64::from(v))
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(v: i64) -> Self {
+        Answer::Int(v)
+    }
+}
+
+/// Values too large for an `i64` are kept as text
+impl From<u64> for Answer {
+    fn from(v: u64) -> Self {
+        i64::try_from(v)
+            .map(Answer::Int)
+            .unwrap_or_else(|_| Answer::Str(v.to_string()))
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(v: usize) -> Self {
+        Answer::from(v as u64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Str(s)
+    }
+}
+
+impl<'a> From<&'a str> for Answer {
+    fn from(s: &'a str) -> Self {
+        Answer::Str(s.to_owned())
+    }
+}
+
+impl From<Vector2> for Answer {
+    fn from(v: Vector2) -> Self {
+        Answer::Point(v)
+    }
+}
+
+impl From<(i32, i32)> for Answer {
+    fn from(v: (i32, i32)) -> Self {
+        Answer::Tuple(vec![i64::from(v.0), i64::from(v.1)])
+    }
+}
+
+impl From<(i32, i32, i32)> for Answer {
+    fn from(v: (i32, i32, i32)) -> Self {
+        Answer::Tuple(vec![i64::from(v.0), i64::from(v.1), i64::from(v.2)])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_answers() {
+        assert_eq!(Answer::None.to_string(), "-");
+        assert_eq!(Answer::from(-12).to_string(), "-12");
+        assert_eq!(
+            Answer::from(4_000_000_001_480_i64).to_string(),
+            "4000000001480"
+        );
+        assert_eq!(Answer::from("FPRBRRZA").to_string(), "FPRBRRZA");
+        assert_eq!(Answer::from(Vector2::new(21, 77)).to_string(), "21,77");
+        assert_eq!(Answer::from((224, 222, 27)).to_string(), "224,222,27");
+    }
+
+    #[test]
+    fn json_answers() {
+        assert_eq!("null", Answer::None.to_json());
+        assert_eq!("-12", Answer::from(-12).to_json());
+        assert_eq!(r#""21,77""#, Answer::from(Vector2::new(21, 77)).to_json());
+    }
+
+    #[test]
+    fn compare_answers() {
+        assert_eq!(Answer::from(5usize), Answer::from(5));
+        assert_ne!(Answer::from(5), Answer::from("5"));
+    }
+
+    #[test]
+    fn large_unsigned_answers_are_text() {
+        assert_eq!(Answer::Int(i64::MAX), Answer::from(i64::MAX as u64));
+        assert_eq!(
+            Answer::from("9223372036854775808"),
+            Answer::from(i64::MAX as u64 + 1)
+        );
+        assert_eq!(
+            r#""18446744073709551615""#,
+            Answer::from(u64::MAX).to_json()
+        );
+    }
+}
@@ -0,0 +1,164 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::{Error, Result};
+
+/// Known answers keyed by day and part
+///
+/// The manifest is a small subset of TOML, one table per day:
+///
+/// ```text
+/// [day01]
+/// part1 = 497
+/// part2 = "558"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Answers {
+    answers: BTreeMap<(u32, u32), String>,
+}
+
+impl Answers {
+    /// Reads the manifest at `path`
+    pub fn read(path: &Path) -> Result<Self> {
+        let s = crate::read_path(path)?;
+
+        Answers::parse_lines(&s)
+            .map_err(|(nr, e)| Error::parse(e).at_line(nr).with_path(path))
+    }
+
+    /// Parses the manifest
+    pub fn parse(s: &str) -> Result<Self> {
+        Answers::parse_lines(s).map_err(|(nr, e)| Error::parse(e).at_line(nr))
+    }
+
+    /// Parses the manifest, errors contain the line number
+    fn parse_lines(s: &str) -> std::result::Result<Self, (usize, String)> {
+        let mut answers = BTreeMap::new();
+        let mut day = None;
+
+        for (index, line) in s.lines().enumerate() {
+            let nr = index + 1;
+            let line = strip_comment(line).trim();
+
+            if line.is_empty() {
+                continue;
+            }
+
+            if line.starts_with('[') {
+                let name =
+                    line.trim_start_matches('[').trim_end_matches(']').trim();
+
+                day = Some(parse_number(name, "day").ok_or_else(|| {
+                    (nr, format!("invalid table '{}'", line))
+                })?);
+
+                continue;
+            }
+
+            let day = day
+                .ok_or_else(|| (nr, "key outside of a day table".to_owned()))?;
+
+            let mut parts = line.splitn(2, '=');
+            let key = parts.next().unwrap_or("").trim();
+            let value = parts
+                .next()
+                .map(str::trim)
+                .ok_or_else(|| (nr, "expected 'key = value'".to_owned()))?;
+
+            let part = parse_number(key, "part")
+                .filter(|p| *p == 1 || *p == 2)
+                .ok_or_else(|| (nr, format!("invalid key '{}'", key)))?;
+
+            let value = parse_value(value)
+                .ok_or_else(|| (nr, format!("invalid value '{}'", value)))?;
+
+            answers.insert((day, part), value);
+        }
+
+        Ok(Answers { answers })
+    }
+
+    /// Returns the known answer for the part
+    pub fn get(&self, day: u32, part: u32) -> Option<&str> {
+        self.answers.get(&(day, part)).map(|s| s.as_str())
+    }
+
+    pub fn len(&self) -> usize {
+        self.answers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.answers.is_empty()
+    }
+}
+
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..i],
+            _ => {}
+        }
+    }
+
+    line
+}
+
+/// Parses names such as `day07` or `part2`
+fn parse_number(name: &str, prefix: &str) -> Option<u32> {
+    name.strip_prefix(prefix)
+        .and_then(|nr| nr.parse::<u32>().ok())
+}
+
+/// Parses a quoted string or a bare integer
+fn parse_value(value: &str) -> Option<String> {
+    if value.len() >= 2 && value.starts_with('"') && value.ends_with('"') {
+        let inner = &value[1..value.len() - 1];
+        if inner.contains('"') {
+            return None;
+        }
+        return Some(inner.to_owned());
+    }
+
+    value.parse::<i64>().ok().map(|v| v.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &'static str = r#"
+# Known answers
+[day01]
+part1 = 497
+part2 = "558" # trailing comment
+
+[day11]
+part1 = "21,77"
+    "#;
+
+    #[test]
+    fn parse_manifest() {
+        let answers = Answers::parse(INPUT.trim()).unwrap();
+
+        assert_eq!(3, answers.len());
+        assert_eq!(Some("497"), answers.get(1, 1));
+        assert_eq!(Some("558"), answers.get(1, 2));
+        assert_eq!(Some("21,77"), answers.get(11, 1));
+        assert_eq!(None, answers.get(11, 2));
+    }
+
+    #[test]
+    fn parse_errors_name_line() {
+        let err = Answers::parse("[day01]\npart3 = 1").unwrap_err();
+        assert_eq!("line 2: invalid key 'part3'", err.to_string());
+
+        let err = Answers::parse("part1 = 1").unwrap_err();
+        assert_eq!("line 1: key outside of a day table", err.to_string());
+
+        let err = Answers::parse("[day01]\npart1 = abc").unwrap_err();
+        assert_eq!("line 2: invalid value 'abc'", err.to_string());
+    }
+}
@@ -0,0 +1,331 @@
+use std::fmt;
+use std::iter::Enumerate;
+use std::slice;
+
+/// Stable identifier of a value in an `Arena`
+///
+/// A handle stays valid until its value is removed. The slot of a removed
+/// value is reused with a new generation, so stale handles never refer to
+/// the values inserted later.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub struct Handle {
+    index: usize,
+    generation: u32,
+}
+
+impl Handle {
+    /// Slot of the value, unique among the values in the arena
+    pub fn index(self) -> usize {
+        self.index
+    }
+
+    pub fn generation(self) -> u32 {
+        self.generation
+    }
+}
+
+impl fmt::Debug for Handle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}v{}", self.index, self.generation)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Slot<T> {
+    generation: u32,
+    value: Option<T>,
+}
+
+/// Generational arena, values are stored in slots and found with handles
+///
+/// Values are iterated in the order of their slots, which does not change
+/// while they stay in the arena.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Arena<T> {
+    slots: Vec<Slot<T>>,
+    free: Vec<usize>,
+    len: usize,
+}
+
+impl<T> Arena<T> {
+    pub fn new() -> Self {
+        Arena {
+            slots: Vec::new(),
+            free: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// Number of values in the arena
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Adds a value, reusing the most recently freed slot if there is one
+    pub fn insert(&mut self, value: T) -> Handle {
+        self.len += 1;
+
+        match self.free.pop() {
+            Some(index) => {
+                let slot = &mut self.slots[index];
+                slot.value = Some(value);
+
+                Handle {
+                    index,
+                    generation: slot.generation,
+                }
+            }
+            None => {
+                self.slots.push(Slot {
+                    generation: 0,
+                    value: Some(value),
+                });
+
+                Handle {
+                    index: self.slots.len() - 1,
+                    generation: 0,
+                }
+            }
+        }
+    }
+
+    /// Removes the value of `handle`, `None` if it was already removed
+    pub fn remove(&mut self, handle: Handle) -> Option<T> {
+        let slot = self.slots.get_mut(handle.index)?;
+
+        if slot.generation != handle.generation {
+            return None;
+        }
+
+        let value = slot.value.take()?;
+        slot.generation += 1;
+        self.free.push(handle.index);
+        self.len -= 1;
+
+        Some(value)
+    }
+
+    /// Whether the value of `handle` is still in the arena
+    pub fn contains(&self, handle: Handle) -> bool {
+        self.get(handle).is_some()
+    }
+
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        self.slots
+            .get(handle.index)
+            .filter(|slot| slot.generation == handle.generation)
+            .and_then(|slot| slot.value.as_ref())
+    }
+
+    pub fn get_mut(&mut self, handle: Handle) -> Option<&mut T> {
+        self.slots
+            .get_mut(handle.index)
+            .filter(|slot| slot.generation == handle.generation)
+            .and_then(|slot| slot.value.as_mut())
+    }
+
+    /// Handles of the values in slot order
+    pub fn handles(&self) -> impl Iterator<Item = Handle> + '_ {
+        self.iter().map(|(handle, _)| handle)
+    }
+
+    /// Handles and values in slot order
+    pub fn iter(&self) -> impl Iterator<Item = (Handle, &T)> {
+        self.slots.iter().enumerate().filter_map(|(index, slot)| {
+            let handle = Handle {
+                index,
+                generation: slot.generation,
+            };
+
+            slot.value.as_ref().map(|value| (handle, value))
+        })
+    }
+
+    /// Handles and mutable values in slot order
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Handle, &mut T)> {
+        self.slots
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(index, slot)| {
+                let handle = Handle {
+                    index,
+                    generation: slot.generation,
+                };
+
+                slot.value.as_mut().map(|value| (handle, value))
+            })
+    }
+
+    /// Values in slot order
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.iter().map(|(_, value)| value)
+    }
+
+    /// Keeps only the values for which `f` returns true
+    pub fn retain<F: FnMut(Handle, &mut T) -> bool>(&mut self, mut f: F) {
+        let mut removed = Vec::new();
+
+        for (handle, value) in self.iter_mut() {
+            if !f(handle, value) {
+                removed.push(handle);
+            }
+        }
+
+        for handle in removed {
+            self.remove(handle);
+        }
+    }
+
+    /// Removes every value, the handles stop being valid
+    pub fn clear(&mut self) {
+        self.drain();
+    }
+
+    /// Removes and returns every value in slot order, the values not taken
+    /// are dropped with the iterator
+    pub fn drain(&mut self) -> Drain<'_, T> {
+        self.len = 0;
+
+        Drain {
+            slots: self.slots.iter_mut().enumerate(),
+            free: &mut self.free,
+        }
+    }
+}
+
+impl<T> Default for Arena<T> {
+    fn default() -> Self {
+        Arena::new()
+    }
+}
+
+/// Iterator removing the values of an arena
+pub struct Drain<'a, T> {
+    slots: Enumerate<slice::IterMut<'a, Slot<T>>>,
+    free: &'a mut Vec<usize>,
+}
+
+impl<'a, T> Iterator for Drain<'a, T> {
+    type Item = (Handle, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for (index, slot) in &mut self.slots {
+            if let Some(value) = slot.value.take() {
+                let handle = Handle {
+                    index,
+                    generation: slot.generation,
+                };
+
+                slot.generation += 1;
+                self.free.push(index);
+
+                return Some((handle, value));
+            }
+        }
+
+        None
+    }
+}
+
+impl<'a, T> Drop for Drain<'a, T> {
+    fn drop(&mut self) {
+        self.for_each(drop);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_and_get() {
+        let mut arena = Arena::new();
+
+        let a = arena.insert("a");
+        let b = arena.insert("b");
+
+        assert_eq!(2, arena.len());
+        assert_eq!(Some(&"a"), arena.get(a));
+        assert_eq!(Some(&"b"), arena.get(b));
+        assert_ne!(a, b);
+
+        *arena.get_mut(b).unwrap() = "c";
+        assert_eq!(Some(&"c"), arena.get(b));
+    }
+
+    #[test]
+    fn stale_handles_are_detected() {
+        let mut arena = Arena::new();
+
+        let a = arena.insert(1);
+        assert_eq!(Some(1), arena.remove(a));
+        assert_eq!(None, arena.remove(a));
+
+        // The slot is reused with a new generation
+        let b = arena.insert(2);
+        assert_eq!(a.index(), b.index());
+        assert_ne!(a.generation(), b.generation());
+
+        assert!(!arena.contains(a));
+        assert_eq!(None, arena.get(a));
+        assert_eq!(None, arena.get_mut(a));
+        assert_eq!(None, arena.remove(a));
+        assert_eq!(Some(&2), arena.get(b));
+        assert_eq!(1, arena.len());
+    }
+
+    #[test]
+    fn iteration_order_is_stable() {
+        let mut arena = Arena::new();
+        let handles: Vec<_> = (0..5).map(|i| arena.insert(i)).collect();
+
+        arena.remove(handles[1]);
+        arena.remove(handles[3]);
+
+        assert_eq!(vec![0, 2, 4], arena.values().cloned().collect::<Vec<_>>());
+
+        let six = arena.insert(6);
+        assert_eq!(handles[3].index(), six.index());
+        assert_eq!(
+            vec![0, 2, 6, 4],
+            arena.values().cloned().collect::<Vec<_>>()
+        );
+
+        for (_, value) in arena.iter_mut() {
+            *value *= 10;
+        }
+        assert_eq!(
+            vec![handles[0], handles[2], six, handles[4]],
+            arena.handles().collect::<Vec<_>>()
+        );
+        assert_eq!(Some(&60), arena.get(six));
+    }
+
+    #[test]
+    fn retain_and_drain() {
+        let mut arena = Arena::new();
+        let handles: Vec<_> = (0..6).map(|i| arena.insert(i)).collect();
+
+        arena.retain(|_, value| *value % 2 == 0);
+        assert_eq!(3, arena.len());
+        assert!(!arena.contains(handles[1]));
+
+        let drained: Vec<_> = arena.drain().collect();
+        assert_eq!(
+            vec![(handles[0], 0), (handles[2], 2), (handles[4], 4)],
+            drained
+        );
+        assert!(arena.is_empty());
+        assert_eq!(None, arena.get(handles[0]));
+
+        let a = arena.insert(7);
+        let b = arena.insert(8);
+        arena.drain().next();
+        assert!(!arena.contains(a) && !arena.contains(b));
+        assert_eq!(0, arena.values().count());
+    }
+}
@@ -0,0 +1,160 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+/// Global allocator counting the allocations made through it
+///
+/// Install it in a binary with `#[global_allocator]` to get allocation counts
+/// from `measure`.
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(
+        &self,
+        ptr: *mut u8,
+        layout: Layout,
+        new_size: usize,
+    ) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(new_size, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+/// Measurement of a single run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Sample {
+    pub elapsed: Duration,
+    pub allocations: usize,
+    pub allocated_bytes: usize,
+}
+
+/// Runs `f` once and measures the time and allocations it took
+pub fn measure<T, F: FnOnce() -> T>(f: F) -> (T, Sample) {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let bytes = ALLOCATED_BYTES.load(Ordering::Relaxed);
+    let start = Instant::now();
+
+    let value = f();
+
+    let elapsed = start.elapsed();
+    let sample = Sample {
+        elapsed,
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        allocated_bytes: ALLOCATED_BYTES.load(Ordering::Relaxed) - bytes,
+    };
+
+    (value, sample)
+}
+
+/// Summary of several runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+    /// Median allocation count of a single run
+    pub allocations: usize,
+    /// Median allocated bytes of a single run
+    pub allocated_bytes: usize,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Sample]) -> Self {
+        if samples.is_empty() {
+            return Stats::default();
+        }
+
+        let mut times: Vec<_> = samples.iter().map(|s| s.elapsed).collect();
+        let mut allocations: Vec<_> =
+            samples.iter().map(|s| s.allocations).collect();
+        let mut bytes: Vec<_> =
+            samples.iter().map(|s| s.allocated_bytes).collect();
+
+        times.sort();
+        allocations.sort();
+        bytes.sort();
+
+        Stats {
+            runs: samples.len(),
+            min: times[0],
+            median: median(&times),
+            max: times[times.len() - 1],
+            allocations: allocations[allocations.len() / 2],
+            allocated_bytes: bytes[bytes.len() / 2],
+        }
+    }
+}
+
+/// Median of a non-empty sorted slice, the mean of the two middle values
+/// when the length is even
+fn median(sorted: &[Duration]) -> Duration {
+    let len = sorted.len();
+
+    (sorted[(len - 1) / 2] + sorted[len / 2]) / 2
+}
+
+/// Duration in fractional milliseconds
+pub fn millis(duration: Duration) -> f64 {
+    duration.as_secs() as f64 * 1000.0
+        + f64::from(duration.subsec_nanos()) / 1e6
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(ms: u64, allocations: usize) -> Sample {
+        Sample {
+            elapsed: Duration::from_millis(ms),
+            allocations,
+            allocated_bytes: allocations * 8,
+        }
+    }
+
+    #[test]
+    fn stats_from_samples() {
+        let samples = [sample(5, 3), sample(1, 1), sample(4, 2), sample(2, 2)];
+
+        let stats = Stats::from_samples(&samples);
+
+        assert_eq!(4, stats.runs);
+        assert_eq!(Duration::from_millis(1), stats.min);
+        assert_eq!(Duration::from_millis(3), stats.median);
+        assert_eq!(Duration::from_millis(5), stats.max);
+        assert_eq!(2, stats.allocations);
+        assert_eq!(16, stats.allocated_bytes);
+    }
+
+    #[test]
+    fn stats_from_no_samples() {
+        assert_eq!(Stats::default(), Stats::from_samples(&[]));
+    }
+
+    #[test]
+    fn measure_returns_value() {
+        let (value, _) = measure(|| vec![1, 2, 3].len());
+
+        assert_eq!(3, value);
+    }
+}
@@ -0,0 +1,182 @@
+//! Property tests for comparing solvers against brute-force references
+//!
+//! `check` runs a property on random inputs and shrinks the first failing
+//! input by trying the smaller inputs of a shrink function until none of
+//! them fails, so the failure names a minimal input.
+
+use std::fmt::Debug;
+
+use crate::generate::Rng;
+
+/// Result of a property on one input, `Err` describes the failure
+pub type Outcome = std::result::Result<(), String>;
+
+/// Most shrinking steps taken before reporting the failing input
+const MAX_SHRINKS: usize = 1000;
+
+/// Checks `property` on `cases` inputs made by `generate`, panics with the
+/// smallest failing input found
+pub fn check<T, G, S, P>(
+    seed: u64,
+    cases: usize,
+    mut generate: G,
+    shrink: S,
+    property: P,
+) where
+    T: Debug,
+    G: FnMut(&mut Rng) -> T,
+    S: Fn(&T) -> Vec<T>,
+    P: Fn(&T) -> Outcome,
+{
+    let mut rng = Rng::new(seed);
+
+    for case in 0..cases {
+        let input = generate(&mut rng);
+
+        if let Err(reason) = property(&input) {
+            let (input, reason, steps) =
+                minimize(input, reason, &shrink, &property);
+
+            panic!(
+                "property failed on case {} of seed {}, shrunk {} times\n\
+                 input: {:?}\n{}",
+                case, seed, steps, input, reason
+            );
+        }
+    }
+}
+
+/// Shrinks a failing input while some smaller input still fails
+fn minimize<T, S, P>(
+    mut input: T,
+    mut reason: String,
+    shrink: &S,
+    property: &P,
+) -> (T, String, usize)
+where
+    S: Fn(&T) -> Vec<T>,
+    P: Fn(&T) -> Outcome,
+{
+    let mut steps = 0;
+
+    'shrink: while steps < MAX_SHRINKS {
+        for candidate in shrink(&input) {
+            if let Err(why) = property(&candidate) {
+                input = candidate;
+                reason = why;
+                steps += 1;
+                continue 'shrink;
+            }
+        }
+
+        break;
+    }
+
+    (input, reason, steps)
+}
+
+/// Fails when the optimized answer differs from the reference answer
+pub fn agree<A: PartialEq + Debug>(optimized: A, reference: A) -> Outcome {
+    if optimized == reference {
+        Ok(())
+    } else {
+        Err(format!(
+            "optimized: {:?}\nreference: {:?}",
+            optimized, reference
+        ))
+    }
+}
+
+/// Values between `target` and `value`, closest to `target` first
+pub fn shrink_int(value: i64, target: i64) -> Vec<i64> {
+    let mut values = Vec::new();
+    let mut delta = value - target;
+
+    while delta != 0 {
+        values.push(value - delta);
+        delta /= 2;
+    }
+
+    values
+}
+
+/// Shorter lists with at least `min_len` items first, then lists with one
+/// item shrunk by `shrink_item`
+pub fn shrink_vec<T, F>(
+    items: &[T],
+    min_len: usize,
+    shrink_item: F,
+) -> Vec<Vec<T>>
+where
+    T: Clone,
+    F: Fn(&T) -> Vec<T>,
+{
+    let mut lists = Vec::new();
+
+    let mut removed = items.len().saturating_sub(min_len);
+    while removed > 0 {
+        for start in (0..=items.len() - removed).step_by(removed) {
+            let mut shorter = items[..start].to_vec();
+            shorter.extend_from_slice(&items[start + removed..]);
+            lists.push(shorter);
+        }
+        removed /= 2;
+    }
+
+    for (index, item) in items.iter().enumerate() {
+        for smaller in shrink_item(item) {
+            let mut list = items.to_vec();
+            list[index] = smaller;
+            lists.push(list);
+        }
+    }
+
+    lists
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shrinks_ints_towards_target() {
+        assert_eq!(vec![0, 50, 75, 88, 94, 97, 99], shrink_int(100, 0));
+        assert_eq!(vec![3, 4], shrink_int(5, 3));
+        assert!(shrink_int(7, 7).is_empty());
+    }
+
+    #[test]
+    fn finds_minimal_failing_input() {
+        let shrink = |items: &Vec<i64>| {
+            shrink_vec(items, 0, |&item| shrink_int(item, 0))
+        };
+        let property = |items: &Vec<i64>| {
+            if items.iter().all(|&item| item < 5) {
+                Ok(())
+            } else {
+                Err("item of at least 5".into())
+            }
+        };
+
+        let (input, _, _) = minimize(
+            vec![3, 8, -2, 7, 1],
+            "item of at least 5".into(),
+            &shrink,
+            &property,
+        );
+
+        assert_eq!(vec![5], input);
+    }
+
+    #[test]
+    #[should_panic(expected = "input: [")]
+    fn check_reports_failure() {
+        check(
+            1,
+            100,
+            |rng| (0..rng.below(10)).map(|_| rng.range(0, 9)).collect(),
+            |items: &Vec<i64>| shrink_vec(items, 0, |_| Vec::new()),
+            |items| agree(items.len() < 5, true),
+        );
+    }
+}
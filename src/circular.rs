@@ -0,0 +1,322 @@
+use std::fmt;
+use std::iter::FromIterator;
+
+/// Circular doubly linked list with a cursor
+///
+/// The values form a ring, "next" is clockwise and "previous" counter
+/// clockwise. The cursor is always at a value unless the list is empty.
+/// Moving the cursor by one step, inserting and removing take constant time,
+/// `rotate` takes time linear to the shorter way around the ring.
+#[derive(Clone)]
+pub struct CircularList<T> {
+    nodes: Vec<Node<T>>,
+    /// Slots of removed nodes, reused by later inserts
+    free: Vec<usize>,
+    cursor: Option<usize>,
+    len: usize,
+}
+
+#[derive(Debug, Clone)]
+struct Node<T> {
+    prev: usize,
+    next: usize,
+    value: Option<T>,
+}
+
+impl<T> CircularList<T> {
+    pub fn new() -> Self {
+        CircularList::with_capacity(0)
+    }
+
+    /// Empty list with room for `capacity` values
+    pub fn with_capacity(capacity: usize) -> Self {
+        CircularList {
+            nodes: Vec::with_capacity(capacity),
+            free: Vec::new(),
+            cursor: None,
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Value at the cursor, `None` if the list is empty
+    pub fn current(&self) -> Option<&T> {
+        self.cursor.and_then(|c| self.nodes[c].value.as_ref())
+    }
+
+    pub fn current_mut(&mut self) -> Option<&mut T> {
+        let cursor = self.cursor?;
+        self.nodes[cursor].value.as_mut()
+    }
+
+    /// Moves the cursor to the next value
+    pub fn move_next(&mut self) {
+        if let Some(c) = self.cursor {
+            self.cursor = Some(self.nodes[c].next);
+        }
+    }
+
+    /// Moves the cursor to the previous value
+    pub fn move_prev(&mut self) {
+        if let Some(c) = self.cursor {
+            self.cursor = Some(self.nodes[c].prev);
+        }
+    }
+
+    /// Moves the cursor `steps` values forward, backwards if negative,
+    /// going the shorter way around
+    pub fn rotate(&mut self, steps: isize) {
+        self.cursor = self.cursor.map(|c| self.walk(c, steps));
+    }
+
+    /// Inserts `value` after the cursor and moves the cursor to it
+    pub fn insert_after(&mut self, value: T) {
+        let index = match self.cursor {
+            Some(c) => {
+                let next = self.nodes[c].next;
+                let index = self.alloc(c, next, value);
+                self.nodes[c].next = index;
+                self.nodes[next].prev = index;
+                index
+            }
+            None => self.alloc_single(value),
+        };
+
+        self.cursor = Some(index);
+        self.len += 1;
+    }
+
+    /// Inserts `value` before the cursor and moves the cursor to it
+    pub fn insert_before(&mut self, value: T) {
+        self.move_prev();
+        self.insert_after(value);
+    }
+
+    /// Removes the value at the cursor, the cursor moves to the next value
+    pub fn remove(&mut self) -> Option<T> {
+        let c = self.cursor?;
+        let (prev, next) = (self.nodes[c].prev, self.nodes[c].next);
+
+        self.nodes[prev].next = next;
+        self.nodes[next].prev = prev;
+
+        self.len -= 1;
+        self.cursor = if self.len == 0 { None } else { Some(next) };
+        self.free.push(c);
+
+        self.nodes[c].value.take()
+    }
+
+    /// Values once around the ring starting from the cursor
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.iter_from(0)
+    }
+
+    /// Values once around the ring starting `steps` values from the cursor,
+    /// backwards from the cursor if negative
+    pub fn iter_from(&self, steps: isize) -> Iter<'_, T> {
+        Iter {
+            list: self,
+            next: self.cursor.map(|c| self.walk(c, steps)),
+            remaining: self.len,
+        }
+    }
+
+    /// Node `steps` forward from `from`, going the shorter way around
+    fn walk(&self, from: usize, steps: isize) -> usize {
+        let len = self.len as isize;
+        let forward = steps.rem_euclid(len);
+
+        if forward <= len / 2 {
+            (0..forward).fold(from, |node, _| self.nodes[node].next)
+        } else {
+            (forward..len).fold(from, |node, _| self.nodes[node].prev)
+        }
+    }
+
+    fn alloc(&mut self, prev: usize, next: usize, value: T) -> usize {
+        let node = Node {
+            prev,
+            next,
+            value: Some(value),
+        };
+
+        match self.free.pop() {
+            Some(index) => {
+                self.nodes[index] = node;
+                index
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    /// Allocates a node linked only to itself
+    fn alloc_single(&mut self, value: T) -> usize {
+        let index = self.free.last().cloned().unwrap_or(self.nodes.len());
+        self.alloc(index, index, value)
+    }
+}
+
+impl<T> Default for CircularList<T> {
+    fn default() -> Self {
+        CircularList::new()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for CircularList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// Values in order with the cursor at the first one
+impl<T> FromIterator<T> for CircularList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = CircularList::new();
+
+        for value in iter {
+            list.insert_after(value);
+        }
+
+        list.move_next();
+        list
+    }
+}
+
+/// Iterator over the values of a list from the cursor
+pub struct Iter<'a, T> {
+    list: &'a CircularList<T>,
+    next: Option<usize>,
+    remaining: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let node = &self.list.nodes[self.next?];
+        self.next = Some(node.next);
+        self.remaining -= 1;
+
+        node.value.as_ref()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(list: &CircularList<i32>) -> Vec<i32> {
+        list.iter().cloned().collect()
+    }
+
+    #[test]
+    fn insert_and_move() {
+        let mut list = CircularList::new();
+        assert_eq!(None, list.current());
+
+        list.insert_after(1);
+        list.insert_after(2);
+        list.insert_after(3);
+        assert_eq!(vec![3, 1, 2], values(&list));
+
+        list.move_next();
+        assert_eq!(Some(&1), list.current());
+        list.move_prev();
+        list.move_prev();
+        assert_eq!(Some(&2), list.current());
+
+        list.insert_before(4);
+        assert_eq!(vec![4, 2, 3, 1], values(&list));
+        assert_eq!(4, list.len());
+    }
+
+    #[test]
+    fn rotate_either_way() {
+        let mut list: CircularList<i32> = (0..5).collect();
+        assert_eq!(Some(&0), list.current());
+
+        list.rotate(2);
+        assert_eq!(Some(&2), list.current());
+        list.rotate(-3);
+        assert_eq!(Some(&4), list.current());
+        list.rotate(11);
+        assert_eq!(Some(&0), list.current());
+        list.rotate(4);
+        assert_eq!(Some(&4), list.current());
+
+        CircularList::<i32>::new().rotate(3);
+    }
+
+    #[test]
+    fn iterate_from_any_value() {
+        let list: CircularList<i32> = (0..5).collect();
+
+        assert_eq!(
+            vec![2, 3, 4, 0, 1],
+            list.iter_from(2).cloned().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![4, 0, 1, 2, 3],
+            list.iter_from(-1).cloned().collect::<Vec<_>>()
+        );
+        assert_eq!(5, list.iter_from(7).len());
+        assert_eq!(Some(&0), list.current());
+    }
+
+    #[test]
+    fn remove_moves_to_next() {
+        let mut list: CircularList<i32> = (0..4).collect();
+
+        list.rotate(3);
+        assert_eq!(Some(3), list.remove());
+        assert_eq!(vec![0, 1, 2], values(&list));
+
+        *list.current_mut().unwrap() = 10;
+        list.insert_after(5);
+        assert_eq!(vec![5, 1, 2, 10], values(&list));
+
+        while list.remove().is_some() {}
+        assert!(list.is_empty());
+        assert_eq!(0, list.iter().count());
+
+        list.insert_before(7);
+        assert_eq!(vec![7], values(&list));
+    }
+
+    #[test]
+    fn slots_are_reused() {
+        let mut list = CircularList::with_capacity(3);
+        list.insert_after(-1);
+
+        for i in 0..100 {
+            list.insert_after(i);
+            list.insert_after(i);
+            list.remove();
+            list.remove();
+        }
+
+        assert!(list.nodes.len() <= 3);
+        assert_eq!(vec![99], values(&list));
+    }
+}
@@ -0,0 +1,362 @@
+//! Finding cycles in sequences of states where each state is computed from
+//! the previous one
+//!
+//! The sequence must eventually repeat, otherwise the searches never end.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Where a sequence starts repeating and how long the repeating part is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub struct Cycle {
+    /// Index of the first state in the cycle
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Index of the earliest state equal to the state at `n`
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+
+    /// Number of full cycles completed before the state at `n`
+    pub fn laps(&self, n: usize) -> usize {
+        if n < self.start {
+            0
+        } else {
+            (n - self.start) / self.length
+        }
+    }
+}
+
+/// States of a sequence up to its first repeat and the cycle they form
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unrolled<S> {
+    /// Every distinct state in order
+    pub states: Vec<S>,
+    pub cycle: Cycle,
+}
+
+impl<S> Unrolled<S> {
+    /// State at index `n` of the sequence
+    pub fn nth(&self, n: usize) -> &S {
+        &self.states[self.cycle.reduce(n)]
+    }
+}
+
+/// Finds the cycle by remembering every state, calls `step` once per
+/// distinct state
+pub fn find<S, F>(initial: S, step: F) -> Cycle
+where
+    S: Clone + Hash + Eq,
+    F: FnMut(&S) -> S,
+{
+    find_by_key(initial, step, S::clone)
+}
+
+/// Finds the cycle by remembering the key of every state, states with
+/// equal keys are considered equal
+///
+/// Use a fingerprint of the state as the key when cloning the states
+/// would be expensive.
+pub fn find_by_key<S, K, F, G>(initial: S, mut step: F, mut key: G) -> Cycle
+where
+    K: Hash + Eq,
+    F: FnMut(&S) -> S,
+    G: FnMut(&S) -> K,
+{
+    let mut seen = HashMap::new();
+    let mut state = initial;
+
+    for index in 0.. {
+        let k = key(&state);
+
+        if let Some(&start) = seen.get(&k) {
+            return Cycle {
+                start,
+                length: index - start,
+            };
+        }
+
+        seen.insert(k, index);
+        state = step(&state);
+    }
+
+    unreachable!()
+}
+
+/// Collects every distinct state of the sequence
+pub fn unroll<S, F>(initial: S, mut step: F) -> Unrolled<S>
+where
+    S: Clone + Hash + Eq,
+    F: FnMut(&S) -> S,
+{
+    let mut seen = HashMap::new();
+    let mut states = Vec::new();
+    let mut state = initial;
+
+    loop {
+        if let Some(&start) = seen.get(&state) {
+            let length = states.len() - start;
+
+            return Unrolled {
+                states,
+                cycle: Cycle { start, length },
+            };
+        }
+
+        let next = step(&state);
+        seen.insert(state.clone(), states.len());
+        states.push(state);
+        state = next;
+    }
+}
+
+/// State at index `n` of the sequence, stops stepping at the first repeat
+pub fn nth<S, F>(initial: S, step: F, n: usize) -> S
+where
+    S: Clone + Hash + Eq,
+    F: FnMut(&S) -> S,
+{
+    nth_by_key(initial, step, S::clone, n)
+}
+
+/// Like `nth`, but states with equal keys are considered equal
+pub fn nth_by_key<S, K, F, G>(
+    initial: S,
+    mut step: F,
+    mut key: G,
+    n: usize,
+) -> S
+where
+    K: Hash + Eq,
+    F: FnMut(&S) -> S,
+    G: FnMut(&S) -> K,
+{
+    let mut seen = HashMap::new();
+    let mut states = Vec::new();
+    let mut state = initial;
+
+    loop {
+        if states.len() == n {
+            return state;
+        }
+
+        let k = key(&state);
+
+        if let Some(&start) = seen.get(&k) {
+            let length = states.len() - start;
+            let index = Cycle { start, length }.reduce(n);
+
+            return states.swap_remove(index);
+        }
+
+        let next = step(&state);
+        seen.insert(k, states.len());
+        states.push(state);
+        state = next;
+    }
+}
+
+/// State at index `n` of a sequence which may repeat in a different
+/// position
+///
+/// `normalize` splits a state into its shape and position. Once a shape
+/// repeats, every lap of the cycle moves the position by the same amount.
+/// Returns the shape and the position of the state at `n`.
+pub fn nth_translated<S, K, F, N>(
+    initial: S,
+    mut step: F,
+    mut normalize: N,
+    n: usize,
+) -> (K, i64)
+where
+    K: Clone + Hash + Eq,
+    F: FnMut(&S) -> S,
+    N: FnMut(&S) -> (K, i64),
+{
+    let mut seen = HashMap::new();
+    let mut shapes: Vec<(K, i64)> = Vec::new();
+    let mut state = initial;
+
+    loop {
+        let (shape, position) = normalize(&state);
+
+        if shapes.len() == n {
+            return (shape, position);
+        }
+
+        if let Some(&start) = seen.get(&shape) {
+            let cycle = Cycle {
+                start,
+                length: shapes.len() - start,
+            };
+            let drift = position - shapes[start].1;
+            let (shape, position) = shapes[cycle.reduce(n)].clone();
+
+            return (shape, position + drift * cycle.laps(n) as i64);
+        }
+
+        seen.insert(shape.clone(), shapes.len());
+        shapes.push((shape, position));
+        state = step(&state);
+    }
+}
+
+/// Floyd's tortoise and hare, keeps only two states in memory but calls
+/// `step` several times per state
+pub fn floyd<S, F>(initial: S, mut step: F) -> Cycle
+where
+    S: Eq,
+    F: FnMut(&S) -> S,
+{
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        hare = step(&hare);
+    }
+
+    let mut start = 0;
+    tortoise = initial;
+
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    hare = step(&tortoise);
+
+    while tortoise != hare {
+        hare = step(&hare);
+        length += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// Brent's algorithm, keeps only two states in memory and usually calls
+/// `step` fewer times than `floyd`
+pub fn brent<S, F>(initial: S, mut step: F) -> Cycle
+where
+    S: Clone + Eq,
+    F: FnMut(&S) -> S,
+{
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+
+        hare = step(&hare);
+        length += 1;
+    }
+
+    let mut start = 0;
+    tortoise = initial.clone();
+    hare = initial;
+
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 -> 1 -> 2 -> 3 -> 4 -> 5 -> 6 -> 3
+    fn step(n: &u32) -> u32 {
+        if *n == 6 {
+            3
+        } else {
+            n + 1
+        }
+    }
+
+    #[test]
+    fn algorithms_agree() {
+        let expected = Cycle {
+            start: 3,
+            length: 4,
+        };
+
+        assert_eq!(expected, find(0, step));
+        assert_eq!(expected, find_by_key(0, step, |n| n % 100));
+        assert_eq!(expected, floyd(0, step));
+        assert_eq!(expected, brent(0, step));
+        assert_eq!(
+            Cycle {
+                start: 0,
+                length: 4
+            },
+            brent(5, step)
+        );
+    }
+
+    #[test]
+    fn far_future_states() {
+        assert_eq!(2, nth(0, step, 2));
+        assert_eq!(3, nth(0, step, 7));
+        assert_eq!(5, nth(0, step, 1_000_000_001));
+
+        let unrolled = unroll(0, step);
+        assert_eq!(vec![0, 1, 2, 3, 4, 5, 6], unrolled.states);
+        assert_eq!(5, *unrolled.nth(1_000_000_001));
+
+        assert_eq!(
+            0,
+            Cycle {
+                start: 3,
+                length: 4
+            }
+            .laps(6)
+        );
+        assert_eq!(
+            2,
+            Cycle {
+                start: 3,
+                length: 4
+            }
+            .laps(11)
+        );
+    }
+
+    #[test]
+    fn translated_cycles() {
+        // A glider alternating between two shapes, moving right by 3 every
+        // other step
+        let step =
+            |&(phase, x): &(bool, i64)| (!phase, if phase { x + 3 } else { x });
+
+        let (shape, x) = nth_translated((false, 10), step, |&(p, x)| (p, x), 5);
+        assert_eq!((true, 16), (shape, x));
+
+        let (shape, x) =
+            nth_translated((false, 10), step, |&(p, x)| (p, x), 1_000_000_000);
+        assert_eq!((false, 10 + 3 * 500_000_000), (shape, x));
+    }
+}
@@ -0,0 +1,98 @@
+use std::collections::HashSet;
+
+use crate::{parse, Answer, Error, Result, Solution};
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    fn day(&self) -> u32 {
+        1
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        let numbers = parse_numbers(input.trim())?;
+        part1(&numbers).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        let numbers = parse_numbers(input.trim())?;
+        part2(&numbers).map(Answer::from)
+    }
+
+    fn parse(&self, input: &str) -> Result<bool> {
+        parse_numbers(input.trim())?;
+        Ok(true)
+    }
+}
+
+fn parse_numbers(s: &str) -> Result<Vec<i32>> {
+    crate::parse_lines(s, parse::signed)
+}
+
+fn part1(nr: &[i32]) -> Result<i32> {
+    let mut freq = 0;
+
+    for change in &nr[..] {
+        freq += change;
+    }
+
+    Ok(freq)
+}
+
+fn part2(nr: &[i32]) -> Result<i32> {
+    if nr.is_empty() {
+        return Err(Error::unsolvable("No frequency changes"));
+    }
+
+    let total: i32 = nr.iter().sum();
+
+    let (min, max, _) =
+        nr.iter().fold((0, 0, 0), |(min, max, freq), change| {
+            let freq = freq + change;
+            (min.min(freq), max.max(freq), freq)
+        });
+
+    // Every pass shifts the frequencies of the first one by the total, a
+    // frequency is reached twice before they have been shifted past the
+    // range of the first pass or never
+    let passes = if total == 0 {
+        1
+    } else {
+        (max - min) / total.abs() + 2
+    };
+
+    let mut freq = 0;
+    let mut seen = HashSet::new();
+    seen.insert(0);
+
+    for _ in 0..passes {
+        for change in &nr[..] {
+            freq += change;
+            if !seen.insert(freq) {
+                return Ok(freq);
+            }
+        }
+    }
+
+    Err(Error::unsolvable("No frequency is reached twice"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part2_example_inputs() {
+        assert_eq!(0, part2(&[1, -1]).unwrap());
+        assert_eq!(10, part2(&[3, 3, 4, -2, -4]).unwrap());
+        assert_eq!(5, part2(&[-6, 3, 8, 5, -6]).unwrap());
+        assert_eq!(14, part2(&[7, 7, -2, -7, -4]).unwrap());
+    }
+
+    #[test]
+    fn part2_without_repeats() {
+        assert!(part2(&[]).is_err());
+        assert!(part2(&[1]).is_err());
+        assert!(part2(&[2, 2, -1]).is_err());
+    }
+}
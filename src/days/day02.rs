@@ -1,13 +1,20 @@
-use aoc::Result;
+use crate::{Answer, Error, Result, Solution};
 use std::collections::HashMap;
 
-fn main() -> Result<()> {
-    let s = aoc::read_input()?;
+pub struct Day02;
 
-    part1(&s)?;
-    part2(&s)?;
+impl Solution for Day02 {
+    fn day(&self) -> u32 {
+        2
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        part1(input.trim()).map(Answer::from)
+    }
 
-    Ok(())
+    fn part2(&self, input: &str) -> Result<Answer> {
+        part2(input.trim()).map(Answer::from)
+    }
 }
 
 fn part1(s: &str) -> Result<usize> {
@@ -23,8 +30,6 @@ fn part1(s: &str) -> Result<usize> {
 
     let out = twices * thrices;
 
-    eprintln!("part1 {}", out);
-
     Ok(out)
 }
 
@@ -32,13 +37,12 @@ fn part2(s: &str) -> Result<String> {
     for (i, line) in s.lines().enumerate() {
         for alt in s.lines().skip(i) {
             if let Some(s) = diff_by_one(&line, &alt) {
-                eprintln!("part2 {}", s);
                 return Ok(s);
             }
         }
     }
 
-    Ok("".into())
+    Err(Error::unsolvable("No ids differing by one character"))
 }
 
 fn diff_by_one(lhs: &str, rhs: &str) -> Option<String> {
@@ -0,0 +1,167 @@
+use crate::{
+    debug, scan, trace, Answer, Color, Error, Image, Rect, Result, Solution, V2,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Claim {
+    id: i32,
+    rect: Rect<i32>,
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    fn day(&self) -> u32 {
+        3
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        part1(input.trim()).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        part2(input.trim()).map(Answer::from)
+    }
+
+    fn parse(&self, input: &str) -> Result<bool> {
+        crate::parse_lines(input.trim(), parse_claim)?;
+        Ok(true)
+    }
+
+    fn image(&self, input: &str) -> Result<Option<Image>> {
+        draw(input.trim()).map(Some)
+    }
+}
+
+fn part1(s: &str) -> Result<usize> {
+    let claims = crate::parse_lines(s, parse_claim)?;
+    let grid = claim_counts(&claims)?;
+
+    let result = grid
+        .iter()
+        .map(|row| row.iter().filter(|v| **v >= 2).count())
+        .sum();
+
+    Ok(result)
+}
+
+fn part2(s: &str) -> Result<i32> {
+    let claims = crate::parse_lines(s, parse_claim)?;
+
+    for claim in claims.iter() {
+        if is_only_claimer(claim, &claims) {
+            return Ok(claim.id);
+        }
+    }
+
+    Err(Error::unsolvable("No claim without overlap"))
+}
+
+/// Counts the claims on each square inch of the fabric
+fn claim_counts(claims: &[Claim]) -> Result<Vec<Vec<i32>>> {
+    let fabric = claims
+        .iter()
+        .map(|c| c.rect)
+        .reduce(|a, b| a.union(b))
+        .ok_or_else(|| Error::invalid("No claims"))?
+        .including(V2::zero());
+
+    debug!("Fabric {:?}", fabric);
+
+    let mut grid =
+        vec![vec![0_i32; fabric.width() as usize]; fabric.height() as usize];
+
+    for claim in claims.iter() {
+        mark(claim, &mut grid);
+    }
+
+    Ok(grid)
+}
+
+/// Draws unclaimed fabric dark, single claims blue and overlaps red
+fn draw(s: &str) -> Result<Image> {
+    let claims = crate::parse_lines(s, parse_claim)?;
+    let grid = claim_counts(&claims)?;
+
+    let width = grid.first().map_or(0, |row| row.len());
+
+    Ok(Image::from_fn(width, grid.len(), |x, y| match grid[y][x] {
+        0 => Color::rgb(20, 20, 30),
+        1 => Color::rgb(70, 130, 180),
+        _ => Color::rgb(220, 50, 50),
+    }))
+}
+
+#[allow(dead_code)]
+fn display_grid(grid: &[Vec<i32>]) -> Result<()> {
+    for row in grid.iter() {
+        let mut s = String::new();
+        for col in row.iter() {
+            if *col == 0 {
+                s.push('.');
+            } else if *col == 1 {
+                s.push('#');
+            } else {
+                let c = std::char::from_digit(*col as u32, 10)
+                    .ok_or_else(|| Error::invalid("Too many overlaps"))?;
+                s.push(c);
+            }
+        }
+        trace!("{}", s);
+    }
+    Ok(())
+}
+
+fn mark(claim: &Claim, grid: &mut [Vec<i32>]) {
+    for p in claim.rect.points() {
+        grid[p.y() as usize][p.x() as usize] += 1;
+    }
+}
+
+fn is_only_claimer(claim: &Claim, claims: &[Claim]) -> bool {
+    claims.iter().all(|other| {
+        other.id == claim.id || claim.rect.intersection(other.rect).is_none()
+    })
+}
+
+fn parse_claim(s: &str) -> Result<Claim> {
+    let (id, left, top, width, height) =
+        scan!(s, "#{} @ {},{}: {}x{}", i32, i32, i32, i32, i32)?;
+
+    let rect = Rect::from_size((left, top).into(), (width, height).into())
+        .ok_or_else(|| Error::invalid("Empty claim"))?;
+
+    Ok(Claim { id, rect })
+}
+
+#[cfg(test)]
+mod part1_tests {
+    use super::*;
+
+    #[test]
+    fn test_example() {
+        let input = r#"
+#1 @ 1,3: 4x4
+#2 @ 3,1: 4x4
+#3 @ 5,5: 2x2
+        "#;
+
+        assert_eq!(4, part1(input).unwrap());
+    }
+}
+
+#[cfg(test)]
+mod part2_tests {
+    use super::*;
+
+    #[test]
+    fn test_example() {
+        let input = r#"
+#1 @ 1,3: 4x4
+#2 @ 3,1: 4x4
+#3 @ 5,5: 2x2
+        "#;
+
+        assert_eq!(3, part2(input).unwrap());
+    }
+}
@@ -1,51 +1,9 @@
-#[macro_use]
-extern crate lazy_static;
-extern crate regex;
-
-extern crate itertools;
-
 use itertools::Itertools;
 
 use std::collections::HashMap;
-use std::fmt;
 use std::str::FromStr;
 
-use regex::Regex;
-use std::error;
-
-use aoc::{read_input, Result};
-
-#[derive(Debug, Clone, PartialEq, Eq)]
-struct CustomError(String);
-
-impl fmt::Display for CustomError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Error: {}", self.0)
-    }
-}
-
-// This is important for other errors to wrap this one.
-impl error::Error for CustomError {
-    fn description(&self) -> &str {
-        &self.0
-    }
-
-    fn cause(&self) -> Option<&error::Error> {
-        // Generic error, underlying cause isn't tracked.
-        None
-    }
-}
-
-fn get_value<'a>(
-    caps: &regex::Captures<'a>,
-    index: usize,
-) -> std::result::Result<i32, CustomError> {
-    caps.get(index)
-        .and_then(|v| v.as_str().parse::<i32>().ok())
-        .ok_or_else::<CustomError, _>(|| {
-            CustomError(format!("Invalid {}", index))
-        })
-}
+use crate::{scan, trace, Answer, Error, Result, Solution};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 enum EventType {
@@ -55,29 +13,18 @@ enum EventType {
 }
 
 impl FromStr for EventType {
-    type Err = CustomError;
+    type Err = Error;
 
-    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        lazy_static! {
-            //         1
-            // Guard #id
-            static ref RE: Regex = Regex::new(r"Guard #(\d+)").unwrap();
-        }
-        if s.contains("falls asleep") {
-            return Ok(EventType::FallAsleep);
-        }
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "falls asleep" => Ok(EventType::FallAsleep),
+            "wakes up" => Ok(EventType::WakeUp),
+            _ => {
+                let (id,) = scan!(s, "Guard #{} begins shift", i32)?;
 
-        if s.contains("wakes up") {
-            return Ok(EventType::WakeUp);
+                Ok(EventType::ShiftStart(id))
+            }
         }
-
-        let caps = RE
-            .captures(s)
-            .ok_or_else(|| CustomError("Invalid captures".to_owned()))?;
-
-        let id = get_value(&caps, 1)?;
-
-        Ok(EventType::ShiftStart(id))
     }
 }
 
@@ -92,24 +39,15 @@ struct Timestamp {
 }
 
 impl FromStr for Timestamp {
-    type Err = CustomError;
-
-    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        lazy_static! {
-            // 1      2    3   4    5
-            // year-month-day hour:min
-            static ref RE: Regex = Regex::new(r"(\d{4})-(\d{2})-(\d{2}) (\d{2}):(\d{2})").unwrap();
-        }
+    type Err = Error;
 
-        let caps = RE
-            .captures(s)
-            .ok_or_else(|| CustomError("Invalid captures".to_owned()))?;
+    fn from_str(s: &str) -> Result<Self> {
+        let (year, month, day, hour, minute) =
+            scan!(s, "{}-{}-{} {}:{}", i32, i32, i32, i32, i32)?;
 
-        let year = get_value(&caps, 1)?;
-        let month = get_value(&caps, 2)?;
-        let day = get_value(&caps, 3)?;
-        let hour = get_value(&caps, 4)?;
-        let minute = get_value(&caps, 5)?;
+        if minute >= 60 {
+            return Err(Error::parse(format!("invalid minute {}", minute)));
+        }
 
         Ok(Timestamp {
             year,
@@ -128,11 +66,10 @@ struct LogEvent {
 }
 
 impl FromStr for LogEvent {
-    type Err = CustomError;
+    type Err = Error;
 
-    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        let timestamp = s.parse::<Timestamp>()?;
-        let event = s.parse::<EventType>()?;
+    fn from_str(s: &str) -> Result<Self> {
+        let (timestamp, event) = scan!(s, "[{}] {}", Timestamp, EventType)?;
 
         Ok(LogEvent { timestamp, event })
     }
@@ -145,20 +82,29 @@ struct Sleeping {
     duration: i32,
 }
 
-fn main() -> Result<()> {
-    let s = read_input()?;
+pub struct Day04;
+
+impl Solution for Day04 {
+    fn day(&self) -> u32 {
+        4
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        part1(input.trim()).map(Answer::from)
+    }
 
-    part1(&s)?;
-    part2(&s)?;
+    fn part2(&self, input: &str) -> Result<Answer> {
+        part2(input.trim()).map(Answer::from)
+    }
 
-    Ok(())
+    fn parse(&self, input: &str) -> Result<bool> {
+        crate::parse_lines(input.trim(), str::parse::<LogEvent>)?;
+        Ok(true)
+    }
 }
 
 fn part1(s: &str) -> Result<i32> {
-    let events: std::result::Result<Vec<_>, _> =
-        s.lines().map(|v| v.parse::<LogEvent>()).collect();
-
-    let mut events = events?;
+    let mut events = crate::parse_lines(s, str::parse::<LogEvent>)?;
 
     events.sort();
 
@@ -166,16 +112,11 @@ fn part1(s: &str) -> Result<i32> {
 
     let res = id * min;
 
-    eprintln!("part1: {}", res);
-
     Ok(res)
 }
 
 fn part2(s: &str) -> Result<i32> {
-    let events: std::result::Result<Vec<_>, _> =
-        s.lines().map(|v| v.parse::<LogEvent>()).collect();
-
-    let mut events = events?;
+    let mut events = crate::parse_lines(s, str::parse::<LogEvent>)?;
 
     events.sort();
 
@@ -183,8 +124,6 @@ fn part2(s: &str) -> Result<i32> {
 
     let res = id * min;
 
-    eprintln!("part2: {}", res);
-
     Ok(res)
 }
 
@@ -223,9 +162,7 @@ fn gather(events: &[LogEvent]) -> Result<(i32, i32)> {
             let total_sleep: i32 = v.iter().map(|v| v.duration).sum();
             total_sleep
         })
-        .ok_or_else::<Box<CustomError>, _>(|| {
-            CustomError("Unable to find entry".to_string()).into()
-        });
+        .ok_or_else(|| Error::invalid("No guard falls asleep"));
 
     let (id, values) = entry?;
     let mut sleeping = vec![0; 60];
@@ -240,9 +177,7 @@ fn gather(events: &[LogEvent]) -> Result<(i32, i32)> {
         .iter()
         .enumerate()
         .max_by(|(_, val), (_, v2)| val.cmp(&v2))
-        .ok_or_else::<Box<CustomError>, _>(|| {
-            CustomError("Missing minute".to_string()).into()
-        });
+        .ok_or_else(|| Error::invalid("Missing minute"));
 
     let (index, _) = minute?;
 
@@ -262,7 +197,11 @@ fn gather_2(events: &[LogEvent]) -> Result<(i32, i32)> {
                 guard = id;
             }
             EventType::WakeUp => {
-                assert!(guard > 0);
+                if guard == 0 {
+                    return Err(Error::invalid(
+                        "A guard wakes up before any shift started",
+                    ));
+                }
                 sleeping_end = event.timestamp.minute;
 
                 let minutes = map.entry(guard).or_insert_with(|| vec![0; 60]);
@@ -280,9 +219,7 @@ fn gather_2(events: &[LogEvent]) -> Result<(i32, i32)> {
     let entry = map
         .iter()
         .max_by_key(|(_, v)| v.iter().max())
-        .ok_or_else::<Box<CustomError>, _>(|| {
-            CustomError("Unable to find entry".to_string()).into()
-        });
+        .ok_or_else(|| Error::invalid("No guard falls asleep"));
 
     let entry = entry?;
 
@@ -291,9 +228,7 @@ fn gather_2(events: &[LogEvent]) -> Result<(i32, i32)> {
         .iter()
         .enumerate()
         .max_by_key(|(_, &v)| v)
-        .ok_or_else::<Box<CustomError>, _>(|| {
-            CustomError("Missing minute".to_string()).into()
-        });
+        .ok_or_else(|| Error::invalid("Missing minute"));
 
     let minute = minute?;
 
@@ -302,7 +237,7 @@ fn gather_2(events: &[LogEvent]) -> Result<(i32, i32)> {
 
 /// Visualizes the given events
 /// events should be sorted before calling this
-/// ```
+/// ```text
 /// Date   ID   Minute
 ///             000000000011111111112222222222333333333344444444445555555555
 ///             012345678901234567890123456789012345678901234567890123456789
@@ -327,9 +262,9 @@ fn visualize(events: &[LogEvent]) {
         id, "012345678901234567890123456789012345678901234567890123456789"
     );
 
-    eprintln!("{}", header);
-    eprintln!("{}", minutes_top);
-    eprintln!("{}", minutes_bot);
+    trace!("{}", header);
+    trace!("{}", minutes_top);
+    trace!("{}", minutes_bot);
 
     let groups = events
         .iter()
@@ -371,7 +306,7 @@ fn visualize(events: &[LogEvent]) {
 
         let id = format!("#{}", guard);
 
-        eprintln!("{:7}{:6}{}", date, id, s);
+        trace!("{:7}{:6}{}", date, id, s);
 
         sleeping = vec!['.'; 60];
         guard = next_guard;
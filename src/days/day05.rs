@@ -1,14 +1,22 @@
-use std::collections::HashSet;
+use std::collections::BTreeSet;
 
-use aoc::{read_input, Result};
+use crate::pool::Pool;
+use crate::{Answer, Result, Solution};
 
-fn main() -> Result<()> {
-    let s = read_input()?;
+pub struct Day05;
 
-    part1(&s)?;
-    part2(&s)?;
+impl Solution for Day05 {
+    fn day(&self) -> u32 {
+        5
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        part1(input.trim()).map(Answer::from)
+    }
 
-    Ok(())
+    fn part2(&self, input: &str) -> Result<Answer> {
+        part2(input.trim()).map(Answer::from)
+    }
 }
 
 fn reacts(a: char, b: char) -> bool {
@@ -34,29 +42,26 @@ fn react(s: &str, ignore: Option<char>) -> Vec<char> {
 fn part1(s: &str) -> Result<usize> {
     let output = react(s, None);
 
-    eprintln!("part1 {}", output.len());
-
     Ok(output.len())
 }
 
 fn part2(s: &str) -> Result<usize> {
+    shortest_without_one(s, Pool::current())
+}
+
+/// Shortest polymer after removing one unit type, each type is tried on
+/// the threads of the pool
+fn shortest_without_one(s: &str, pool: Pool) -> Result<usize> {
     let polymers = s
         .chars()
         .map(|v| v.to_ascii_lowercase())
-        .collect::<HashSet<char>>();
+        .collect::<BTreeSet<char>>()
+        .into_iter()
+        .collect::<Vec<_>>();
 
-    let mut length = usize::max_value();
+    let lengths = pool.map(&polymers, |&polymer| react(s, Some(polymer)).len());
 
-    for polymer in polymers.iter() {
-        let chars = react(s, Some(*polymer));
-
-        if chars.len() < length {
-            length = chars.len();
-        }
-    }
-    eprintln!("part2 {}", length);
-
-    Ok(length)
+    Ok(lengths.into_iter().min().unwrap_or(usize::max_value()))
 }
 
 #[cfg(test)]
@@ -80,4 +85,14 @@ mod part2_tests {
 
         assert_eq!(4, part2(input.trim()).unwrap());
     }
+
+    #[test]
+    fn same_length_on_any_number_of_threads() {
+        let input = r"dabAcCaCBAcCcaDA";
+
+        for threads in 1..=4 {
+            let pool = Pool::new(threads);
+            assert_eq!(4, shortest_without_one(input, pool).unwrap());
+        }
+    }
 }
@@ -1,51 +1,5 @@
-use aoc::{read_input, CustomError, Result};
+use crate::{trace, Answer, Color, Error, Image, Rect, Result, Solution, V2};
 use std::collections::HashMap;
-use std::str::FromStr;
-
-use lazy_static::*;
-use regex::Regex;
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
-struct Point {
-    x: i32,
-    y: i32,
-    id: i32,
-}
-
-impl Point {
-    fn distance_from(&self, other: &Self) -> usize {
-        manhattan_distance(self, other)
-    }
-}
-
-impl FromStr for Point {
-    type Err = CustomError;
-
-    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        lazy_static! {
-            static ref RE: Regex = Regex::new(r"(\d+),\s*(\d+)").unwrap();
-        }
-
-        let caps = RE
-            .captures(s)
-            .ok_or_else(|| CustomError("Invalid captures".to_owned()))?;
-
-        let x = aoc::get_value(&caps, 1)?;
-        let y = aoc::get_value(&caps, 2)?;
-
-        Ok(Point { x, y, id: 0 })
-    }
-}
-
-impl std::convert::From<(usize, usize)> for Point {
-    fn from(v: (usize, usize)) -> Self {
-        Point {
-            x: v.0 as i32,
-            y: v.1 as i32,
-            id: 0,
-        }
-    }
-}
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
 struct Cell {
@@ -89,64 +43,42 @@ impl Cell {
 
 type Grid<T> = Vec<Vec<T>>;
 
-fn main() -> Result<()> {
-    let s = read_input()?;
+pub struct Day06;
 
-    part1(&s)?;
-    part2(&s, 10000)?;
+impl Solution for Day06 {
+    fn day(&self) -> u32 {
+        6
+    }
 
-    Ok(())
-}
+    fn part1(&self, input: &str) -> Result<Answer> {
+        part1(input.trim()).map(Answer::from)
+    }
 
-fn manhattan_distance(a: &Point, b: &Point) -> usize {
-    ((b.x - a.x).abs() + (b.y - a.y).abs()) as usize
-}
+    fn part2(&self, input: &str) -> Result<Answer> {
+        part2(input.trim(), 10000).map(Answer::from)
+    }
 
-fn get_points(s: &str) -> Result<(Vec<Point>, usize, usize)> {
-    let coords: std::result::Result<Vec<_>, _> =
-        s.lines().map(|v| v.parse::<Point>()).collect();
+    fn parse(&self, input: &str) -> Result<bool> {
+        get_points(input.trim())?;
+        Ok(true)
+    }
 
-    let mut coords = coords?;
+    fn image(&self, input: &str) -> Result<Option<Image>> {
+        draw(input.trim()).map(Some)
+    }
+}
 
-    let min_x = coords
-        .iter()
-        .map(|c| c.x)
-        .min()
-        .ok_or_else::<Box<CustomError>, _>(|| {
-            CustomError("Missing min_x".to_string()).into()
-        })?;
-    let max_x = coords
-        .iter()
-        .map(|c| c.x)
-        .max()
-        .ok_or_else::<Box<CustomError>, _>(|| {
-            CustomError("Missing max_x".to_string()).into()
-        })?;
-    let min_y = coords
-        .iter()
-        .map(|c| c.y)
-        .min()
-        .ok_or_else::<Box<CustomError>, _>(|| {
-            CustomError("Missing min_y".to_string()).into()
-        })?;
-    let max_y = coords
-        .iter()
-        .map(|c| c.y)
-        .max()
-        .ok_or_else::<Box<CustomError>, _>(|| {
-            CustomError("Missing max_y".to_string()).into()
-        })?;
+/// Reads the coordinates moved so that the smallest one is at the origin,
+/// returns them with the size of the area they cover
+fn get_points(s: &str) -> Result<(Vec<V2<i32>>, usize, usize)> {
+    let coords = crate::parse_lines(s, str::parse::<V2<i32>>)?;
 
-    let size_x = (max_x - min_x).abs() as usize + 1;
-    let size_y = (max_y - min_y).abs() as usize + 1;
+    let bounds = Rect::from_points(coords.iter().cloned())
+        .ok_or_else(|| Error::invalid("Missing coordinates"))?;
 
-    for (i, mut co) in coords.iter_mut().enumerate() {
-        co.x -= min_x;
-        co.y -= min_y;
-        co.id = i as i32;
-    }
+    let coords = coords.into_iter().map(|c| bounds.normalize(c)).collect();
 
-    Ok((coords, size_x, size_y))
+    Ok((coords, bounds.width() as usize, bounds.height() as usize))
 }
 
 fn part1(s: &str) -> Result<usize> {
@@ -158,8 +90,6 @@ fn part1(s: &str) -> Result<usize> {
 
     let r = count_grid1(&grid, (size_x - 1) as usize, (size_y - 1) as usize)?;
 
-    eprintln!("part1: {:?}", r);
-
     Ok(r)
 }
 
@@ -170,26 +100,25 @@ fn part2(s: &str, limit: usize) -> Result<usize> {
 
     let res = count_grid2(&grid, &coords, limit)?;
 
-    eprintln!("part2: {:?}", res);
-
     Ok(res)
 }
 
-fn update_grid(grid: &mut Grid<Cell>, coords: &[Point]) -> Result<()> {
+fn update_grid(grid: &mut Grid<Cell>, coords: &[V2<i32>]) -> Result<()> {
     for (y, row) in grid.iter_mut().enumerate() {
         for (x, mut col) in row.iter_mut().enumerate() {
-            let pt: Point = (x, y).into();
-            for coord in coords.iter() {
-                let dist = manhattan_distance(&pt, &coord) as usize;
+            let pt = V2::new([x as i32, y as i32]);
+            for (id, coord) in coords.iter().enumerate() {
+                let id = id as i32;
+                let dist = pt.manhattan(*coord) as usize;
                 if dist == 0 {
                     col.last_distance = dist;
-                    col.set(coord.id);
+                    col.set(id);
                 } else if dist == col.last_distance {
                     col.last_distance = dist;
-                    col.add(coord.id);
+                    col.add(id);
                 } else if dist < col.last_distance {
                     col.last_distance = dist;
-                    col.set(coord.id);
+                    col.set(id);
                 }
             }
         }
@@ -198,18 +127,18 @@ fn update_grid(grid: &mut Grid<Cell>, coords: &[Point]) -> Result<()> {
     Ok(())
 }
 
-fn count_grid2<T>(grid: &[T], coords: &[Point], limit: usize) -> Result<usize>
+fn count_grid2<T>(grid: &[T], coords: &[V2<i32>], limit: usize) -> Result<usize>
 where
     T: AsRef<[Cell]>,
 {
     let mut total = 0;
     for (y, row) in grid.iter().enumerate() {
         for (x, _) in row.as_ref().iter().enumerate() {
-            let pt: Point = (x, y).into();
+            let pt = V2::new([x as i32, y as i32]);
 
-            let all: usize = coords.iter().map(|c| pt.distance_from(c)).sum();
+            let all: i32 = coords.iter().map(|c| pt.manhattan(*c)).sum();
 
-            if all < limit {
+            if (all as usize) < limit {
                 total += 1;
             }
         }
@@ -218,6 +147,26 @@ where
     Ok(total)
 }
 
+/// Draws the area closest to each coordinate in its own color, ties are
+/// gray and the coordinates white
+fn draw(s: &str) -> Result<Image> {
+    let (coords, size_x, size_y) = get_points(s)?;
+
+    let mut grid = vec![vec![Cell::new(); size_x]; size_y];
+
+    update_grid(&mut grid, &coords)?;
+
+    Ok(Image::from_fn(size_x, size_y, |x, y| {
+        let cell = &grid[y][x];
+
+        match cell.id() {
+            Some(_) if cell.last_distance == 0 => Color::WHITE,
+            Some(id) => Color::distinct(id as usize),
+            None => Color::rgb(60, 60, 60),
+        }
+    }))
+}
+
 fn count_grid1<T>(grid: &[T], max_x: usize, max_y: usize) -> Result<usize>
 where
     T: AsRef<[Cell]>,
@@ -244,7 +193,7 @@ where
         .filter(|(_, v)| **v >= 0)
         .map(|(_, v)| v)
         .max()
-        .unwrap();
+        .ok_or_else(|| Error::unsolvable("Every area is infinite"))?;
 
     Ok(*result as usize)
 }
@@ -257,7 +206,7 @@ fn display_grid(grid: &[Vec<Cell>]) -> Result<()> {
             s.push_str(&col.value());
         }
 
-        eprintln!("{}", s);
+        trace!("{}", s);
     }
     Ok(())
 }
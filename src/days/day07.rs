@@ -1,16 +1,23 @@
-use aoc::{CustomError, Result};
+use crate::{debug, trace, Answer, Error, Result, Solution};
 use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 use lazy_static::lazy_static;
 use regex::Regex;
 
-fn main() -> Result<()> {
-    let s = aoc::read_input()?;
+pub struct Day07;
 
-    part1(&s)?;
-    part2(&s, 60, 5)?;
+impl Solution for Day07 {
+    fn day(&self) -> u32 {
+        7
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        part1(input.trim()).map(Answer::from)
+    }
 
-    Ok(())
+    fn part2(&self, input: &str) -> Result<Answer> {
+        part2(input.trim(), 60, 5).map(Answer::from)
+    }
 }
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
@@ -27,17 +34,17 @@ fn get_steps(s: &str) -> Result<Vec<Step>> {
         .unwrap();
     }
 
-    s.lines()
-        .map(|line| {
-            let caps = RE
-                .captures(line)
-                .ok_or_else(|| CustomError("Invalid captures".to_owned()))?;
-
-            let first: String = aoc::get_value(&caps, 1)?;
-            let second: String = aoc::get_value(&caps, 2)?;
-            Ok(Step { first, second })
-        })
-        .collect()
+    crate::parse_lines(s, |line| {
+        let caps = RE.captures(line).ok_or_else(|| {
+            Error::parse(
+                "expected 'Step X must be finished before step Y can begin.'",
+            )
+        })?;
+
+        let first: String = crate::get_value(&caps, 1)?;
+        let second: String = crate::get_value(&caps, 2)?;
+        Ok(Step { first, second })
+    })
 }
 
 fn pop_front(tree: &mut BTreeSet<String>) -> Option<String> {
@@ -72,19 +79,19 @@ fn part1(s: &str) -> Result<String> {
         }
     }
 
-    // eprintln!("Maps {:?}", map);
-    // eprintln!("Reqs {:?}", prereqs);
+    trace!("Maps {:?}", map);
+    trace!("Reqs {:?}", prereqs);
 
     let mut work_queue: BTreeSet<_> =
         firsts.difference(&seconds).cloned().collect();
 
-    eprintln!("Starts {:?}", work_queue);
+    debug!("Starts {:?}", work_queue);
 
     let mut completed: Vec<String> = Vec::new();
 
     while !work_queue.is_empty() {
         let node = pop_front(&mut work_queue).unwrap();
-        // eprintln!("Visiting {}", node);
+        trace!("Visiting {}", node);
 
         completed.push(node.clone());
 
@@ -109,8 +116,6 @@ fn part1(s: &str) -> Result<String> {
 
     let res = completed.join("");
 
-    eprintln!("part1: {}", res);
-
     Ok(res)
 }
 
@@ -122,7 +127,7 @@ fn part2(s: &str, min_time: usize, nr_workers: usize) -> Result<usize> {
         .map(|(i, v)| ((v as char).to_string(), (i + 1) + min_time))
         .collect();
 
-    // eprintln!("Times {:?}", times);
+    trace!("Times {:?}", times);
 
     let steps = get_steps(s)?;
 
@@ -152,13 +157,13 @@ fn part2(s: &str, min_time: usize, nr_workers: usize) -> Result<usize> {
         duration: usize,
     }
 
-    // eprintln!("Maps {:?}", map);
-    // eprintln!("Reqs {:?}", prereqs);
+    trace!("Maps {:?}", map);
+    trace!("Reqs {:?}", prereqs);
 
     let mut work_queue: BTreeSet<_> =
         firsts.difference(&seconds).cloned().collect();
 
-    // eprintln!("Starts {:?}", work_queue);
+    trace!("Starts {:?}", work_queue);
 
     let mut completed: Vec<String> = Vec::new();
 
@@ -219,7 +224,7 @@ fn part2(s: &str, min_time: usize, nr_workers: usize) -> Result<usize> {
     }
 
     while !work_queue.is_empty() || workers_working(&workers) {
-        // eprintln!("{} Queue {:?}", tick, work_queue);
+        trace!("{} Queue {:?}", tick, work_queue);
 
         let mut output = format!("{: <6}", tick);
         // let mut output = String::new()
@@ -279,7 +284,7 @@ fn part2(s: &str, min_time: usize, nr_workers: usize) -> Result<usize> {
 
         output.push_str(&format!("   {}", completed.join("")));
 
-        eprintln!("{}", output);
+        trace!("{}", output);
         tick += 1;
     }
 
@@ -288,13 +293,7 @@ fn part2(s: &str, min_time: usize, nr_workers: usize) -> Result<usize> {
         tick -= 1;
     }
 
-    let res = completed.join("");
-
-    eprintln!("part1: {}", res);
-
-    eprintln!("part2: {}", tick);
-
-    Ok(0)
+    Ok(tick)
 }
 
 #[cfg(test)]
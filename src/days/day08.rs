@@ -1,4 +1,4 @@
-use aoc::Result;
+use crate::{trace, Answer, Error, Result, Solution};
 use std::collections::VecDeque;
 use std::fmt;
 
@@ -73,50 +73,60 @@ impl Tree {
     }
 }
 
-fn main() -> Result<()> {
-    let s = aoc::read_input()?;
+pub struct Day08;
 
-    part1(&s)?;
-    part2(&s)?;
+impl Solution for Day08 {
+    fn day(&self) -> u32 {
+        8
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        part1(input.trim()).map(Answer::from)
+    }
 
-    Ok(())
+    fn part2(&self, input: &str) -> Result<Answer> {
+        part2(input.trim()).map(Answer::from)
+    }
+
+    fn parse(&self, input: &str) -> Result<bool> {
+        read_values(input.trim())?;
+        Ok(true)
+    }
 }
 
-fn part1(s: &str) -> Result<usize> {
-    let values: Result<VecDeque<u32>> = s
-        .split(' ')
-        .map(|v| v.parse::<u32>().map_err(|err| err.into()))
-        .collect();
+fn read_values(s: &str) -> Result<VecDeque<u32>> {
+    s.split(' ')
+        .map(|v| {
+            v.parse::<u32>().map_err(|_| {
+                Error::parse(format!("invalid number '{}'", v))
+                    .at_line(1)
+                    .at_token(s, v)
+            })
+        })
+        .collect()
+}
 
-    let mut values = values?;
+fn part1(s: &str) -> Result<usize> {
+    let mut values = read_values(s)?;
 
     let mut tree = Tree::new();
 
-    read_tree(&mut tree, &mut values);
+    read_tree(&mut tree, &mut values)?;
 
     let result: u32 = tree.iter().map(|v| v.sum()).sum();
 
-    eprintln!("part1: {:?}", result);
-
     Ok(result as usize)
 }
 
 fn part2(s: &str) -> Result<usize> {
-    let values: Result<VecDeque<u32>> = s
-        .split(' ')
-        .map(|v| v.parse::<u32>().map_err(|err| err.into()))
-        .collect();
-
-    let mut values = values?;
+    let mut values = read_values(s)?;
 
     let mut tree = Tree::new();
 
-    read_tree(&mut tree, &mut values);
+    read_tree(&mut tree, &mut values)?;
 
     let result = count_tree(&tree);
 
-    eprintln!("part2: {:?}", result);
-
     Ok(result as usize)
 }
 
@@ -147,30 +157,40 @@ fn count_tree(tree: &Tree) -> u32 {
     count_node(tree, root)
 }
 
-fn read_tree(tree: &mut Tree, values: &mut VecDeque<u32>) -> NodeId {
-    let children = values.pop_front().unwrap();
-    let metacount = values.pop_front().unwrap();
+fn read_tree(tree: &mut Tree, values: &mut VecDeque<u32>) -> Result<NodeId> {
+    let mut next = || {
+        values
+            .pop_front()
+            .ok_or_else(|| Error::parse("unexpected end of the tree"))
+    };
+
+    let children = next()?;
+    let metacount = next()?;
 
-    // eprintln!("children {} metacount {}", children, metacount);
+    trace!("children {} metacount {}", children, metacount);
 
     let mut nodes = Vec::new();
 
     let node = tree.new_node(Vec::new());
 
     for _ in 0..children {
-        nodes.push(read_tree(tree, values));
+        nodes.push(read_tree(tree, values)?);
     }
 
     let mut metas = Vec::new();
 
     for _ in 0..metacount {
-        metas.push(values.pop_front().unwrap());
+        metas.push(
+            values.pop_front().ok_or_else(|| {
+                Error::parse("unexpected end of the metadata")
+            })?,
+        );
     }
 
     tree.get_mut(node).unwrap().children = nodes;
     tree.get_mut(node).unwrap().meta = metas;
 
-    node
+    Ok(node)
 }
 
 #[cfg(test)]
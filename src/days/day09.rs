@@ -0,0 +1,203 @@
+use crate::{debug, trace, Answer, CircularList, Error, Result, Solution};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    fn day(&self) -> u32 {
+        9
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        part1(input.trim()).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        part2(input.trim(), 100).map(Answer::from)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct Player {
+    id: u32,
+    marbles: Vec<usize>,
+}
+
+impl Player {
+    fn score(&self) -> usize {
+        self.marbles.iter().sum()
+    }
+}
+
+fn part1(s: &str) -> Result<usize> {
+    part2(s, 1)
+}
+
+fn part2(s: &str, multiplier: usize) -> Result<usize> {
+    lazy_static! {
+        static ref RE: Regex =
+            Regex::new(r"(\d+) players; last marble is worth (\d+) points.*")
+                .unwrap();
+    }
+
+    let caps = RE
+        .captures(s)
+        .ok_or_else(|| Error::parse(format!("invalid game '{}'", s.trim())))?;
+
+    let player_count: usize = crate::get_value(&caps, 1)?;
+    let last_points: usize = crate::get_value(&caps, 2)?;
+
+    Ok(high_score(player_count, last_points * multiplier))
+}
+
+/// Highest score after the marble worth `last_points` has been played
+fn high_score(player_count: usize, last_points: usize) -> usize {
+    debug!("players {} last_points {}", player_count, last_points);
+
+    let mut marbles = CircularList::with_capacity(last_points + 1);
+    marbles.insert_after(0);
+    let mut current_marble = 1;
+
+    let mut players = (0..player_count)
+        .map(|i| Player {
+            id: i as u32 + 1,
+            marbles: Vec::new(),
+        })
+        .collect::<Vec<_>>();
+
+    'game: loop {
+        for player in players.iter_mut() {
+            place_marble(&mut current_marble, &mut marbles, player);
+
+            if current_marble > last_points {
+                break 'game;
+            }
+        }
+    }
+
+    let max = players.iter().max_by_key(|p| p.score());
+
+    if let Some(player) = max {
+        debug!("Winner {} score {}", player.id, player.score());
+
+        return player.score();
+    }
+
+    0
+}
+
+#[allow(dead_code)]
+fn show_marbles(marbles: &CircularList<usize>, player: Option<u32>) {
+    let mut output = String::new();
+
+    if let Some(p) = player {
+        output.push_str(&format!("[{:^3}]", p));
+    } else {
+        output.push_str("[ - ]");
+    }
+
+    // The current marble is the first one
+    for (i, marble) in marbles.iter().enumerate() {
+        if i == 0 {
+            output.push_str(&format!(" ({})", marble));
+        } else {
+            output.push_str(&format!("  {} ", marble));
+        }
+    }
+
+    trace!("{}", output);
+}
+
+fn place_marble(
+    current: &mut usize,
+    marbles: &mut CircularList<usize>,
+    player: &mut Player,
+) {
+    if *current % 23 == 0 {
+        marbles.rotate(-7);
+
+        let item = marbles.remove().expect("marbles are never all removed");
+
+        player.marbles.push(*current);
+        player.marbles.push(item);
+    } else {
+        marbles.move_next();
+        marbles.insert_after(*current);
+    }
+    *current += 1;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check;
+
+    const INPUT: &'static str = r"
+9 players; last marble is worth 25 points: high score is 32
+10 players; last marble is worth 1618 points: high score is 8317
+13 players; last marble is worth 7999 points: high score is 146373
+17 players; last marble is worth 1104 points: high score is 2764
+21 players; last marble is worth 6111 points: high score is 54718
+30 players; last marble is worth 5807 points: high score is 37305
+    ";
+    const SCORES: [usize; 6] = [32, 8317, 146373, 2764, 54718, 37305];
+    #[test]
+    fn part1_example_input() {
+        for (i, line) in INPUT.trim().lines().enumerate() {
+            let score = SCORES[i];
+
+            assert_eq!(score, part1(line).unwrap());
+        }
+    }
+
+    /// Plays the game by inserting into and removing from a vector
+    fn reference_high_score(player_count: usize, last_points: usize) -> usize {
+        let mut circle = vec![0];
+        let mut current = 0;
+        let mut scores = vec![0; player_count];
+
+        for marble in 1..=last_points {
+            if marble % 23 == 0 {
+                let removed = (current + circle.len() - 7) % circle.len();
+                scores[(marble - 1) % player_count] +=
+                    marble + circle.remove(removed);
+                current = removed % circle.len();
+            } else {
+                current = (current + 1) % circle.len() + 1;
+                circle.insert(current, marble);
+            }
+        }
+
+        scores.into_iter().max().unwrap_or(0)
+    }
+
+    #[test]
+    fn high_score_matches_reference() {
+        check::check(
+            9,
+            200,
+            |rng| (rng.range(1, 12), rng.range(0, 400)),
+            |&(players, last)| {
+                let mut smaller: Vec<_> = check::shrink_int(players, 1)
+                    .into_iter()
+                    .map(|players| (players, last))
+                    .collect();
+                smaller.extend(
+                    check::shrink_int(last, 0)
+                        .into_iter()
+                        .map(|last| (players, last)),
+                );
+                smaller
+            },
+            |&(players, last)| {
+                let (players, last) = (players as usize, last as usize);
+
+                check::agree(
+                    high_score(players, last),
+                    reference_high_score(players, last),
+                )
+            },
+        );
+    }
+}
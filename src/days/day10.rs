@@ -0,0 +1,354 @@
+use crate::{
+    debug, scan, trace, Answer, Color, Error, Image, Rect, Result, Solution,
+    SparseGrid, Vector2,
+};
+use std::collections::BTreeMap;
+use std::convert::From;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd, Default)]
+struct Light {
+    position: Vector2,
+    velocity: Vector2,
+}
+
+impl FromStr for Light {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (pos_x, pos_y, vel_x, vel_y) = scan!(
+            s,
+            "position=<{}, {}> velocity=<{}, {}>",
+            i32,
+            i32,
+            i32,
+            i32
+        )?;
+
+        let position = Vector2::new(pos_x, pos_y);
+        let velocity = Vector2::new(vel_x, vel_y);
+
+        Ok(Light { position, velocity })
+    }
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    fn day(&self) -> u32 {
+        10
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        part1(input.trim(), 20000).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        part2(input.trim(), 20000).map(Answer::from)
+    }
+
+    fn parse(&self, input: &str) -> Result<bool> {
+        crate::parse_lines(input.trim(), str::parse::<Light>)?;
+        Ok(true)
+    }
+
+    fn image(&self, input: &str) -> Result<Option<Image>> {
+        let message = find_message(input.trim(), 20000)?;
+        let grid = message.sky.to_grid(false);
+
+        let image = Image::from_grid(&grid, |&lit| {
+            if lit {
+                Color::rgb(255, 220, 120)
+            } else {
+                Color::rgb(10, 10, 40)
+            }
+        });
+
+        Ok(Some(image))
+    }
+}
+
+/// Renders the lit points as `#` on a background of `.`
+fn render_sky(sky: &SparseGrid<bool>) -> String {
+    sky.render('.', |_| '#')
+}
+
+#[allow(dead_code)]
+fn debug_grid(orig: &[Vector2]) {
+    if orig.is_empty() {
+        return;
+    }
+
+    let sky: SparseGrid<bool> = orig.iter().map(|&p| (p, true)).collect();
+    trace!("{}", render_sky(&sky));
+}
+
+/// Box covering the lights from the start until `max_ticks`
+fn get_bounds(lights: &[Light], max_ticks: i32) -> Result<Rect<i32>> {
+    let start = lights.iter().map(|c| c.position);
+    let end = lights.iter().map(|c| c.position + (c.velocity * max_ticks));
+
+    Rect::from_points(start.chain(end))
+        .ok_or_else(|| Error::invalid("No lights"))
+}
+
+#[derive(Debug, Clone)]
+struct LetterMap {
+    data: BTreeMap<char, Vec<Vec<Vector2>>>,
+}
+
+impl LetterMap {
+    pub fn new_from(data: BTreeMap<char, Vec<Vec<Vector2>>>) -> Self {
+        LetterMap { data }
+    }
+
+    pub fn get_match(&self, sky: &SparseGrid<bool>) -> Option<String> {
+        let mut positions: Vec<_> = sky.positions().collect();
+        let mut out = String::new();
+        let mut map = BTreeMap::new();
+
+        loop {
+            let mut possible_match = BTreeMap::new();
+
+            if let Some((first, rest)) =
+                positions.split_first().map(|(v, r)| (*v, r.to_vec()))
+            {
+                for (k, vs) in self.data.iter() {
+                    for list in vs.iter() {
+                        let transformed: Vec<_> =
+                            list.iter().cloned().map(|c| c + first).collect();
+
+                        let all_chars =
+                            transformed.iter().all(|pos| sky.contains(*pos));
+
+                        if all_chars {
+                            debug!("Found possible match {} {:?}", k, first);
+
+                            possible_match.insert(*k, transformed.clone());
+                            break;
+                        }
+                    }
+                }
+
+                let max = possible_match.iter().max_by_key(|(_, v)| v.len());
+
+                if let Some((k, values)) = max {
+                    debug!("Best match {} {:?}", k, first);
+                    map.insert(first, *k);
+                    positions = rest
+                        .iter()
+                        .filter(|&v| !values.contains(v))
+                        .cloned()
+                        .collect();
+
+                    out.push(*k);
+                // break 'outer;
+                } else {
+                    positions = rest;
+                }
+            } else {
+                break;
+            }
+        }
+
+        if !out.is_empty() {
+            debug_grid(&positions[..]);
+            trace!("{:?}", map);
+            trace!("{}", out);
+            Some(out)
+        } else {
+            None
+        }
+    }
+}
+
+fn read_letters() -> Result<LetterMap> {
+    let letters =
+        crate::read_path(&crate::input_dir().join("day10_letters.txt"))?;
+
+    let mut chars = letters.char_indices().peekable();
+    let mut line = 0;
+    let mut current = 0;
+
+    let mut letter_index = 0;
+
+    let letters = vec![
+        'A', 'B', 'C', 'E', 'F', 'G', 'H', 'H', 'I', 'J', 'K', 'L', 'N', 'P',
+        'R', 'X', 'Z',
+    ];
+
+    let mut map: BTreeMap<char, Vec<Vec<Vector2>>> = BTreeMap::new();
+
+    while let Some((_ind, ch)) = chars.next() {
+        match ch {
+            '#' => {
+                let vec: Vector2 = (current as i32, line as i32).into();
+                let letter = letters[letter_index];
+                trace!("vec {} {:?}", letters[letter_index], vec);
+                current += 1;
+
+                let e = map.entry(letter).or_insert_with(|| vec![Vec::new()]);
+
+                if let Some(ref mut last) = e.last_mut() {
+                    last.push(vec);
+                }
+            }
+
+            '\n' => match chars.peek() {
+                Some((_, '\n')) => {
+                    if letters[letter_index] == letters[letter_index + 1] {
+                        map.entry(letters[letter_index])
+                            .and_modify(|v| v.push(Vec::new()));
+                    }
+
+                    letter_index += 1;
+                    line += 1;
+                    current = 0;
+                    chars.next();
+                }
+                _ => {
+                    line += 1;
+                    current = 0;
+                }
+            },
+
+            _ => {
+                current += 1;
+            }
+        }
+    }
+
+    // Normalize the values from absolute units
+    // to relative units to the first point
+    for (_, values) in map.iter_mut() {
+        for list in values.iter_mut() {
+            let first = list.first().cloned().unwrap();
+
+            for val in list.iter_mut() {
+                *val -= first;
+            }
+        }
+    }
+
+    trace!("Map {:#?}", map);
+
+    Ok(LetterMap::new_from(map))
+}
+
+fn part1(s: &str, max_ticks: i32) -> Result<String> {
+    let message = find_message(s, max_ticks)?;
+
+    Ok(message.text)
+}
+
+fn part2(s: &str, max_ticks: i32) -> Result<i32> {
+    let message = find_message(s, max_ticks)?;
+
+    Ok(message.seconds)
+}
+
+/// Message spelled by the lights
+struct Message {
+    text: String,
+    /// Second the message appears at
+    seconds: i32,
+    /// Lit points of the message
+    sky: SparseGrid<bool>,
+}
+
+fn find_message(s: &str, max_ticks: i32) -> Result<Message> {
+    let letters = read_letters()?;
+
+    let mut lights = crate::parse_lines(s, str::parse::<Light>)?;
+
+    let bounds = get_bounds(&lights, max_ticks)?;
+
+    debug!("Bounds {:?}", bounds);
+
+    let mut sky = SparseGrid::new();
+
+    // Normalize the coordinates from -X -> +X to 0..
+    for light in lights.iter_mut() {
+        light.position = bounds.normalize(light.position.into()).into();
+    }
+
+    for light in &lights {
+        sky.insert(light.position, true);
+    }
+
+    for tick in 0..max_ticks {
+        sky.clear();
+
+        for light in lights.iter_mut() {
+            light.position += light.velocity;
+            sky.insert(light.position, true);
+        }
+
+        if let Some(res) = letters.get_match(&sky) {
+            debug!("TICK: {}", tick);
+
+            trace!("{}", render_sky(&sky));
+
+            // Seconds start from 1
+            return Ok(Message {
+                text: res,
+                seconds: tick + 1,
+                sky,
+            });
+        }
+    }
+
+    Err(Error::unsolvable(format!(
+        "No message found in {} ticks",
+        max_ticks
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &'static str = r"
+position=< 9,  1> velocity=< 0,  2>
+position=< 7,  0> velocity=<-1,  0>
+position=< 3, -2> velocity=<-1,  1>
+position=< 6, 10> velocity=<-2, -1>
+position=< 2, -4> velocity=< 2,  2>
+position=<-6, 10> velocity=< 2, -2>
+position=< 1,  8> velocity=< 1, -1>
+position=< 1,  7> velocity=< 1,  0>
+position=<-3, 11> velocity=< 1, -2>
+position=< 7,  6> velocity=<-1, -1>
+position=<-2,  3> velocity=< 1,  0>
+position=<-4,  3> velocity=< 2,  0>
+position=<10, -3> velocity=<-1,  1>
+position=< 5, 11> velocity=< 1, -2>
+position=< 4,  7> velocity=< 0, -1>
+position=< 8, -2> velocity=< 0,  1>
+position=<15,  0> velocity=<-2,  0>
+position=< 1,  6> velocity=< 1,  0>
+position=< 8,  9> velocity=< 0, -1>
+position=< 3,  3> velocity=<-1,  1>
+position=< 0,  5> velocity=< 0, -1>
+position=<-2,  2> velocity=< 2,  0>
+position=< 5, -2> velocity=< 1,  2>
+position=< 1,  4> velocity=< 2,  1>
+position=<-2,  7> velocity=< 2, -2>
+position=< 3,  6> velocity=<-1, -1>
+position=< 5,  0> velocity=< 1,  0>
+position=<-6,  0> velocity=< 2,  0>
+position=< 5,  9> velocity=< 1, -2>
+position=<14,  7> velocity=<-2,  0>
+position=<-3,  6> velocity=< 2, -1>
+    ";
+
+    #[test]
+    fn part1_example_input() {
+        assert_eq!("HI", part1(INPUT.trim(), 5).unwrap());
+    }
+
+    #[test]
+    fn part2_example_input() {
+        assert_eq!(3, part2(INPUT.trim(), 5).unwrap());
+    }
+}
@@ -0,0 +1,342 @@
+use crate::pool::Pool;
+use crate::{debug, Answer, Color, Grid, Image, Result, Solution, Vector2};
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    fn day(&self) -> u32 {
+        11
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        part1(parse_serial(input.trim())?).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        part2(parse_serial(input.trim())?).map(Answer::from)
+    }
+
+    fn parse(&self, input: &str) -> Result<bool> {
+        parse_serial(input.trim())?;
+        Ok(true)
+    }
+
+    fn image(&self, input: &str) -> Result<Option<Image>> {
+        Ok(Some(draw(parse_serial(input.trim())?)))
+    }
+}
+
+fn parse_serial(s: &str) -> Result<i32> {
+    let serial_number = s.parse::<i32>()?;
+
+    Ok(serial_number)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd, Default)]
+struct PowerGrid {
+    power: Grid<i32>,
+    sums: Grid<i32>,
+}
+
+impl PowerGrid {
+    fn new_with(serial: i32) -> Self {
+        let mut power = Grid::new(300, 300, 0);
+        power.set_offset((1, 1).into());
+
+        for (pos, cell) in power.iter_mut() {
+            let rack_id = pos.x + 10;
+            let mut power_level = rack_id * pos.y;
+            power_level += serial;
+
+            power_level *= rack_id;
+
+            power_level = hundred_digit(power_level);
+            power_level -= 5;
+
+            *cell = power_level;
+        }
+
+        PowerGrid::from_power(power)
+    }
+
+    /// Grid of the given power levels starting from (1, 1) with its
+    /// summed-area table
+    fn from_power(power: Grid<i32>) -> Self {
+        let mut grid = PowerGrid {
+            sums: power.clone(),
+            power,
+        };
+
+        grid.calculate_summed_area();
+
+        grid
+    }
+
+    #[allow(dead_code)]
+    fn as_string(&self) -> String {
+        let mut out = String::new();
+
+        for grid in &[&self.power, &self.sums] {
+            for row in grid.rows() {
+                for val in row {
+                    out.push_str(&format!("{:^8}", val));
+                }
+                out.push('\n');
+            }
+            out.push('\n');
+        }
+
+        out
+    }
+
+    fn calculate_summed_area(&mut self) {
+        for (pos, &current) in self.power.iter() {
+            let left = self.summed_value(pos.left());
+            let top = self.summed_value(pos.up());
+            let corner = self.summed_value(pos.left().up());
+
+            self.sums[pos] = current + left + top - corner;
+        }
+    }
+
+    fn summed_value<P: Into<Vector2>>(&self, pt: P) -> i32 {
+        self.sums.get(pt).cloned().unwrap_or(0)
+    }
+
+    fn find_region(&self, size: i32) -> Option<(Vector2, i32)> {
+        let off = self.sums.offset();
+        let height = self.sums.height() as i32;
+        let width = self.sums.width() as i32;
+
+        let mut current_max = i32::min_value();
+        let mut current_point: Vector2 = (0, 0).into();
+
+        // Region has to be fully inside, (x, y) is the cell diagonally
+        // before its top left corner
+        for y in (off.y - 1)..=(off.y - 1 + height - size) {
+            for x in (off.x - 1)..=(off.x - 1 + width - size) {
+                let a = self.summed_value((x, y));
+                let b = self.summed_value((x + size, y));
+                let c = self.summed_value((x, y + size));
+                let d = self.summed_value((x + size, y + size));
+
+                let total = d - b - c + a;
+
+                if total >= current_max {
+                    current_max = total;
+                    current_point = (x + 1, y + 1).into();
+                }
+            }
+        }
+
+        if current_max != i32::min_value() {
+            Some((current_point, current_max))
+        } else {
+            None
+        }
+    }
+
+    /// Region of any size with the largest total, each size is searched on
+    /// the threads of the pool
+    fn find_sized_region(&self, pool: Pool) -> Option<(Vector2, i32, i32)> {
+        let mut current_size = i32::min_value();
+        let mut current_max = i32::min_value();
+        let mut current_pt: Vector2 = (0, 0).into();
+
+        let largest = self.sums.width().min(self.sums.height()) as i32;
+        let sizes: Vec<i32> = (1..=largest).collect();
+        let regions = pool.map(&sizes, |&size| self.find_region(size));
+
+        for (size, region) in sizes.into_iter().zip(regions) {
+            if let Some((pt, max)) = region {
+                if max >= current_max {
+                    current_max = max;
+                    current_pt = pt;
+                    current_size = size;
+                }
+            }
+        }
+
+        if current_max != i32::min_value() {
+            Some((current_pt, current_max, current_size))
+        } else {
+            None
+        }
+    }
+}
+
+fn part1(serial: i32) -> Result<Vector2> {
+    let grid = PowerGrid::new_with(serial);
+
+    if let Some((pt, max)) = grid.find_region(3) {
+        debug!("part1_max: {}", max);
+        debug!("part1_point: {:?}", pt);
+        return Ok(pt);
+    }
+
+    Ok((0, 0).into())
+}
+
+fn part2(serial: i32) -> Result<(i32, i32, i32)> {
+    let grid = PowerGrid::new_with(serial);
+
+    if let Some((pt, max, size)) = grid.find_sized_region(Pool::current()) {
+        debug!("part2_max: {}", max);
+        debug!("part2_point: {:?}", pt);
+        debug!("part2_size: {:?}", size);
+        return Ok((pt.x, pt.y, size));
+    }
+
+    Ok((0, 0, 0))
+}
+
+/// Draws the power levels from blue (-5) to red (4) with the best 3x3
+/// square outlined in white
+fn draw(serial: i32) -> Image {
+    let grid = PowerGrid::new_with(serial);
+
+    let low = Color::rgb(20, 40, 160);
+    let high = Color::rgb(230, 60, 30);
+
+    let mut image = Image::from_grid(&grid.power, |&power| {
+        low.lerp(high, f64::from(power + 5) / 9.0)
+    });
+
+    if let Some((pt, _)) = grid.find_region(3) {
+        // The grid starts from (1, 1)
+        let (left, top) = (pt.x as usize - 1, pt.y as usize - 1);
+
+        for i in 0..3 {
+            for &(x, y) in &[(i, 0), (i, 2), (0, i), (2, i)] {
+                image.set(left + x, top + y, Color::WHITE);
+            }
+        }
+    }
+
+    image
+}
+
+fn hundred_digit(value: i32) -> i32 {
+    (value / 100) % 10
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check;
+
+    #[test]
+    fn part1_example_input() {
+        assert_eq!(Vector2::from((33, 45)), part1(18).unwrap());
+        assert_eq!(Vector2::from((21, 61)), part1(42).unwrap());
+    }
+
+    #[test]
+    fn part2_example_input() {
+        assert_eq!((90, 269, 16), part2(18).unwrap());
+        assert_eq!((232, 251, 12), part2(42).unwrap());
+    }
+
+    /// Total power of the square at `(x, y)` counted cell by cell, the
+    /// grid starts from (1, 1)
+    fn reference_total(rows: &[Vec<i32>], x: i32, y: i32, size: i32) -> i32 {
+        let (x, y, size) = (x as usize - 1, y as usize - 1, size as usize);
+
+        rows[y..y + size]
+            .iter()
+            .map(|row| row[x..x + size].iter().sum::<i32>())
+            .sum()
+    }
+
+    /// Largest total power of any square of `size`
+    fn reference_max(rows: &[Vec<i32>], size: i32) -> i32 {
+        let (width, height) = (rows[0].len() as i32, rows.len() as i32);
+
+        (1..=height - size + 1)
+            .flat_map(|y| (1..=width - size + 1).map(move |x| (x, y)))
+            .map(|(x, y)| reference_total(rows, x, y, size))
+            .max()
+            .unwrap()
+    }
+
+    fn shrink_rows(rows: &Vec<Vec<i32>>) -> Vec<Vec<Vec<i32>>> {
+        let mut smaller = Vec::new();
+
+        if rows.len() > 1 {
+            smaller.push(rows[1..].to_vec());
+            smaller.push(rows[..rows.len() - 1].to_vec());
+        }
+
+        if rows[0].len() > 1 {
+            smaller.push(rows.iter().map(|row| row[1..].to_vec()).collect());
+            smaller.push(
+                rows.iter()
+                    .map(|row| row[..row.len() - 1].to_vec())
+                    .collect(),
+            );
+        }
+
+        smaller.extend(check::shrink_vec(rows, rows.len(), |row| {
+            check::shrink_vec(row, row.len(), |&power| {
+                check::shrink_int(i64::from(power), 0)
+                    .into_iter()
+                    .map(|power| power as i32)
+                    .collect()
+            })
+        }));
+
+        smaller
+    }
+
+    #[test]
+    fn regions_match_reference() {
+        check::check(
+            11,
+            200,
+            |rng| {
+                let (width, height) = (rng.range(1, 8), rng.range(1, 8));
+                (0..height)
+                    .map(|_| {
+                        (0..width).map(|_| rng.range(-5, 4) as i32).collect()
+                    })
+                    .collect()
+            },
+            shrink_rows,
+            |rows: &Vec<Vec<i32>>| {
+                let mut power = Grid::from_rows(rows.clone()).unwrap();
+                power.set_offset((1, 1).into());
+                let grid = PowerGrid::from_power(power);
+                let largest = rows.len().min(rows[0].len()) as i32;
+
+                for size in 1..=largest {
+                    let (pt, total) = grid
+                        .find_region(size)
+                        .ok_or_else(|| format!("no region of size {}", size))?;
+
+                    check::agree(
+                        (total, total),
+                        (
+                            reference_max(rows, size),
+                            reference_total(rows, pt.x, pt.y, size),
+                        ),
+                    )
+                    .map_err(|e| format!("size {}\n{}", size, e))?;
+                }
+
+                let region = grid.find_sized_region(Pool::new(1));
+                check::agree(grid.find_sized_region(Pool::new(3)), region)?;
+
+                let (pt, total, size) = region.ok_or("no region")?;
+                let best = (1..=largest)
+                    .map(|size| reference_max(rows, size))
+                    .max()
+                    .unwrap();
+
+                check::agree(
+                    (total, total),
+                    (best, reference_total(rows, pt.x, pt.y, size)),
+                )
+            },
+        );
+    }
+}
@@ -0,0 +1,404 @@
+use crate::log::Level;
+use crate::{cycle, log_enabled, trace, Answer, Error, Result, Solution};
+use std::collections::BTreeMap;
+use std::fmt;
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    fn day(&self) -> u32 {
+        12
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        part1(input.trim()).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        part2(input.trim()).map(Answer::from)
+    }
+
+    fn parse(&self, input: &str) -> Result<bool> {
+        read_from(input.trim())?;
+        Ok(true)
+    }
+}
+
+type State = BTreeMap<i64, PotState>;
+
+type Rules = BTreeMap<[PotState; 5], PotState>;
+
+fn read_from(s: &str) -> Result<(State, Rules)> {
+    let s = s.replace("initial state: ", "");
+
+    let mut iter = s.lines();
+
+    let mut pots: State = BTreeMap::new();
+
+    if let Some(initial) = iter.next() {
+        pots = initial
+            .char_indices()
+            .filter(|(_, ch)| match ch {
+                '#' => true,
+                _ => false,
+            })
+            .map(|(ind, ch)| match ch {
+                '#' => (ind as i64, PotState::Plant),
+                _ => unreachable!(),
+            })
+            .collect();
+    }
+
+    // Skip the empty line
+    iter.next();
+
+    let mut rules: Rules = BTreeMap::new();
+
+    for rule in iter {
+        let mut state_arr: [PotState; 5] = Default::default();
+        let states: Vec<_> = rule
+            .chars()
+            .take(5)
+            .map(|ch| match ch {
+                '#' => PotState::Plant,
+                _ => PotState::Empty,
+            })
+            .collect();
+
+        if states.len() < 5 {
+            return Err(Error::parse(format!("invalid rule '{}'", rule)));
+        }
+
+        state_arr.copy_from_slice(&states[0..5]);
+
+        let after: Vec<_> = rule
+            .chars()
+            .skip(9)
+            .map(|ch| match ch {
+                '#' => PotState::Plant,
+                _ => PotState::Empty,
+            })
+            .collect();
+
+        if let Some(rule) = after.first() {
+            rules.insert(state_arr, *rule);
+        } else {
+            return Err(Error::parse(format!("invalid rule '{}'", rule)));
+        }
+    }
+
+    Ok((pots, rules))
+}
+
+fn get_state(id: i64, state: &State) -> PotState {
+    state.get(&id).cloned().unwrap_or_default()
+}
+
+fn advance(state: &State, rules: &Rules) -> State {
+    let mut new_state: State = BTreeMap::new();
+
+    let (min_id, max_id) = match (state.keys().next(), state.keys().next_back())
+    {
+        (Some(min), Some(max)) => (min - 3, max + 3),
+        _ => return new_state,
+    };
+
+    for id in min_id..=max_id {
+        let rule_state: [PotState; 5] = [
+            get_state(id - 2, &state),
+            get_state(id - 1, &state),
+            get_state(id, &state),
+            get_state(id + 1, &state),
+            get_state(id + 2, &state),
+        ];
+
+        let next_state = rules.get(&rule_state).cloned().unwrap_or_default();
+
+        if next_state == PotState::Plant {
+            new_state.insert(id, next_state);
+        }
+    }
+
+    new_state
+}
+
+fn visualize_generation_with(gen: i64, state: &State, min: i64, max: i64) {
+    if !log_enabled!(Level::Trace) {
+        return;
+    }
+
+    let mut out = String::new();
+
+    for id in (min - 1)..=(max + 1) {
+        let val = state.get(&id).cloned().unwrap_or_default();
+
+        out.push(val.as_char());
+    }
+
+    trace!("{: >3} {}", gen, out);
+}
+
+fn show_rule(rule: [PotState; 5], after: PotState) -> String {
+    let mut out = String::new();
+
+    for r in rule.iter() {
+        out.push(r.as_char());
+    }
+    out.push_str(" => ");
+
+    out.push(after.as_char());
+
+    out
+}
+
+fn visualize_rules(rules: &Rules) {
+    for (rule, next) in rules.iter() {
+        trace!("{}", show_rule(*rule, *next));
+    }
+}
+
+fn count_ids(state: &State) -> i64 {
+    let mut ids = 0;
+
+    for (id, pot) in state.iter() {
+        if *pot == PotState::Plant {
+            ids += id;
+        }
+    }
+
+    ids
+}
+
+fn part1(s: &str) -> Result<i64> {
+    let (orig_state, rules) = read_from(s)?;
+    let mut state = orig_state;
+
+    visualize_rules(&rules);
+
+    let mut states: Vec<State> = Vec::new();
+    states.push(state.clone());
+
+    for _ in 0..20 {
+        // visualize_generation(i, &state);
+        state = advance(&state, &rules);
+        states.push(state.clone());
+    }
+
+    // The generations have no plants when the state is empty
+    let min = states
+        .iter()
+        .filter_map(|state| state.keys().next())
+        .fold(0, |min, id| std::cmp::min(min, *id));
+    let max = states
+        .iter()
+        .filter_map(|state| state.keys().next_back())
+        .fold(0, |max, id| std::cmp::max(max, *id));
+
+    for (id, state) in states.iter().enumerate() {
+        visualize_generation_with(id as i64, state, min, max);
+    }
+
+    let total_id = count_ids(&state);
+
+    Ok(total_id)
+}
+
+fn part2(s: &str) -> Result<i64> {
+    plants_after(s, 50_000_000_000)
+}
+
+/// Sum of the ids of the plants after `generations`, extrapolated from the
+/// first repeating shape of the plants
+fn plants_after(s: &str, generations: usize) -> Result<i64> {
+    let (state, rules) = read_from(s)?;
+
+    let (shape, offset) = cycle::nth_translated(
+        state,
+        |state| advance(state, &rules),
+        normalize,
+        generations,
+    );
+
+    Ok(shape.iter().map(|id| id + offset).sum())
+}
+
+/// Splits the state into the ids of the plants relative to the left-most
+/// plant and the id of that plant
+fn normalize(state: &State) -> (Vec<i64>, i64) {
+    let offset = state.keys().next().cloned().unwrap_or_default();
+
+    (state.keys().map(|id| id - offset).collect(), offset)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+enum PotState {
+    Empty,
+    Plant,
+}
+
+impl PotState {
+    fn as_char(self) -> char {
+        match self {
+            PotState::Empty => '.',
+            PotState::Plant => '#',
+        }
+    }
+}
+
+impl fmt::Display for PotState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PotState::Empty => write!(f, "."),
+            PotState::Plant => write!(f, "#"),
+        }
+    }
+}
+
+impl Default for PotState {
+    fn default() -> Self {
+        PotState::Empty
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check;
+    use crate::generate::Rng;
+    use std::collections::BTreeSet;
+
+    const INPUT: &'static str = r"
+initial state: #..#.#..##......###...###
+
+...## => #
+..#.. => #
+.#... => #
+.#.#. => #
+.#.## => #
+.##.. => #
+.#### => #
+#.#.# => #
+#.### => #
+##.#. => #
+##.## => #
+###.. => #
+###.# => #
+####. => #
+    ";
+    #[test]
+    fn part1_example_input() {
+        assert_eq!(325, part1(INPUT.trim()).unwrap());
+    }
+
+    #[test]
+    fn no_plants() {
+        assert_eq!(0, part1("initial state: ...").unwrap());
+        assert_eq!(0, part1(&input_text("#.#", &".".repeat(32))).unwrap());
+        assert!(part1("initial state: #\n\n##").is_err());
+    }
+
+    /// Input with the initial state and the outcome of every pattern, the
+    /// pattern of a rule is the binary number of its index
+    fn input_text(initial: &str, rules: &str) -> String {
+        let mut s = format!("initial state: {}\n\n", initial);
+
+        for (pattern, after) in rules.chars().enumerate() {
+            let pots: String = (0..5)
+                .rev()
+                .map(|bit| if pattern >> bit & 1 == 1 { '#' } else { '.' })
+                .collect();
+            s.push_str(&format!("{} => {}\n", pots, after));
+        }
+
+        s
+    }
+
+    /// Simulates every generation one pot at a time
+    fn reference_plants_after(s: &str, generations: usize) -> i64 {
+        let (state, rules) = read_from(s).unwrap();
+        let mut plants: BTreeSet<i64> = state.keys().cloned().collect();
+
+        for _ in 0..generations {
+            let (first, last) =
+                match (plants.iter().next(), plants.iter().last()) {
+                    (Some(&first), Some(&last)) => (first, last),
+                    _ => break,
+                };
+
+            plants = (first - 2..=last + 2)
+                .filter(|id| {
+                    let mut pots = [PotState::Empty; 5];
+                    for (pot, id) in pots.iter_mut().zip(id - 2..=id + 2) {
+                        if plants.contains(&id) {
+                            *pot = PotState::Plant;
+                        }
+                    }
+                    rules.get(&pots) == Some(&PotState::Plant)
+                })
+                .collect();
+        }
+
+        plants.iter().sum()
+    }
+
+    /// Strings with one plant less
+    fn shrink_pots(pots: &str) -> Vec<String> {
+        pots.char_indices()
+            .filter(|&(_, ch)| ch == '#')
+            .map(|(i, _)| format!("{}.{}", &pots[..i], &pots[i + 1..]))
+            .collect()
+    }
+
+    #[test]
+    fn extrapolation_matches_reference() {
+        let pots = |rng: &mut Rng, n| -> String {
+            (0..n)
+                .map(|_| if rng.chance(0.5) { '#' } else { '.' })
+                .collect()
+        };
+
+        check::check(
+            12,
+            200,
+            |rng| {
+                let len = rng.below(12);
+                let initial = pots(rng, len);
+                // Empty pots stay empty
+                let rules = format!(".{}", pots(rng, 31));
+                (initial, rules, rng.below(80))
+            },
+            |(initial, rules, generations)| {
+                let mut smaller: Vec<_> =
+                    check::shrink_int(*generations as i64, 0)
+                        .into_iter()
+                        .map(|g| (initial.clone(), rules.clone(), g as usize))
+                        .collect();
+
+                let chars: Vec<char> = initial.chars().collect();
+                for shorter in check::shrink_vec(&chars, 0, |_| Vec::new()) {
+                    let shorter = shorter.into_iter().collect();
+                    smaller.push((shorter, rules.clone(), *generations));
+                }
+
+                for initial in shrink_pots(initial) {
+                    smaller.push((initial, rules.clone(), *generations));
+                }
+
+                for rules in shrink_pots(rules) {
+                    smaller.push((initial.clone(), rules, *generations));
+                }
+
+                smaller
+            },
+            |(initial, rules, generations)| {
+                let s = input_text(initial, rules);
+
+                check::agree(
+                    plants_after(&s, *generations)
+                        .map_err(|e| e.to_string())?,
+                    reference_plants_after(&s, *generations),
+                )
+            },
+        );
+    }
+}
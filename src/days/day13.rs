@@ -1,13 +1,40 @@
-use aoc::Result;
+use crate::record::{Frame, Recorder};
+use crate::{
+    debug, trace, Answer, Dir4, Error, Grid, Recording, Result, Solution,
+};
 use std::collections::HashSet;
 
-fn main() -> Result<()> {
-    let s = aoc::read_input_untrimmed()?;
+pub struct Day13;
 
-    part1(&s)?;
-    part2(&s)?;
+impl Solution for Day13 {
+    fn day(&self) -> u32 {
+        13
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        part1(input).map(Answer::from)
+    }
 
-    Ok(())
+    fn part2(&self, input: &str) -> Result<Answer> {
+        part2(input).map(Answer::from)
+    }
+
+    fn parse(&self, input: &str) -> Result<bool> {
+        read_mine(input)?;
+        Ok(true)
+    }
+
+    fn record(&self, input: &str, part: u32) -> Result<Option<Recording>> {
+        let mut recorder = Recorder::new(format!("Day 13 part {}", part));
+
+        if part == 1 {
+            first_crash(input, &mut recorder)?;
+        } else {
+            last_cart(input, &mut recorder)?;
+        }
+
+        Ok(Some(recorder.finish()))
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd, Default)]
@@ -48,70 +75,22 @@ trait AsChar: Sized {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
-enum Direction {
-    /// <
+enum Turn {
     Left,
-    /// >
+    Straight,
     Right,
-    /// ^
-    Up,
-    /// v
-    Down,
 }
 
-impl AsChar for Direction {
-    fn from_char(c: char) -> Option<Self> {
-        match c {
-            '<' => Some(Direction::Left),
-            '>' => Some(Direction::Right),
-            '^' => Some(Direction::Up),
-            'v' => Some(Direction::Down),
-            _ => None,
-        }
-    }
-
-    fn as_char(&self) -> char {
+impl Turn {
+    /// Direction after taking the turn when heading to `dir`
+    fn apply(self, dir: Dir4) -> Dir4 {
         match self {
-            Direction::Left => '<',
-            Direction::Right => '>',
-            Direction::Up => '^',
-            Direction::Down => 'v',
+            Turn::Left => dir.turn_left(),
+            Turn::Straight => dir,
+            Turn::Right => dir.turn_right(),
         }
     }
-}
 
-impl Default for Direction {
-    fn default() -> Self {
-        Direction::Right
-    }
-}
-
-impl Direction {
-    fn turn(self, turn: Turn) -> Self {
-        match (self, turn) {
-            (Direction::Left, Turn::Left) => Direction::Down,
-            (Direction::Right, Turn::Left) => Direction::Up,
-            (Direction::Up, Turn::Left) => Direction::Left,
-            (Direction::Down, Turn::Left) => Direction::Right,
-
-            (Direction::Left, Turn::Right) => Direction::Up,
-            (Direction::Right, Turn::Right) => Direction::Down,
-            (Direction::Up, Turn::Right) => Direction::Right,
-            (Direction::Down, Turn::Right) => Direction::Left,
-
-            (d, Turn::Straight) => d,
-        }
-    }
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
-enum Turn {
-    Left,
-    Straight,
-    Right,
-}
-
-impl Turn {
     fn turn(self) -> Self {
         match self {
             Turn::Left => Turn::Straight,
@@ -127,10 +106,10 @@ impl Default for Turn {
     }
 }
 
-#[derive(Debug, Clone, Copy, Hash, Default)]
+#[derive(Debug, Clone, Copy, Hash)]
 pub struct Cart {
     location: Location,
-    direction: Direction,
+    direction: Dir4,
     next_turn: Turn,
     collision: bool,
 }
@@ -138,26 +117,16 @@ use std::cmp::Ordering;
 
 impl Cart {
     fn turn(&mut self) {
-        let next_dir = self.direction.turn(self.next_turn);
+        let next_dir = self.next_turn.apply(self.direction);
         self.next_turn = self.next_turn.turn();
         self.direction = next_dir;
     }
 
     fn r#move(&mut self) {
-        match self.direction {
-            Direction::Left => {
-                self.location.x -= 1;
-            }
-            Direction::Right => {
-                self.location.x += 1;
-            }
-            Direction::Up => {
-                self.location.y -= 1;
-            }
-            Direction::Down => {
-                self.location.y += 1;
-            }
-        }
+        let offset = self.direction.offset();
+
+        self.location.x += offset.x;
+        self.location.y += offset.y;
     }
 
     fn turn_track(&mut self, track: Track) {
@@ -168,24 +137,18 @@ impl Cart {
             Track::StraightHorizontal => {}
             Track::StraightVertical => {}
             Track::CurveLeft => {
-                let next_dir = match self.direction {
-                    Direction::Left => Direction::Up,
-                    Direction::Right => Direction::Down,
-                    Direction::Up => Direction::Left,
-                    Direction::Down => Direction::Right,
+                self.direction = if self.direction.is_horizontal() {
+                    self.direction.turn_right()
+                } else {
+                    self.direction.turn_left()
                 };
-
-                self.direction = next_dir;
             }
             Track::CurveRight => {
-                let next_dir = match self.direction {
-                    Direction::Left => Direction::Down,
-                    Direction::Right => Direction::Up,
-                    Direction::Up => Direction::Right,
-                    Direction::Down => Direction::Left,
+                self.direction = if self.direction.is_horizontal() {
+                    self.direction.turn_left()
+                } else {
+                    self.direction.turn_right()
                 };
-
-                self.direction = next_dir;
             }
             Track::Empty => {}
         }
@@ -260,46 +223,16 @@ impl AsChar for Track {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd, Default)]
-struct Grid {
-    tracks: Vec<Track>,
+struct Mine {
+    tracks: Grid<Track>,
     carts: Vec<Cart>,
-    width: usize,
-    height: usize,
 }
 
-fn to_index(width: usize, v: (usize, usize)) -> usize {
-    v.1 * width + v.0
-}
-
-impl Grid {
-    pub fn new(mut tracks: Vec<Vec<Track>>, mut carts: Vec<Cart>) -> Self {
-        let max_width = tracks.iter().map(|v| v.len()).max().unwrap();
-
-        for t in tracks.iter_mut() {
-            if t.len() < max_width {
-                let diff = max_width - t.len();
-                t.extend((0..diff).map(|_| Track::Empty));
-            }
-        }
-
-        for t in tracks.iter() {
-            assert_eq!(max_width, t.len());
-        }
-
-        let height = tracks.len();
-
+impl Mine {
+    pub fn new(tracks: Grid<Track>, mut carts: Vec<Cart>) -> Self {
         carts.sort();
 
-        Grid {
-            tracks: tracks.into_iter().flatten().collect(),
-            carts,
-            width: max_width,
-            height,
-        }
-    }
-
-    fn as_index(&self, v: (usize, usize)) -> usize {
-        to_index(self.width, v)
+        Mine { tracks, carts }
     }
 
     fn get_colliding_ids(&self) -> HashSet<usize> {
@@ -380,7 +313,6 @@ impl Grid {
     }
 
     fn tick_part2(&mut self) -> Option<(i32, i32)> {
-        let w = self.width;
         let len = self.carts.len();
 
         for index in 0..len {
@@ -405,9 +337,9 @@ impl Grid {
             {
                 let cart = &mut self.carts[index];
                 // Get the track under the new location
-                let i = to_index(w, cart.location.into());
+                let location: (i32, i32) = cart.location.into();
 
-                let track = self.tracks[i];
+                let track = self.tracks[location];
 
                 cart.turn_track(track);
             }
@@ -428,7 +360,6 @@ impl Grid {
     }
 
     fn tick(&mut self) {
-        let w = self.width;
         let len = self.carts.len();
 
         for index in 0..len {
@@ -445,9 +376,9 @@ impl Grid {
             {
                 let cart = &mut self.carts[index];
                 // Get the track under the new location
-                let i = to_index(w, cart.location.into());
+                let location: (i32, i32) = cart.location.into();
 
-                let track = self.tracks[i];
+                let track = self.tracks[location];
 
                 cart.turn_track(track);
             }
@@ -457,109 +388,82 @@ impl Grid {
     }
 
     fn draw(&self) -> String {
-        let mut output = String::new();
-
-        let mut vec: Vec<_> = self.tracks.iter().map(Track::as_char).collect();
+        let mut chars = self.tracks.map(Track::as_char);
 
         for c in self.carts.iter() {
-            let i = self.as_index(c.location.into());
+            let location: (i32, i32) = c.location.into();
 
             if !c.collision {
-                vec[i] = c.direction.as_char();
+                chars[location] = c.direction.arrow();
             } else {
-                vec[i] = 'X';
+                chars[location] = 'X';
             }
         }
 
-        let mut row = String::new();
-        // First draw out tracks
-        for y in 0..self.height {
-            row.clear();
-            row.push_str(&format!("{: >3} ", y));
-            for x in 0..self.width {
-                let i = self.as_index((x, y));
-                row.push(vec[i]);
-                // row.push(self.tracks[i].as_char());
-            }
+        chars.render(|&c| c)
+    }
 
-            output.push_str(&row);
-            output.push('\n');
-        }
+    fn frame(&self, tick: usize) -> Frame {
+        let crashed = self.carts.iter().filter(|c| c.collision).count();
 
-        output
+        Frame::new(tick, self.draw())
+            .with("carts", self.carts.len() - crashed)
+            .with("crashed", crashed)
     }
 }
 
-fn read_grid(s: &str) -> Result<Grid> {
-    eprintln!("{}", s);
-
-    let mut line: i32 = 0;
-    let mut column: i32 = 0;
+fn read_mine(s: &str) -> Result<Mine> {
+    trace!("{}", s);
 
-    let mut chars = s.char_indices();
-
-    let mut tracks = Vec::new();
     let mut carts = Vec::new();
-    let mut track_row = Vec::new();
 
-    while let Some((_, ch)) = chars.next() {
-        match (Track::from_char(ch), Direction::from_char(ch)) {
-            (Some(t), None) => {
-                track_row.push(t);
-            }
+    let tracks = Grid::parse(s, |pos, ch| {
+        match (Track::from_char(ch), Dir4::from_arrow(ch)) {
+            (Some(t), None) => Some(t),
             (None, Some(d)) => {
-                match d {
-                    Direction::Left => {
-                        track_row.push(Track::StraightHorizontal)
-                    }
-                    Direction::Right => {
-                        track_row.push(Track::StraightHorizontal)
-                    }
-                    Direction::Up => track_row.push(Track::StraightVertical),
-                    Direction::Down => track_row.push(Track::StraightVertical),
-                }
                 carts.push(Cart {
-                    location: (column, line).into(),
+                    location: (pos.x, pos.y).into(),
                     direction: d,
                     next_turn: Turn::Left,
                     collision: false,
                 });
-            }
-            _ => match ch {
-                '\n' => {
-                    line += 1;
-                    column = 0;
-                    tracks.push(track_row);
-                    track_row = Vec::new();
-                    continue;
-                }
-                _ => {
-                    unreachable!();
+
+                if d.is_horizontal() {
+                    Some(Track::StraightHorizontal)
+                } else {
+                    Some(Track::StraightVertical)
                 }
-            },
+            }
+            _ => None,
         }
+    })?;
 
-        column += 1;
-    }
+    Ok(Mine::new(tracks, carts))
+}
 
-    if !track_row.is_empty() {
-        tracks.push(track_row);
-    }
+fn part1(s: &str) -> Result<(i32, i32)> {
+    first_crash(s, &mut Recorder::off())
+}
 
-    Ok(Grid::new(tracks, carts))
+fn part2(s: &str) -> Result<(i32, i32)> {
+    last_cart(s, &mut Recorder::off())
 }
 
-fn part1(s: &str) -> Result<(i32, i32)> {
-    let mut grid = read_grid(s)?;
+fn first_crash(s: &str, recorder: &mut Recorder) -> Result<(i32, i32)> {
+    let mut grid = read_mine(s)?;
 
-    eprintln!("{}", grid.draw());
+    trace!("{}", grid.draw());
+    recorder.record(|| grid.frame(0));
 
     let mut tick = 0;
     let res: Option<(i32, i32)> = loop {
         grid.tick();
 
-        if let Some(p) = grid.check_for_collisions() {
-            eprintln!("{} Collision {:?}", tick, p);
+        let crash = grid.check_for_collisions();
+        recorder.record(|| grid.frame(tick + 1));
+
+        if let Some(p) = crash {
+            debug!("{} Collision {:?}", tick, p);
             break Some(p);
         }
 
@@ -570,26 +474,25 @@ fn part1(s: &str) -> Result<(i32, i32)> {
         }
     };
 
-    eprintln!("{}", grid.draw());
+    trace!("{}", grid.draw());
 
-    if let Some(p) = res {
-        eprintln!("part1: {:?}", p);
-        Ok(p)
-    } else {
-        Ok((0, 0))
-    }
+    res.ok_or_else(|| Error::unsolvable("No crash within 1000000 ticks"))
 }
 
-fn part2(s: &str) -> Result<(i32, i32)> {
-    let mut grid = read_grid(s)?;
+fn last_cart(s: &str, recorder: &mut Recorder) -> Result<(i32, i32)> {
+    let mut grid = read_mine(s)?;
 
-    eprintln!("{}", grid.draw());
+    trace!("{}", grid.draw());
+    recorder.record(|| grid.frame(0));
 
     let mut tick = 0;
 
     let res: Option<(i32, i32)> = loop {
-        if let Some(pos) = grid.tick_part2() {
-            eprintln!("{} -> {:?}", tick, pos);
+        let last = grid.tick_part2();
+        recorder.record(|| grid.frame(tick + 1));
+
+        if let Some(pos) = last {
+            debug!("{} -> {:?}", tick, pos);
             break Some(pos);
         }
 
@@ -600,14 +503,11 @@ fn part2(s: &str) -> Result<(i32, i32)> {
         }
     };
 
-    eprintln!("{}", grid.draw());
+    trace!("{}", grid.draw());
 
-    if let Some(p) = res {
-        eprintln!("part2: {:?}", p);
-        Ok(p)
-    } else {
-        Ok((0, 0))
-    }
+    res.ok_or_else(|| {
+        Error::unsolvable("No single cart left within 1000000 ticks")
+    })
 }
 
 #[cfg(test)]
@@ -0,0 +1,207 @@
+use crate::{Answer, Error, Result, Solution};
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    fn day(&self) -> u32 {
+        14
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        let nr_recipes = input.trim().parse::<u32>()?;
+
+        part1(nr_recipes).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        part2(input.trim()).map(Answer::from)
+    }
+}
+
+fn digits(mut val: u32) -> Vec<u32> {
+    let mut digits = Vec::new();
+
+    if val == 0 {
+        digits.push(val);
+        return digits;
+    }
+
+    while val > 0 {
+        let digit = val % 10;
+        val = val / 10;
+
+        digits.push(digit);
+    }
+
+    digits.reverse();
+
+    digits
+}
+
+fn next_index(cur: usize, recipes: &[u32]) -> usize {
+    assert!(!recipes.is_empty());
+
+    let val = recipes[cur];
+
+    let forward = cur + 1 + val as usize;
+
+    let n = forward % recipes.len();
+
+    n
+}
+
+fn part1(nr_recipes: u32) -> Result<String> {
+    let mut recipes: Vec<u32> = vec![3, 7];
+
+    let mut elf1: usize = 0;
+    let mut elf2: usize = 1;
+
+    let mut idx = 0;
+    loop {
+        if recipes.len() >= (nr_recipes as usize) + 10 || idx >= 1_000_000_000 {
+            break;
+        }
+
+        let sum = recipes[elf1] + recipes[elf2];
+
+        recipes.append(&mut digits(sum));
+
+        elf1 = next_index(elf1, &recipes);
+        elf2 = next_index(elf2, &recipes);
+
+        idx += 1;
+    }
+
+    let next_scores: String = recipes
+        .iter()
+        .skip(nr_recipes as usize)
+        .take(10)
+        .map(|v| v.to_string())
+        .collect();
+
+    Ok(next_scores)
+}
+
+fn part2(s: &str) -> Result<u32> {
+    let expected_digits: Vec<u32> =
+        s.chars().filter_map(|c| c.to_digit(10)).collect();
+
+    if expected_digits.is_empty() {
+        return Err(Error::parse("Expected at least one digit"));
+    }
+
+    let mut recipes: Vec<u32> = vec![3, 7];
+
+    let mut elf1: usize = 0;
+    let mut elf2: usize = 1;
+
+    // Number of leading recipes already checked for ending
+    // with the expected sequence
+    let mut checked = 0;
+
+    loop {
+        while checked < recipes.len() {
+            checked += 1;
+
+            if recipes[..checked].ends_with(&expected_digits) {
+                return Ok((checked - expected_digits.len()) as u32);
+            }
+        }
+
+        if recipes.len() >= 1_000_000_000 {
+            return Err(Error::unsolvable("Sequence not found"));
+        }
+
+        let sum = recipes[elf1] + recipes[elf2];
+
+        recipes.append(&mut digits(sum));
+
+        elf1 = next_index(elf1, &recipes);
+        elf2 = next_index(elf2, &recipes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check;
+
+    #[test]
+    fn test_next() {
+        assert_eq!(0, next_index(0, &[3, 7, 1, 0]));
+        assert_eq!(1, next_index(1, &[3, 7, 1, 0]));
+    }
+
+    #[test]
+    fn test_digits() {
+        assert_eq!(vec![5, 0], digits(50));
+        assert_eq!(vec![1, 0], digits(10));
+        assert_eq!(vec![1], digits(1));
+        assert_eq!(vec![0], digits(0));
+        assert_eq!(vec![1, 1, 1], digits(111));
+    }
+
+    #[test]
+    fn part1_example_input() {
+        assert_eq!("0124515891", part1(5).unwrap());
+        assert_eq!("5158916779", part1(9).unwrap());
+        assert_eq!("9251071085", part1(18).unwrap());
+        assert_eq!("5941429882", part1(2018).unwrap());
+    }
+
+    #[test]
+    fn part2_example_input() {
+        assert_eq!(9, part2("51589").unwrap());
+        assert_eq!(5, part2("01245").unwrap());
+        assert_eq!(18, part2("92510").unwrap());
+        assert_eq!(2018, part2("59414").unwrap());
+    }
+
+    /// Scores of the first `len` recipes, one recipe at a time
+    fn reference_scoreboard(len: usize) -> String {
+        let mut recipes: Vec<u32> = vec![3, 7];
+        let (mut elf1, mut elf2) = (0, 1);
+
+        while recipes.len() < len {
+            let sum = recipes[elf1] + recipes[elf2];
+            recipes
+                .extend(sum.to_string().chars().filter_map(|c| c.to_digit(10)));
+            elf1 = (elf1 + 1 + recipes[elf1] as usize) % recipes.len();
+            elf2 = (elf2 + 1 + recipes[elf2] as usize) % recipes.len();
+        }
+
+        recipes.iter().map(|score| score.to_string()).collect()
+    }
+
+    #[test]
+    fn part2_matches_reference() {
+        // Not every sequence appears on the scoreboard, so the sequences
+        // are taken from its start
+        let scoreboard = reference_scoreboard(20_000);
+
+        check::check(
+            14,
+            200,
+            |rng| {
+                let start = rng.below(5_000);
+                scoreboard[start..start + 1 + rng.below(6)].to_string()
+            },
+            |pattern: &String| {
+                if pattern.len() > 1 {
+                    vec![
+                        pattern[1..].to_string(),
+                        pattern[..pattern.len() - 1].to_string(),
+                    ]
+                } else {
+                    Vec::new()
+                }
+            },
+            |pattern| {
+                check::agree(
+                    part2(pattern).map_err(|e| e.to_string())? as usize,
+                    scoreboard.find(pattern.as_str()).unwrap(),
+                )
+            },
+        );
+    }
+}
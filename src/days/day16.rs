@@ -0,0 +1,322 @@
+#![allow(dead_code)]
+#![allow(unused_variables)]
+use crate::elfcode::{Instruction, Machine, Opcode};
+use crate::{debug, parse, trace, Answer, Error, Result, Solution};
+
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    fn day(&self) -> u32 {
+        16
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        part1(input.trim()).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        part2(input.trim()).map(Answer::from)
+    }
+}
+
+type Registers = [i32; 4];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd, Default)]
+struct ByteCode {
+    instruction: i32,
+    a: i32,
+    b: i32,
+    c: i32,
+}
+
+fn maybe_register(val: i32) -> bool {
+    val >= 0 && val <= 3
+}
+
+impl ByteCode {
+    fn with_opcode(&self, op: Opcode) -> Instruction<i32> {
+        Instruction::new(op, self.a, self.b, self.c)
+    }
+
+    fn to_possible_instructions(&self) -> Vec<Instruction<i32>> {
+        Opcode::ALL.iter().map(|&op| self.with_opcode(op)).collect()
+    }
+}
+
+/// Parses registers formatted as `[a, b, c, d]`
+fn parse_registers(s: &str) -> Result<Registers> {
+    let values: Vec<i32> =
+        parse::list(s.trim_matches(|p| p == '[' || p == ']'), ',')?;
+
+    if values.len() != 4 {
+        return Err(Error::parse(format!("Expected 4 registers, got '{}'", s)));
+    }
+
+    Ok([values[0], values[1], values[2], values[3]])
+}
+
+impl FromStr for ByteCode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let values: Vec<&str> = s
+            .trim_matches(|p| p == '[' || p == ']')
+            .split(' ')
+            .map(|s| s.trim())
+            .collect();
+
+        if values.len() != 4 {
+            return Err(Error::parse(format!(
+                "Expected 4 values in an instruction, got {}",
+                values.len()
+            )));
+        }
+
+        let reg_0 = values[0].parse::<i32>()?;
+        let reg_1 = values[1].parse::<i32>()?;
+        let reg_2 = values[2].parse::<i32>()?;
+        let reg_3 = values[3].parse::<i32>()?;
+
+        // Ok(ByteCode([reg_0, reg_1, reg_2, reg_3]))
+        Ok(ByteCode {
+            instruction: reg_0,
+            a: reg_1,
+            b: reg_2,
+            c: reg_3,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd, Default)]
+struct Test {
+    initial: Registers,
+    expected: Registers,
+    code: ByteCode,
+}
+
+impl Test {
+    /// Whether executing `instr` turns the initial registers into the
+    /// expected ones, instructions using invalid registers never match
+    fn matches(&self, instr: Instruction<i32>) -> bool {
+        let mut machine = Machine::with_registers(self.initial);
+
+        machine.execute(instr).is_ok() && machine.registers() == &self.expected
+    }
+
+    fn run_test(&self) -> usize {
+        let instructions = self.code.to_possible_instructions();
+
+        instructions
+            .into_iter()
+            .filter(|instr| self.matches(*instr))
+            .count()
+    }
+
+    fn run_with(&self, opcodes: &[Opcode]) -> Vec<Opcode> {
+        opcodes
+            .iter()
+            .cloned()
+            .filter(|op| self.matches(self.code.with_opcode(*op)))
+            .collect()
+    }
+
+    fn get_instructions(&self) -> (ByteCode, Vec<Opcode>) {
+        (self.code, self.run_with(&Opcode::ALL))
+    }
+}
+
+/// Lines that are not empty with their line numbers starting from 1
+fn numbered_lines(s: &str) -> Vec<(usize, &str)> {
+    s.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(nr, line)| (nr + 1, line))
+        .collect()
+}
+
+/// Sample of the three lines starting at `index`, `None` if there are no
+/// more samples
+fn read_sample(lines: &[(usize, &str)], index: usize) -> Result<Option<Test>> {
+    let (before_nr, before) = match lines.get(index) {
+        Some(&(nr, line)) if line.starts_with("Before: ") => (nr, line),
+        _ => return Ok(None),
+    };
+
+    let line = |offset: usize| {
+        lines.get(index + offset).cloned().ok_or_else(|| {
+            Error::parse("Incomplete sample").at_line(before_nr + offset)
+        })
+    };
+
+    let (input_nr, input) = line(1)?;
+    let (after_nr, after) = line(2)?;
+
+    if !after.starts_with("After:  ") {
+        return Err(
+            Error::parse("Expected 'After:  '").in_line(after_nr, after)
+        );
+    }
+
+    let initial_regs = parse_registers(&before.replace("Before: ", ""))?;
+    let result_regs = parse_registers(&after.replace("After:  ", ""))?;
+    let bytecode = input
+        .parse::<ByteCode>()
+        .map_err(|e| e.in_line(input_nr, input))?;
+
+    trace!("before {:?}", initial_regs);
+    trace!("input {:?}", bytecode);
+    trace!("after {:?}", result_regs);
+
+    Ok(Some(Test {
+        initial: initial_regs,
+        expected: result_regs,
+        code: bytecode,
+    }))
+}
+
+fn part1(s: &str) -> Result<i32> {
+    let lines = numbered_lines(s);
+
+    let mut tests: Vec<_> = Vec::new();
+
+    while let Some(test) = read_sample(&lines, tests.len() * 3)? {
+        tests.push(test);
+    }
+
+    let how_many = tests.iter().map(Test::run_test).filter(|v| *v >= 3).count();
+
+    Ok(how_many as i32)
+}
+
+fn collect_instructions_from_samples(
+    mut tests: HashMap<i32, Vec<Test>>,
+) -> HashMap<i32, Opcode> {
+    let mut instruction_map: HashMap<i32, Opcode> = HashMap::new();
+    let mut inst_set: HashSet<Opcode> = HashSet::new();
+
+    let mut count = 0;
+
+    loop {
+        if count >= 1000 || tests.is_empty() {
+            break;
+        }
+
+        for (k, tests) in tests.iter() {
+            let first = tests.first().unwrap();
+            let (_, original) = first.get_instructions();
+
+            let mut instrs: Vec<_> = original
+                .into_iter()
+                .filter(|op| !inst_set.contains(op))
+                .collect();
+            trace!("start k: {} -> {:?}", k, instrs);
+
+            for test in tests.iter().skip(1) {
+                instrs = test.run_with(&instrs);
+                if instrs.is_empty() {
+                    break;
+                }
+                trace!("run k: {} -> {:?}", k, instrs);
+            }
+
+            if instrs.len() == 1 {
+                debug!("{} end k: {} -> {:?}", count, k, instrs);
+                instruction_map.insert(*k, instrs[0]);
+                inst_set.insert(instrs[0]);
+            }
+        }
+
+        for k in instruction_map.keys() {
+            tests.remove(&k);
+        }
+
+        count += 1;
+    }
+
+    instruction_map
+}
+
+fn part2(s: &str) -> Result<i32> {
+    let lines = numbered_lines(s);
+
+    let mut test_program_start = 0;
+
+    let mut instruction_tests: HashMap<i32, Vec<Test>> = HashMap::new();
+
+    while let Some(test) = read_sample(&lines, test_program_start)? {
+        instruction_tests
+            .entry(test.code.instruction)
+            .or_insert_with(Vec::new)
+            .push(test);
+
+        test_program_start += 3;
+    }
+
+    trace!("tests {:?}", instruction_tests);
+    let map = collect_instructions_from_samples(instruction_tests);
+
+    let mut instructions = Vec::new();
+
+    for (nr, line) in lines.into_iter().skip(test_program_start) {
+        let code = line.parse::<ByteCode>().map_err(|e| e.in_line(nr, line))?;
+
+        let op = map.get(&code.instruction).ok_or_else(|| {
+            Error::invalid(format!("Unknown opcode {}", code.instruction))
+        })?;
+
+        instructions.push(code.with_opcode(*op));
+    }
+
+    trace!("{:#?}", instructions.len());
+
+    let mut machine = Machine::with_registers([0; 4]);
+
+    for instr in instructions {
+        machine.execute(instr)?;
+    }
+
+    trace!("{:#?}", machine.registers());
+
+    let result = machine.registers()[0];
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_example_input() {
+        let input = r"
+Before: [3, 2, 1, 1]
+9 2 1 2
+After:  [3, 2, 2, 1]
+        ";
+
+        // The single sample behaves like three opcodes
+        assert_eq!(1, part1(input.trim()).unwrap())
+    }
+
+    #[test]
+    fn incomplete_samples_are_errors() {
+        assert!(part1("Before: [0, 0, 0, 0]").is_err());
+        assert!(part1("Before: [0, 0, 0, 0]\n9 2 1 2\n1 2 3 4").is_err());
+        assert!(part2("Before: [0, 0, 0, 0]\n9 2 1 2").is_err());
+    }
+
+    #[test]
+    fn short_instructions_are_errors() {
+        let err = part1("Before: [1, 2, 3, 4]\n9 2 1\nAfter:  [1, 2, 3, 4]")
+            .unwrap_err();
+        assert!(err.to_string().starts_with("line 2:"), "{}", err);
+
+        let program =
+            "Before: [1, 2, 3, 4]\n9 2 1 2\nAfter:  [1, 2, 3, 4]\n\n\n1 2";
+        let err = part2(program).unwrap_err();
+        assert!(err.to_string().contains("4 values"), "{}", err);
+    }
+}
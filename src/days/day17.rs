@@ -0,0 +1,612 @@
+#![allow(dead_code)]
+use crate::record::{Frame, Recorder};
+use crate::{
+    debug, trace, Answer, Color, Dir4, Error, Grid, Image, Recording, Rect,
+    Result, Solution, SparseGrid, Vector2, V2,
+};
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::{HashMap, VecDeque};
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    fn day(&self) -> u32 {
+        17
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        part1(input.trim()).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        part2(input.trim()).map(Answer::from)
+    }
+
+    fn parse(&self, input: &str) -> Result<bool> {
+        read_clay_locations(input.trim())?;
+        Ok(true)
+    }
+
+    fn image(&self, input: &str) -> Result<Option<Image>> {
+        let (grid, _, _) = simulate(input.trim(), &mut Recorder::off())?;
+
+        Ok(Some(Image::from_grid(&grid, Tile::color)))
+    }
+
+    /// Both parts spread the same water
+    fn record(&self, input: &str, _part: u32) -> Result<Option<Recording>> {
+        let mut recorder = Recorder::new("Day 17");
+
+        simulate(input.trim(), &mut recorder)?;
+
+        Ok(Some(recorder.finish()))
+    }
+}
+
+/// What a stream of water does next from its position
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+enum Stream {
+    /// Falls until it reaches something it can rest on
+    Down,
+    /// Spreads to both sides over what it rests on
+    Side,
+    /// Fills the container it fell in
+    Up,
+}
+
+impl Default for Stream {
+    fn default() -> Self {
+        Stream::Down
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+enum Tile {
+    Sand,
+    Clay,
+    Spring,
+    Flow,
+    Rest,
+    Invalid,
+}
+
+impl Tile {
+    fn as_char(&self) -> char {
+        match self {
+            Tile::Clay => '#',
+            Tile::Sand => '.',
+            Tile::Spring => '+',
+            Tile::Flow => '|',
+            Tile::Rest => '~',
+            Tile::Invalid => 'X',
+        }
+    }
+
+    fn color(&self) -> Color {
+        match self {
+            Tile::Clay => Color::rgb(130, 80, 40),
+            Tile::Sand => Color::rgb(235, 215, 160),
+            Tile::Spring => Color::rgb(255, 0, 0),
+            Tile::Flow => Color::rgb(120, 190, 250),
+            Tile::Rest => Color::rgb(20, 80, 200),
+            Tile::Invalid => Color::BLACK,
+        }
+    }
+}
+
+impl Default for Tile {
+    fn default() -> Self {
+        Tile::Sand
+    }
+}
+
+fn part1(s: &str) -> Result<usize> {
+    let (_, reached, _) = simulate(s, &mut Recorder::off())?;
+
+    Ok(reached)
+}
+
+fn part2(s: &str) -> Result<usize> {
+    let (_, _, resting) = simulate(s, &mut Recorder::off())?;
+
+    Ok(resting)
+}
+
+/// Returns the ground after the water has spread, the number of tiles
+/// reached by water and the number of tiles where the water comes to rest
+fn simulate(
+    s: &str,
+    recorder: &mut Recorder,
+) -> Result<(Grid<Tile>, usize, usize)> {
+    let mut ground: SparseGrid<Tile> = read_clay_locations(s)?
+        .into_iter()
+        .map(|pos| (pos, Tile::Clay))
+        .collect();
+
+    let clay = ground.bounds().ok_or_else(|| Error::invalid("No clay"))?;
+
+    let mut spring: Vector2 = Vector2::new(500, 0);
+    ground.insert(spring, Tile::Spring);
+
+    // Water can flow over the left edge of the leftmost clay
+    let bounds = clay.including(spring.into());
+    let bounds = Rect::new(bounds.min() - V2::new([1, 0]), bounds.max());
+
+    let c_min_y = bounds.normalize(clay.min()).y();
+
+    debug!("Bounds {:?}", bounds);
+    debug!("Clay {:?}", clay);
+
+    // Normalize the coordinates from -X -> +X to 0..
+    let mut grid = ground.to_grid_covering(bounds, Tile::default());
+    grid.set_offset(Vector2::default());
+
+    spring = bounds.normalize(spring.into()).into();
+
+    debug!("spring {:?}", spring);
+
+    // display_grid(&grid);
+
+    // for i in 0..=1_000_000 {
+    //     eprintln!("springs {:?}", springs);
+
+    //     if !produce_water_2(spring, &mut grid, &mut waters, &mut springs) {
+    //         eprintln!("Tick {}", i);
+    //         // display_grid(&grid);
+    //         break;
+    //     }
+    //     display_grid(&grid);
+    // }
+    let waters = run_stream(spring, &mut grid, recorder);
+
+    display_grid(&grid);
+
+    debug!("count {}", count_water(&grid));
+
+    let keys = waters.keys().filter(|&&v| v.y >= c_min_y).count();
+    debug!("Water {}", waters.keys().count());
+    debug!("Water keys {}", keys);
+
+    let resting = waters
+        .iter()
+        .filter(|(v, t)| v.y >= c_min_y && **t == Tile::Rest)
+        .count();
+    debug!("Resting keys {}", resting);
+
+    Ok((grid, keys, resting))
+}
+
+type Range = std::ops::RangeInclusive<i32>;
+
+fn run_stream(
+    spring: Vector2,
+    grid: &mut Grid<Tile>,
+    recorder: &mut Recorder,
+) -> HashMap<Vector2, Tile> {
+    let mut q = VecDeque::new();
+    q.push_back((spring, Stream::Down));
+    let mut waters: HashMap<Vector2, Tile> = HashMap::new();
+    let mut tick = 0;
+
+    while !q.is_empty() {
+        recorder.record(|| {
+            Frame::new(tick, grid.render(Tile::as_char))
+                .with("streams", q.len())
+                .with("water", waters.len())
+        });
+        tick += 1;
+
+        let (mut pos, dir) = q.pop_front().unwrap();
+
+        trace!("{} {:?}", pos, dir);
+
+        match dir {
+            Stream::Down => {
+                while pos.y + 1 < grid.height() as i32
+                    && !is_resting(pos.down(), grid)
+                {
+                    pos = pos.down();
+                    waters.insert(pos, Tile::Flow);
+                    // grid[pos] = Tile::Flow;
+                    set(pos, Tile::Flow, grid);
+                }
+
+                if is_at_edge(pos, grid) {
+                    continue;
+                }
+
+                if !q.contains(&(pos, Stream::Up)) {
+                    q.push_back((pos, Stream::Up));
+                }
+            }
+
+            Stream::Up => {
+                trace!("up {}", pos);
+                while let Some(range) =
+                    find_walls(pos, grid, floor_below(pos, grid))
+                {
+                    trace!("Container {} {:?}", pos, range);
+
+                    range.clone().for_each(|x| {
+                        let tp = (x, pos.y).into();
+                        waters.insert(tp, Tile::Rest);
+                        // grid[tp] = Tile::Rest;
+                        set(tp, Tile::Rest, grid);
+                    });
+
+                    pos = pos.up();
+                }
+                trace!("up {}", pos);
+
+                waters.insert(pos, Tile::Flow);
+                set(pos, Tile::Flow, grid);
+                //grid[pos] = Tile::Flow;
+                //pos = pos.up();
+
+                if !q.contains(&(pos, Stream::Side)) {
+                    q.push_back((pos, Stream::Side));
+                }
+            }
+            Stream::Side => {
+                let floor = floor_below(pos, grid);
+
+                if floor.is_none() {
+                    continue;
+                }
+
+                let floor = floor.unwrap();
+
+                trace!("hre;{:?}", floor);
+
+                let (start, end) = (*floor.start(), *floor.end());
+
+                let min_pos =
+                    spread(pos, Dir4::Left, &floor, grid, &mut waters);
+                trace!("min {}", min_pos);
+
+                let max_pos =
+                    spread(pos, Dir4::Right, &floor, grid, &mut waters);
+                trace!("max {}", max_pos);
+
+                if min_pos.x < start && !q.contains(&(min_pos, Stream::Down)) {
+                    q.push_back((min_pos, Stream::Down));
+                }
+                if max_pos.x > end && !q.contains(&(max_pos, Stream::Down)) {
+                    q.push_back((max_pos, Stream::Down));
+                }
+            }
+        }
+    }
+
+    recorder.record(|| {
+        Frame::new(tick, grid.render(Tile::as_char))
+            .with("streams", 0)
+            .with("water", waters.len())
+    });
+
+    waters
+}
+
+/// Spreads water from `pos` towards `dir` until it is blocked or has
+/// flowed over the edge of `floor`, returns where the water ends
+fn spread(
+    mut pos: Vector2,
+    dir: Dir4,
+    floor: &Range,
+    grid: &mut Grid<Tile>,
+    waters: &mut HashMap<Vector2, Tile>,
+) -> Vector2 {
+    let over = (*floor.start() - 1)..=(*floor.end() + 1);
+
+    while over.contains(&(pos + dir).x)
+        && is_inside(pos, grid)
+        && !is_resting(pos + dir, grid)
+    {
+        pos += dir;
+        waters.insert(pos, Tile::Flow);
+        set(pos, Tile::Flow, grid);
+    }
+
+    pos
+}
+
+fn find_walls(
+    v: Vector2,
+    grid: &Grid<Tile>,
+    floor: Option<Range>,
+) -> Option<Range> {
+    trace!("Here {} {:?} {:?}", v, grid.get(v), floor);
+    // if !is(v.down(), Tile::Clay, grid) {
+    if !is_resting(v.down(), grid) {
+        return None;
+    }
+
+    trace!("Here2 {} {:?} {:?}", v, grid.get(v), floor);
+
+    let floor = floor?;
+
+    let mut min_pos = v;
+
+    while !is_resting(min_pos.left(), grid) && min_pos.x >= 0 {
+        min_pos = min_pos.left();
+    }
+    trace!("Here3 {} {:?} {:?}", v, grid.get(v), floor);
+
+    let mut max_pos = v;
+
+    while !is_resting(max_pos.right(), grid) && max_pos.x < grid.width() as i32
+    {
+        max_pos = max_pos.right();
+    }
+
+    trace!("{} {}", min_pos, max_pos);
+
+    //Some(floor)
+    if min_pos.x >= *floor.start() && max_pos.x <= *floor.end() {
+        return Some(min_pos.x..=max_pos.x);
+    }
+
+    None
+}
+
+fn floor_below(v: Vector2, grid: &Grid<Tile>) -> Option<Range> {
+    let below = v.down();
+
+    if !is_resting(below, grid) {
+        return None;
+    }
+
+    let end = |dir: Dir4| {
+        let mut pos = below;
+
+        while is_resting(pos + dir, grid) {
+            pos += dir;
+        }
+
+        pos.x
+    };
+
+    Some(end(Dir4::Left)..=end(Dir4::Right))
+}
+
+fn is_inside(v: Vector2, grid: &Grid<Tile>) -> bool {
+    (v.x >= 0 && v.x < grid.width() as i32)
+        && (v.y >= 0 && v.y < grid.height() as i32)
+}
+
+fn is_resting(v: Vector2, grid: &Grid<Tile>) -> bool {
+    grid.get(v) == Some(&Tile::Rest) || grid.get(v) == Some(&Tile::Clay)
+}
+
+fn is_tile(v: Vector2, expected: Tile, grid: &Grid<Tile>) -> bool {
+    tile_at(v, grid) == expected
+}
+
+fn is(v: Vector2, expected: Tile, grid: &Grid<Tile>) -> bool {
+    tile_at(v, grid) == expected
+}
+
+fn is_not(v: Vector2, expected: Tile, grid: &Grid<Tile>) -> bool {
+    tile_at(v, grid) != expected
+    // && tile_at(v, grid) != Tile::Invalid
+}
+
+fn is_full_of_water(mut row: Vector2, grid: &Grid<Tile>) -> bool {
+    if is_tile(row, Tile::Clay, grid) {
+        return false;
+    }
+
+    while is_not(row, Tile::Clay, grid) {
+        if is_not(row, Tile::Rest, grid) {
+            return false;
+        }
+
+        row = row.left();
+    }
+
+    while is_not(row, Tile::Clay, grid) {
+        if is_not(row, Tile::Rest, grid) {
+            return false;
+        }
+
+        row = row.right();
+    }
+
+    true
+}
+
+fn first_down(start: Vector2, grid: &Grid<Tile>) -> Option<(Tile, Vector2)> {
+    for y in (start.y + 1)..grid.height() as i32 {
+        let loc: Vector2 = (start.x, y).into();
+        if let Some(Tile::Sand) = grid.get(loc) {
+            return Some((Tile::Sand, loc));
+        } else if let Some(Tile::Clay) = grid.get(loc) {
+            return Some((Tile::Clay, loc));
+        }
+    }
+
+    None
+}
+
+fn first_left(start: Vector2, grid: &Grid<Tile>) -> Option<(Tile, Vector2)> {
+    if start.y == grid.height() as i32 - 1 {
+        return None;
+    }
+
+    for x in (0..start.x).rev() {
+        let c: Vector2 = (x, start.y).into();
+        if let Some(Tile::Sand) = grid.get(c) {
+            return Some((Tile::Sand, c));
+        } else if let Some(Tile::Clay) = grid.get(c) {
+            return Some((Tile::Clay, c));
+        }
+    }
+    None
+}
+
+fn first_right(start: Vector2, grid: &Grid<Tile>) -> Option<(Tile, Vector2)> {
+    if start.y == grid.height() as i32 - 1 {
+        return None;
+    }
+    for x in (start.x + 1)..grid.width() as i32 {
+        let c: Vector2 = (x, start.y).into();
+        if let Some(Tile::Sand) = grid.get(c) {
+            return Some((Tile::Sand, c));
+        } else if let Some(Tile::Clay) = grid.get(c) {
+            return Some((Tile::Clay, c));
+        }
+    }
+    None
+}
+
+fn last_non_clay_right(
+    start: Vector2,
+    grid: &Grid<Tile>,
+) -> Option<(Tile, Vector2)> {
+    if start.y == grid.height() as i32 - 1 {
+        return None;
+    }
+
+    for x in (start.x + 1)..grid.width() as i32 {
+        let c: Vector2 = (x, start.y).into();
+        if let Some(Tile::Clay) = grid.get(c) {
+            let actual = c.left();
+            if let Some(t) = grid.get(actual) {
+                return Some((*t, actual));
+            }
+        }
+    }
+
+    None
+}
+
+fn is_at_edge(start: Vector2, grid: &Grid<Tile>) -> bool {
+    if start.y == 0 {
+        return true;
+    }
+
+    if start.y == grid.height() as i32 - 1 {
+        return true;
+    }
+
+    if start.x == 0 {
+        return true;
+    }
+
+    if start.x == grid.width() as i32 - 1 {
+        return true;
+    }
+
+    false
+}
+
+fn display_grid(grid: &Grid<Tile>) {
+    trace!("{}", grid.render(Tile::as_char));
+}
+
+fn count_water(grid: &Grid<Tile>) -> usize {
+    grid.values()
+        .filter(|&&v| v == Tile::Flow || v == Tile::Rest)
+        .count()
+}
+
+/// Sets the tile if it is inside the grid
+fn set(v: Vector2, tile: Tile, grid: &mut Grid<Tile>) {
+    if let Some(t) = grid.get_mut(v) {
+        *t = tile;
+    }
+}
+
+fn tile_at(v: Vector2, grid: &Grid<Tile>) -> Tile {
+    grid.get(v).cloned().unwrap_or(Tile::Invalid)
+}
+
+fn read_clay_locations(s: &str) -> Result<Vec<Vector2>> {
+    let lines = crate::parse_lines(s, read_clay_line)?;
+
+    Ok(lines.into_iter().flatten().collect())
+}
+
+fn read_clay_line(line: &str) -> Result<Vec<Vector2>> {
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"(x|y)=(\d+)\.?\.?(\d+)?").unwrap();
+    }
+
+    let mut clay_locations: Vec<Vector2> = Vec::new();
+    let mut x_values = Vec::new();
+    let mut y_values = Vec::new();
+
+    for caps in RE.captures_iter(line) {
+        let name = caps.get(1).map_or("", |m| m.as_str());
+        let start: i32 = crate::get_value(&caps, 2)?;
+        let end: i32 = match caps.get(3) {
+            Some(_) => crate::get_value(&caps, 3)?,
+            None => start,
+        };
+
+        match name {
+            "x" => x_values.extend(start..=end),
+            "y" => y_values.extend(start..=end),
+            v => {
+                return Err(Error::parse(format!("Unknown field {}", v)));
+            }
+        }
+    }
+
+    if x_values.len() == 1 {
+        let x = *x_values.first().unwrap();
+
+        for y in y_values {
+            clay_locations.push((x, y).into());
+        }
+    } else if y_values.len() == 1 {
+        let y = *y_values.first().unwrap();
+
+        for x in x_values {
+            clay_locations.push((x, y).into());
+        }
+    } else {
+        return Err(Error::parse("expected a single x or y coordinate"));
+    }
+
+    Ok(clay_locations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_example_input() {
+        let input = r"
+x=495, y=2..7
+y=7, x=495..501
+x=501, y=3..7
+x=498, y=2..4
+x=506, y=1..2
+x=498, y=10..13
+x=504, y=10..13
+y=13, x=498..504
+        ";
+
+        assert_eq!(57, part1(input.trim()).unwrap());
+    }
+
+    #[test]
+    fn part2_example_input() {
+        let input = r"
+x=495, y=2..7
+y=7, x=495..501
+x=501, y=3..7
+x=498, y=2..4
+x=506, y=1..2
+x=498, y=10..13
+x=504, y=10..13
+y=13, x=498..504
+        ";
+
+        assert_eq!(29, part2(input.trim()).unwrap());
+    }
+}
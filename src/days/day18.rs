@@ -0,0 +1,240 @@
+use crate::record::{Frame, Recorder};
+use crate::{
+    cycle, debug, trace, Answer, Color, Error, Grid, Image, Recording, Result,
+    Solution, Vector2,
+};
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    fn day(&self) -> u32 {
+        18
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        part1(input.trim(), 10).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        part2(input.trim()).map(Answer::from)
+    }
+
+    fn parse(&self, input: &str) -> Result<bool> {
+        read_landscape(input.trim())?;
+        Ok(true)
+    }
+
+    fn image(&self, input: &str) -> Result<Option<Image>> {
+        let grid = landscape_after(input.trim(), 10)?;
+
+        Ok(Some(Image::from_grid(&grid.acres, Acre::color)))
+    }
+
+    /// Part 2 is recorded until the landscape repeats
+    fn record(&self, input: &str, part: u32) -> Result<Option<Recording>> {
+        let initial = read_landscape(input.trim())?;
+        let mut recorder = Recorder::new(format!("Day 18 part {}", part));
+
+        if part == 1 {
+            let mut grid = initial;
+            recorder.record(|| grid.frame(0));
+
+            for minute in 1..=10 {
+                grid = grid.tick();
+                recorder.record(|| grid.frame(minute));
+            }
+        } else {
+            let unrolled = cycle::unroll(initial, Landscape::tick);
+            let cycle = unrolled.cycle;
+
+            for (minute, grid) in unrolled.states.iter().enumerate() {
+                recorder.record(|| {
+                    grid.frame(minute)
+                        .with("cycle_start", cycle.start)
+                        .with("cycle_length", cycle.length)
+                });
+            }
+        }
+
+        Ok(Some(recorder.finish()))
+    }
+}
+
+fn part1(s: &str, minutes: usize) -> Result<usize> {
+    let grid = landscape_after(s, minutes)?;
+
+    let tree_count = grid.tree_count();
+    let lumber_count = grid.lumber_count();
+
+    let total = lumber_count * tree_count;
+
+    debug!("trees {} lumber {}", tree_count, lumber_count);
+
+    Ok(total)
+}
+
+fn landscape_after(s: &str, minutes: usize) -> Result<Landscape> {
+    let grid = read_landscape(s)?;
+
+    grid.display();
+
+    let grid = cycle::nth(grid, Landscape::tick, minutes);
+
+    debug!("After {} minutes", minutes);
+    grid.display();
+
+    Ok(grid)
+}
+
+fn read_landscape(s: &str) -> Result<Landscape> {
+    let acres = Grid::parse(s, |_, ch| match ch {
+        '.' => Some(Acre::Open),
+        '#' => Some(Acre::Lumber),
+        '|' => Some(Acre::Tree),
+        _ => None,
+    })?;
+
+    if acres.is_empty() {
+        return Err(Error::parse("the map is empty"));
+    }
+
+    Ok(Landscape { acres })
+}
+
+fn part2(s: &str) -> Result<usize> {
+    part1(s, 1_000_000_000)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+enum Acre {
+    Open,
+    Tree,
+    Lumber,
+}
+
+impl Acre {
+    fn as_char(&self) -> char {
+        match self {
+            Acre::Open => '.',
+            Acre::Tree => '|',
+            Acre::Lumber => '#',
+        }
+    }
+
+    fn color(&self) -> Color {
+        match self {
+            Acre::Open => Color::rgb(200, 190, 140),
+            Acre::Tree => Color::rgb(30, 140, 40),
+            Acre::Lumber => Color::rgb(110, 70, 30),
+        }
+    }
+}
+
+impl Default for Acre {
+    fn default() -> Self {
+        Acre::Open
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Landscape {
+    acres: Grid<Acre>,
+}
+
+impl Landscape {
+    fn tree_count(&self) -> usize {
+        self.acres.values().filter(|&&p| p == Acre::Tree).count()
+    }
+
+    fn lumber_count(&self) -> usize {
+        self.acres.values().filter(|&&p| p == Acre::Lumber).count()
+    }
+
+    fn count_adjacent(&self, pt: Vector2, acre: Acre) -> usize {
+        self.acres
+            .neighbors8(pt)
+            .filter(|&p| self.acres[p] == acre)
+            .count()
+    }
+
+    fn tick(&self) -> Landscape {
+        let mut next_data = self.acres.clone();
+
+        for (pt, &current) in self.acres.iter() {
+            match current {
+                Acre::Open => {
+                    let count_trees = self.count_adjacent(pt, Acre::Tree);
+
+                    if count_trees >= 3 {
+                        trace!("{} trees {}", pt, count_trees);
+
+                        next_data[pt] = Acre::Tree;
+                    }
+                }
+                Acre::Tree => {
+                    let count = self.count_adjacent(pt, Acre::Lumber);
+
+                    if count >= 3 {
+                        trace!("{} tree into lumber {}", pt, count);
+                        next_data[pt] = Acre::Lumber;
+                    }
+                }
+                Acre::Lumber => {
+                    let count_trees = self.count_adjacent(pt, Acre::Tree);
+                    let count_lumber = self.count_adjacent(pt, Acre::Lumber);
+
+                    if count_trees >= 1 && count_lumber >= 1 {
+                        trace!(
+                            "{} lumber {} {}",
+                            pt,
+                            count_trees,
+                            count_lumber
+                        );
+                        next_data[pt] = Acre::Lumber;
+                    } else {
+                        trace!("{} open {}", pt, count_trees);
+                        next_data[pt] = Acre::Open;
+                    }
+                }
+            }
+        }
+
+        Landscape { acres: next_data }
+    }
+
+    fn display(&self) {
+        trace!("{}", self.acres.render(Acre::as_char));
+    }
+
+    fn frame(&self, minute: usize) -> Frame {
+        let (trees, lumber) = (self.tree_count(), self.lumber_count());
+
+        Frame::new(minute, self.acres.render(Acre::as_char))
+            .with("trees", trees)
+            .with("lumberyards", lumber)
+            .with("value", trees * lumber)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_example_input() {
+        let input = r"
+.#.#...|#.
+.....#|##|
+.|..|...#.
+..|#.....#
+#.#|||#|#|
+...#.||...
+.|....|...
+||...#|.#|
+|.||||..|.
+...#.|..|.
+        ";
+
+        assert_eq!(1147, part1(input.trim(), 10).unwrap());
+    }
+}
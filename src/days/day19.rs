@@ -0,0 +1,94 @@
+use crate::elfcode::{Instruction, Machine, Program};
+use crate::{trace, Answer, Result, Solution};
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    fn day(&self) -> u32 {
+        19
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        part1(input.trim()).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        part2(input.trim()).map(Answer::from)
+    }
+
+    fn parse(&self, input: &str) -> Result<bool> {
+        read_machine(input.trim())?;
+        Ok(true)
+    }
+}
+
+fn show_instructions(code: &[Instruction<i32>]) {
+    for (i, c) in code.iter().enumerate() {
+        trace!("{: <3}: {}", i, c);
+    }
+}
+
+fn read_machine(s: &str) -> Result<Machine<i32, 6>> {
+    let program = Program::parse(s)?;
+
+    show_instructions(&program.instructions);
+
+    Machine::new(program)
+}
+
+fn part1(s: &str) -> Result<i32> {
+    let mut machine = read_machine(s)?;
+
+    machine.run()?;
+
+    trace!("{:?}", machine.registers());
+
+    Ok(machine.registers()[0])
+}
+
+fn part2(s: &str) -> Result<i32> {
+    let mut machine = read_machine(s)?;
+
+    machine.set_registers([1, 0, 0, 0, 0, 0]);
+
+    // The program first computes a large number and then sums its divisors
+    // in a slow nested loop starting from instruction 1
+    machine.run_until(1)?;
+
+    trace!("{:?}", machine.registers());
+
+    let target = machine.registers().iter().cloned().max().unwrap_or(0);
+
+    Ok(sum_of_divisors(target))
+}
+
+fn sum_of_divisors(n: i32) -> i32 {
+    (1..=n).filter(|d| n % d == 0).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_example_input() {
+        let input = r"
+#ip 0
+seti 5 0 1
+seti 6 0 2
+addi 0 1 0
+addr 1 2 3
+setr 1 0 0
+seti 8 0 4
+seti 9 0 5
+        ";
+
+        assert_eq!(6, part1(input.trim()).unwrap());
+    }
+
+    #[test]
+    fn sums_divisors() {
+        assert_eq!(1, sum_of_divisors(1));
+        assert_eq!(2352, sum_of_divisors(996));
+    }
+}
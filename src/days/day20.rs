@@ -0,0 +1,395 @@
+#![allow(dead_code)]
+use crate::{
+    debug, search, trace, Answer, Dir4, Dir8, Error, Grid, Result, Solution,
+    SparseGrid, Vector2,
+};
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    fn day(&self) -> u32 {
+        20
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        part1(input.trim()).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        part2(input.trim()).map(Answer::from)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PathRegex {
+    Blank,
+    Step(Dir4),
+    Branch(Box<PathRegex>, Box<PathRegex>),
+    List(Vec<PathRegex>),
+}
+
+impl PathRegex {
+    fn is_optional_branch(&self) -> bool {
+        match self {
+            PathRegex::Branch(_, p) if **p == PathRegex::Blank => true,
+            _ => false,
+        }
+    }
+}
+
+struct Parser {
+    input: Vec<char>,
+    current: usize,
+}
+
+impl Parser {
+    pub fn new(input: Vec<char>) -> Self {
+        Parser { input, current: 0 }
+    }
+
+    fn parse_all(&mut self) -> Result<PathRegex> {
+        let mut results = Vec::new();
+
+        while self.has_more() {
+            let r = self.parse()?;
+            results.push(r);
+        }
+
+        Ok(PathRegex::List(results))
+    }
+
+    fn parse(&mut self) -> Result<PathRegex> {
+        match self.peek() {
+            Some('(') => {
+                self.eat('(')?;
+
+                let inner = self.regex()?;
+
+                self.eat(')')?;
+
+                Ok(inner)
+            }
+
+            Some(c) => match Dir4::from_compass(c) {
+                Some(dir) => {
+                    self.eat(c)?;
+                    Ok(PathRegex::Step(dir))
+                }
+                None => Err(Error::parse(format!("unexpected '{}'", c))
+                    .at_line(1)
+                    .at_column(self.current + 1)),
+            },
+
+            None => Err(Error::parse("unexpected end of input")
+                .at_line(1)
+                .at_column(self.current + 1)),
+        }
+    }
+
+    fn regex(&mut self) -> Result<PathRegex> {
+        let term = self.term()?;
+
+        if self.peek() == Some('|') {
+            // New option
+            self.eat('|')?;
+            let alt = self.regex()?;
+            Ok(PathRegex::Branch(Box::new(term), Box::new(alt)))
+        } else {
+            Ok(term)
+        }
+    }
+
+    fn term(&mut self) -> Result<PathRegex> {
+        let blank = PathRegex::Blank;
+
+        let mut list: Vec<PathRegex> = Vec::new();
+
+        while self.has_more()
+            && self.peek() != Some(')')
+            && self.peek() != Some('|')
+        {
+            let next = self.factor()?;
+
+            // Factor = sequence
+            list.push(next)
+        }
+
+        if list.is_empty() {
+            Ok(blank)
+        } else {
+            Ok(PathRegex::List(list))
+        }
+    }
+
+    fn factor(&mut self) -> Result<PathRegex> {
+        let base = self.parse()?;
+
+        Ok(base)
+    }
+
+    fn has_more(&self) -> bool {
+        self.current < self.input.len()
+    }
+
+    /// Current character, `None` at the end of the input
+    fn peek(&self) -> Option<char> {
+        self.input.get(self.current).cloned()
+    }
+
+    fn next(&mut self) -> Result<char> {
+        let c = self.peek().ok_or_else(|| {
+            Error::parse("unexpected end of input")
+                .at_line(1)
+                .at_column(self.current + 1)
+        })?;
+        self.eat(c)?;
+        Ok(c)
+    }
+
+    fn eat(&mut self, c: char) -> Result<()> {
+        let error = match self.peek() {
+            Some(found) if found == c => {
+                // Move from current
+                self.current += 1;
+                return Ok(());
+            }
+            Some(found) => {
+                format!("Invalid character {} expected {}", found, c)
+            }
+            None => format!("unexpected end of input, expected {}", c),
+        };
+
+        Err(Error::parse(error).at_line(1).at_column(self.current + 1))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+enum Tile {
+    Wall,
+    Open,
+    DoorUp,
+    DoorSide,
+    Start,
+}
+
+impl Tile {
+    fn as_char(&self) -> char {
+        match self {
+            Tile::Wall => '#',
+            Tile::Open => '.',
+            Tile::DoorSide => '|',
+            Tile::DoorUp => '-',
+            Tile::Start => 'X',
+        }
+    }
+}
+
+impl Default for Tile {
+    fn default() -> Self {
+        Tile::Wall
+    }
+}
+
+type TileMap = SparseGrid<Tile>;
+
+fn part1(s: &str) -> Result<usize> {
+    let (furthest, _) = explore(s)?;
+
+    Ok(furthest)
+}
+
+fn part2(s: &str) -> Result<usize> {
+    let (_, far_rooms) = explore(s)?;
+
+    Ok(far_rooms)
+}
+
+/// Returns the largest number of doors required to reach a room and the
+/// number of rooms that require passing through at least 1000 doors
+fn explore(s: &str) -> Result<(usize, usize)> {
+    let path_chars: Vec<char> =
+        s.chars().filter(|&c| c != '^' && c != '$').collect();
+
+    trace!("C {:?}", path_chars);
+
+    let mut parser = Parser::new(path_chars);
+
+    let path = parser.parse_all()?;
+
+    trace!("Path {:?}", path);
+
+    let mut coords: Vec<Vector2> = Vec::new();
+
+    let mut start = (0, 0).into();
+
+    coords.push(start);
+    let mut tilemap = TileMap::new();
+    tilemap.insert(start, Tile::Start);
+
+    traverse(&path, &mut start, &mut coords, &mut tilemap);
+
+    trace!("Coords {:?}", coords);
+
+    debug!("Bounds {:?}", tilemap.bounds());
+    trace!("{:?}", tilemap);
+
+    let map = Map {
+        tiles: tilemap.to_grid(Tile::default()),
+    };
+
+    map.display();
+
+    debug!("s {}", start);
+
+    map.find_path(start)
+        .ok_or_else(|| Error::invalid("No rooms found"))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+struct Map {
+    tiles: Grid<Tile>,
+}
+
+impl Map {
+    /// Returns the largest number of doors passed to reach a room and the
+    /// number of rooms behind at least 1000 doors
+    fn find_path(&self, start: Vector2) -> Option<(usize, usize)> {
+        let doors = search::dijkstra(start, |current| {
+            let around = current.around();
+
+            IntoIterator::into_iter(around)
+                .filter(move |p| self.can_walk(*p))
+                .map(move |p| (p, if self.is_door(p) { 1 } else { 0 }))
+        });
+
+        let furthest = doors.iter().map(|(_, d)| d).max()?;
+
+        let rooms_through_1000_doors = doors
+            .iter()
+            .filter(|(k, _)| self.is_open(**k))
+            .filter(|(_, v)| *v >= 1000)
+            .count();
+
+        Some((furthest, rooms_through_1000_doors))
+    }
+
+    fn is_door(&self, pt: Vector2) -> bool {
+        match self.get(pt) {
+            Some(Tile::DoorSide) => true,
+            Some(Tile::DoorUp) => true,
+            _ => false,
+        }
+    }
+
+    fn is_open(&self, pt: Vector2) -> bool {
+        match self.get(pt) {
+            Some(Tile::Open) => true,
+            _ => false,
+        }
+    }
+
+    fn can_walk(&self, pt: Vector2) -> bool {
+        match self.get(pt) {
+            Some(Tile::Open) => true,
+            Some(Tile::DoorSide) => true,
+            Some(Tile::DoorUp) => true,
+
+            _ => false,
+        }
+    }
+
+    fn get(&self, p: Vector2) -> Option<&Tile> {
+        self.tiles.get(p)
+    }
+
+    fn display(&self) {
+        trace!("{}", self.tiles.render(Tile::as_char));
+    }
+}
+
+fn insert_adjacent(c: Vector2, tile: Tile, chars: &mut TileMap) {
+    for &dir in Dir8::ALL.iter() {
+        chars.get_or_insert(c + dir, Tile::default());
+    }
+    chars.insert(c, tile);
+}
+
+fn traverse(
+    path: &PathRegex,
+    current: &mut Vector2,
+    coords: &mut Vec<Vector2>,
+    chars: &mut TileMap,
+) {
+    use self::PathRegex::*;
+
+    trace!("Step {:?} {}", path, current);
+    // if path.is_optional_branch() {
+    //     eprintln!("Optional {:?}", path);
+    //     return;
+    // }
+
+    match path {
+        Blank => {}
+        Step(dir) => {
+            let door = if dir.is_vertical() {
+                Tile::DoorUp
+            } else {
+                Tile::DoorSide
+            };
+
+            *current += *dir;
+            insert_adjacent(*current, door, chars);
+            *current += *dir;
+            insert_adjacent(*current, Tile::Open, chars);
+            coords.push(*current);
+        }
+
+        Branch(left, right) => {
+            let mut r = *current;
+            let mut l = *current;
+
+            traverse(&left, &mut l, coords, chars);
+            traverse(&right, &mut r, coords, chars);
+        }
+
+        List(points) => {
+            let mut c = *current;
+
+            for pt in points.iter() {
+                let mut p = c;
+                traverse(pt, &mut p, coords, chars);
+                c = p;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_example_input() {
+        //         assert_eq!(3, part1(r"
+        // #####
+        // #.|.#
+        // #-###
+        // #.|X#
+        // #####
+        //         ".trim()).unwrap());
+        assert_eq!(3, part1(r"^WNE$".trim()).unwrap());
+        assert_eq!(
+            18,
+            part1("^ENNWSWW(NEWS|)SSSEEN(WNSE|)EE(SWEN|)NNN$").unwrap()
+        );
+        assert_eq!(10, part1("^ENWWW(NEEE|SSE(EE|N))$").unwrap());
+    }
+
+    #[test]
+    fn invalid_paths_are_errors() {
+        assert!(part1("X").is_err());
+        assert!(part1("^N)$").is_err());
+        assert!(part1("^N(E|W$").is_err());
+        assert!(part1("^N(E|W").is_err());
+    }
+}
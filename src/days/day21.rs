@@ -0,0 +1,215 @@
+use crate::elfcode::{Instruction, Machine, Opcode, Program};
+use crate::{cycle, trace, Answer, Error, Result, Solution};
+
+pub type Number = i64;
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    fn day(&self) -> u32 {
+        21
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        part1(input.trim()).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        part2(input.trim()).map(Answer::from)
+    }
+
+    fn parse(&self, input: &str) -> Result<bool> {
+        read_machine(input.trim())?;
+        Ok(true)
+    }
+}
+
+fn show_instructions(code: &[Instruction<Number>]) {
+    for (i, c) in code.iter().enumerate() {
+        trace!("{: <3}: {}", i, c);
+    }
+}
+
+fn part1(s: &str) -> Result<Number> {
+    let (first, _) = find_halting_values(s)?;
+
+    Ok(first)
+}
+
+fn part2(s: &str) -> Result<Number> {
+    let (_, last) = find_halting_values(s)?;
+
+    Ok(last)
+}
+
+fn read_machine(s: &str) -> Result<Machine<Number, 6>> {
+    let program = Program::parse(s)?;
+
+    show_instructions(&program.instructions);
+
+    Machine::new(program)
+}
+
+/// Returns the values of register 0 which halt the program after the fewest
+/// and the most instructions
+fn find_halting_values(s: &str) -> Result<(Number, Number)> {
+    let machine = read_machine(s)?;
+
+    let hash = Hash::from_program(machine.program())
+        .ok_or_else(|| Error::invalid("Unable to find the hashing loop"))?;
+
+    find_repeat(&hash)
+}
+
+/// Constants of the hashing loop of the program
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Hash {
+    seed: Number,
+    /// Set in the previous value before hashing it
+    flag: Number,
+    byte: Number,
+    mask: Number,
+    multiplier: Number,
+    /// The value is hashed one digit of this base at a time
+    base: Number,
+}
+
+impl Hash {
+    /// Finds the loop which sets the flag with `bori` and the seed with
+    /// `seti`, the constants are read from the instructions of the loop
+    fn from_program(program: &Program<Number>) -> Option<Hash> {
+        program.instructions.windows(8).find_map(|w| {
+            let ops = [
+                Opcode::Bori,
+                Opcode::Seti,
+                Opcode::Bani,
+                Opcode::Addr,
+                Opcode::Bani,
+                Opcode::Muli,
+                Opcode::Bani,
+                Opcode::Gtir,
+            ];
+
+            if w.iter().zip(ops.iter()).any(|(inst, op)| inst.op != *op)
+                || w[4].b != w[6].b
+            {
+                return None;
+            }
+
+            Some(Hash {
+                flag: w[0].b,
+                seed: w[1].a,
+                byte: w[2].b,
+                mask: w[4].b,
+                multiplier: w[5].b,
+                base: w[7].a,
+            })
+        })
+    }
+
+    /// Value the program compares with register 0 after `previous`
+    fn next_value(&self, previous: Number) -> Number {
+        let mut r1 = self.seed;
+        let mut r3 = previous | self.flag;
+
+        loop {
+            r1 += r3 & self.byte;
+            r1 &= self.mask;
+            r1 *= self.multiplier;
+            r1 &= self.mask;
+
+            if r3 < self.base {
+                return r1;
+            }
+
+            r3 /= self.base;
+        }
+    }
+}
+
+/// Runs the hashing loop of the program until the compared value repeats,
+/// returns the first and the last unique compared values
+fn find_repeat(hash: &Hash) -> Result<(Number, Number)> {
+    let values = cycle::unroll(hash.next_value(0), |&v| hash.next_value(v));
+
+    match (values.states.first(), values.states.last()) {
+        (Some(first), Some(last)) => Ok((*first, *last)),
+        _ => Err(Error::unsolvable("No values were compared")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &'static str = r"
+#ip 4
+seti 123 0 1
+bani 1 456 1
+eqri 1 72 1
+addr 1 4 4
+seti 0 0 4
+seti 0 6 1
+bori 1 65536 3
+seti 6780005 8 1
+bani 3 255 2
+addr 1 2 1
+bani 1 16777215 1
+muli 1 65899 1
+bani 1 16777215 1
+gtir 256 3 2
+addr 2 4 4
+addi 4 1 4
+seti 27 5 4
+seti 0 5 2
+addi 2 1 5
+muli 5 256 5
+gtrr 5 3 5
+addr 5 4 4
+addi 4 1 4
+seti 25 4 4
+addi 2 1 2
+seti 17 7 4
+setr 2 1 3
+seti 7 3 4
+eqrr 1 0 2
+addr 2 4 4
+seti 5 4 4
+    ";
+
+    /// Steps the program until it first compares a register with register 0
+    fn first_comparison(s: &str) -> Number {
+        let mut machine = read_machine(s).unwrap();
+
+        while let Some(inst) = machine.current() {
+            if inst.op == Opcode::Eqrr && inst.b == 0 {
+                return machine.registers()[inst.a as usize];
+            }
+            machine.step().unwrap();
+        }
+
+        panic!("The program halted before comparing with register 0");
+    }
+
+    #[test]
+    fn part1_matches_first_comparison() {
+        assert_eq!(
+            first_comparison(INPUT.trim()),
+            part1(INPUT.trim()).unwrap()
+        );
+    }
+
+    #[test]
+    fn constants_are_read_from_program() {
+        let input = INPUT
+            .trim()
+            .replace("muli 1 65899 1", "muli 1 65521 1")
+            .replace("bani 1 16777215 1", "bani 1 1048575 1");
+
+        assert_eq!(first_comparison(&input), part1(&input).unwrap());
+        assert_ne!(part1(INPUT.trim()).unwrap(), part1(&input).unwrap());
+
+        let input = INPUT.trim().replace("muli 1 65899 1", "mulr 1 1 1");
+        assert!(part1(&input).is_err());
+    }
+}
@@ -0,0 +1,313 @@
+#![allow(dead_code)]
+use crate::{
+    scan, search, trace, Answer, Color, Error, Grid, Image, Result, Solution,
+    Vector2, V2,
+};
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    fn day(&self) -> u32 {
+        22
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        part1(input.trim()).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        part2(input.trim()).map(Answer::from)
+    }
+
+    fn parse(&self, input: &str) -> Result<bool> {
+        read_input(input.trim())?;
+        Ok(true)
+    }
+
+    fn image(&self, input: &str) -> Result<Option<Image>> {
+        let (depth, target) = read_input(input.trim())?;
+
+        let mut grid =
+            Cave::new(target.x as usize + 10, target.y as usize + 10, depth);
+
+        grid.calculate(target);
+
+        Ok(Some(Image::from_grid(&grid.marked(target), Tile::color)))
+    }
+}
+
+fn read_input(s: &str) -> Result<(usize, Vector2)> {
+    let mut lines = s.lines();
+    let mut line = |nr: usize| {
+        lines.next().ok_or_else(|| {
+            Error::parse("expected the depth and the target").at_line(nr)
+        })
+    };
+
+    let first = line(1)?;
+    let (depth,) =
+        scan!(first, "depth: {}", usize).map_err(|e| e.in_line(1, first))?;
+
+    let second = line(2)?;
+    let (x, y) = scan!(second, "target: {},{}", i32, i32)
+        .map_err(|e| e.in_line(2, second))?;
+
+    Ok((depth, Vector2::new(x, y)))
+}
+
+fn part1(s: &str) -> Result<usize> {
+    let (depth, target) = read_input(s)?;
+
+    let mut grid =
+        Cave::new(target.x as usize + 1, target.y as usize + 1, depth);
+
+    let risk = grid.calculate(target);
+    grid.display(target);
+
+    Ok(risk)
+}
+
+fn part2(s: &str) -> Result<usize> {
+    let (depth, target) = read_input(s)?;
+
+    let mut grid = Cave::new(400, target.y as usize * 2, depth);
+
+    grid.calculate(target);
+    grid.display(target);
+
+    grid.find_path((0, 0).into(), target).ok_or_else(|| {
+        Error::unsolvable(format!("No path to the target at {}", target))
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+enum Tile {
+    Rocky,
+    Wet,
+    Narrow,
+    /// The mouth and the target only mark their regions in drawings, they
+    /// are of the type of their erosion level like the other regions
+    Mouth,
+    Target,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+enum Gear {
+    Torch,
+    Climbing,
+    Neither,
+}
+
+impl Tile {
+    fn as_char(&self) -> char {
+        match self {
+            Tile::Rocky => '.',
+            Tile::Wet => '=',
+            Tile::Narrow => '|',
+            Tile::Mouth => 'M',
+            Tile::Target => 'T',
+        }
+    }
+
+    fn color(&self) -> Color {
+        match self {
+            Tile::Rocky => Color::rgb(120, 120, 120),
+            Tile::Wet => Color::rgb(40, 90, 200),
+            Tile::Narrow => Color::rgb(50, 40, 30),
+            Tile::Mouth => Color::rgb(0, 255, 0),
+            Tile::Target => Color::rgb(255, 0, 0),
+        }
+    }
+}
+
+impl Default for Tile {
+    fn default() -> Self {
+        Tile::Rocky
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+struct Cave {
+    tiles: Grid<Tile>,
+    geologic: Grid<usize>,
+    erosion: Grid<usize>,
+    depth: usize,
+}
+
+impl Cave {
+    fn new(width: usize, height: usize, depth: usize) -> Self {
+        Cave {
+            tiles: Grid::new(width, height, Tile::default()),
+            geologic: Grid::new(width, height, 0),
+            erosion: Grid::new(width, height, 0),
+            depth,
+        }
+    }
+
+    fn calculate(&mut self, target: Vector2) -> usize {
+        for y in 0..self.tiles.height() {
+            for x in 0..self.tiles.width() {
+                let index = (x, y);
+                let g_index = {
+                    if (x == 0 && y == 0) || target == (x, y) {
+                        0
+                    } else if y == 0 {
+                        x * 16807
+                    } else if x == 0 {
+                        y * 48271
+                    } else {
+                        self.erosion[(x - 1, y)] * self.erosion[(x, y - 1)]
+                    }
+                };
+                let erosion = { (g_index + self.depth) % 20183 };
+
+                self.geologic[index] = g_index;
+                self.erosion[index] = erosion;
+
+                let tp = {
+                    if erosion % 3 == 0 {
+                        Tile::Rocky
+                    } else if erosion % 3 == 1 {
+                        Tile::Wet
+                    } else {
+                        Tile::Narrow
+                    }
+                };
+                self.tiles[index] = tp;
+            }
+        }
+
+        let mut risk_level = 0;
+
+        for y in 0..=target.y {
+            for x in 0..=target.x {
+                let risk = match self.tiles[(x, y)] {
+                    Tile::Rocky | Tile::Mouth | Tile::Target => 0,
+                    Tile::Wet => 1,
+                    Tile::Narrow => 2,
+                };
+
+                risk_level += risk;
+            }
+        }
+
+        risk_level
+    }
+
+    fn is_walkable(&self, pos: Vector2) -> bool {
+        self.tiles.contains(pos)
+    }
+
+    fn can_move(&self, gear: Gear, from: Vector2, to: Vector2) -> bool {
+        !self.next_gear(gear, from, to).is_empty()
+    }
+
+    fn heuristic(&self, _gear: Gear, from: Vector2, to: Vector2) -> usize {
+        V2::from(from).manhattan(to.into()) as usize
+    }
+
+    fn gear_is_valid(&self, gear: Gear, pt: Vector2) -> bool {
+        if let Some(tile) = self.tiles.get(pt) {
+            match (tile, gear) {
+                (Tile::Rocky, Gear::Torch) => true,
+                (Tile::Rocky, Gear::Climbing) => true,
+                (Tile::Wet, Gear::Climbing) => true,
+                (Tile::Wet, Gear::Neither) => true,
+                (Tile::Narrow, Gear::Neither) => true,
+                (Tile::Narrow, Gear::Torch) => true,
+
+                _ => false,
+            }
+        } else {
+            false
+        }
+    }
+
+    fn next_gear(&self, _gear: Gear, from: Vector2, to: Vector2) -> Vec<Gear> {
+        let mut gears = Vec::new();
+
+        for g in &[Gear::Torch, Gear::Climbing, Gear::Neither] {
+            if self.gear_is_valid(*g, from) && self.gear_is_valid(*g, to) {
+                gears.push(*g);
+            }
+        }
+
+        gears
+    }
+
+    /// Returns the shortest time in minutes to reach `end` holding the torch
+    fn find_path(&self, start: Vector2, end: Vector2) -> Option<usize> {
+        let (_, minutes) = search::astar(
+            (start, Gear::Torch),
+            |&(current, gear)| {
+                let moves = IntoIterator::into_iter(current.around())
+                    .filter(move |&nbr| self.is_walkable(nbr))
+                    .filter(move |&nbr| self.gear_is_valid(gear, nbr))
+                    .map(move |nbr| ((nbr, gear), 1));
+
+                let switches = self
+                    .next_gear(gear, current, current)
+                    .into_iter()
+                    .filter(move |&ngear| ngear != gear)
+                    .map(move |ngear| ((current, ngear), 7));
+
+                moves.chain(switches)
+            },
+            |&(current, gear)| self.heuristic(gear, current, end),
+            |&node| node == (end, Gear::Torch),
+        )?;
+
+        Some(minutes)
+    }
+
+    /// Regions with the mouth and the target marked
+    fn marked(&self, target: Vector2) -> Grid<Tile> {
+        let mut tiles = self.tiles.clone();
+
+        tiles[(0, 0)] = Tile::Mouth;
+        if let Some(tile) = tiles.get_mut(target) {
+            *tile = Tile::Target;
+        }
+
+        tiles
+    }
+
+    fn display(&self, target: Vector2) {
+        trace!("{}", self.marked(target).render(Tile::as_char));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part2_example_input() {
+        let input = r"
+depth: 510
+target: 10,10
+        ";
+
+        assert_eq!(45, part2(input.trim()).unwrap());
+    }
+
+    #[test]
+    fn part1_example_input() {
+        let input = r"
+depth: 510
+target: 10,10
+        ";
+
+        assert_eq!(114, part1(input.trim()).unwrap());
+    }
+
+    #[test]
+    fn target_surrounded_by_wet_regions() {
+        // The target is rocky, it is reached with the climbing gear and the
+        // torch is equipped there
+        let input = "depth: 7758\ntarget: 13,20";
+
+        assert_eq!(297, part1(input).unwrap());
+        assert_eq!(49, part2(input).unwrap());
+    }
+}
@@ -0,0 +1,369 @@
+use crate::{debug, scan, trace, Answer, Cuboid, Error, Result, Solution, V3};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::str::FromStr;
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    fn day(&self) -> u32 {
+        23
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        part1(input.trim()).map(Answer::from)
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer> {
+        part2(input.trim()).map(Answer::from)
+    }
+
+    fn parse(&self, input: &str) -> Result<bool> {
+        crate::parse_lines(input.trim(), str::parse::<Bot>)?;
+        Ok(true)
+    }
+}
+
+fn part1(s: &str) -> Result<usize> {
+    let bots = crate::parse_lines(s, str::parse::<Bot>)?;
+
+    let max = bots.iter().max_by_key(|b| b.radius);
+
+    trace!("Bots, {:?}", bots);
+
+    if let Some(bot) = max {
+        debug!("Largest {:?}", bot);
+
+        let bots_in_range = bots
+            .iter()
+            .map(|b| bot.pos.manhattan(b.pos) as usize)
+            .filter(|d| *d <= bot.radius as usize)
+            .count();
+
+        return Ok(bots_in_range);
+    }
+
+    Ok(0)
+}
+
+fn part2(s: &str) -> Result<usize> {
+    let bots = crate::parse_lines(s, str::parse::<Bot>)?;
+
+    trace!("Bots, {:?}", bots);
+
+    if bots.is_empty() {
+        return Err(Error::invalid("No bots"));
+    }
+
+    // The closest point in range of the most bots is between the bots and
+    // the origin
+    let bounds =
+        Cuboid::from_points(bots.iter().map(|b| b.pos).chain(Some(V3::zero())))
+            .ok_or_else(|| Error::invalid("No bots"))?;
+
+    debug!("Bounds {:?}", bounds);
+
+    let max_size = bounds.size().0.iter().cloned().max().unwrap_or(1);
+
+    let mut size = 1;
+
+    while size < max_size {
+        size *= 2;
+    }
+
+    debug!("Max size {:?} -> size {}", max_size, size);
+
+    let start = Space {
+        nr_bots: bots.len(),
+        pos: bounds.min(),
+        size,
+    };
+
+    let mut spaces = BinaryHeap::new();
+    spaces.push((start.priority(), start));
+
+    while let Some((_, current)) = spaces.pop() {
+        if current.size == 1 {
+            debug!("Found {:?}", current);
+
+            return Ok(current.dist());
+        }
+
+        let ns = current.size / 2;
+
+        let s1 = {
+            let mut s = Space::at(current.pos + (0, 0, 0).into(), ns);
+
+            s.nr_bots = bots.iter().filter(|b| b.in_range(s)).count();
+
+            s
+        };
+
+        let s2 = {
+            let mut s = Space::at(current.pos + (ns, 0, 0).into(), ns);
+
+            s.nr_bots = bots.iter().filter(|b| b.in_range(s)).count();
+
+            s
+        };
+
+        let s3 = {
+            let mut s = Space::at(current.pos + (0, ns, 0).into(), ns);
+
+            s.nr_bots = bots.iter().filter(|b| b.in_range(s)).count();
+
+            s
+        };
+
+        let s4 = {
+            let mut s = Space::at(current.pos + (0, 0, ns).into(), ns);
+
+            s.nr_bots = bots.iter().filter(|b| b.in_range(s)).count();
+
+            s
+        };
+
+        let s5 = {
+            let mut s = Space::at(current.pos + (ns, ns, 0).into(), ns);
+
+            s.nr_bots = bots.iter().filter(|b| b.in_range(s)).count();
+
+            s
+        };
+
+        let s6 = {
+            let mut s = Space::at(current.pos + (0, ns, ns).into(), ns);
+
+            s.nr_bots = bots.iter().filter(|b| b.in_range(s)).count();
+
+            s
+        };
+
+        let s7 = {
+            let mut s = Space::at(current.pos + (ns, 0, ns).into(), ns);
+
+            s.nr_bots = bots.iter().filter(|b| b.in_range(s)).count();
+
+            s
+        };
+
+        let s8 = {
+            let mut s = Space::at(current.pos + (ns, ns, ns).into(), ns);
+
+            s.nr_bots = bots.iter().filter(|b| b.in_range(s)).count();
+
+            s
+        };
+
+        if s1.nr_bots > 0 {
+            spaces.push((s1.priority(), s1));
+        }
+        if s2.nr_bots > 0 {
+            spaces.push((s2.priority(), s2));
+        }
+        if s3.nr_bots > 0 {
+            spaces.push((s3.priority(), s3));
+        }
+        if s4.nr_bots > 0 {
+            spaces.push((s4.priority(), s4));
+        }
+        if s5.nr_bots > 0 {
+            spaces.push((s5.priority(), s5));
+        }
+        if s6.nr_bots > 0 {
+            spaces.push((s6.priority(), s6));
+        }
+        if s7.nr_bots > 0 {
+            spaces.push((s7.priority(), s7));
+        }
+        if s8.nr_bots > 0 {
+            spaces.push((s8.priority(), s8));
+        }
+    }
+
+    Ok(0)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd, Default)]
+struct Space {
+    nr_bots: usize,
+    /// lower left corner
+    pos: V3<i64>,
+    size: i64,
+}
+
+impl Space {
+    pub fn at(pos: V3<i64>, size: i64) -> Self {
+        Space {
+            nr_bots: 0,
+            pos,
+            size,
+        }
+    }
+
+    pub fn cube(&self) -> Cuboid<i64> {
+        Cuboid::from_size(self.pos, V3::new([self.size; 3]))
+            .expect("spaces are not empty")
+    }
+
+    /// Spaces with the most bots come first, then the closest ones and then
+    /// the smallest ones
+    fn priority(&self) -> (usize, Reverse<usize>, Reverse<i64>) {
+        (self.nr_bots, Reverse(self.dist()), Reverse(self.size))
+    }
+
+    /// Distance from the origin to the closest point of the space
+    pub fn dist(&self) -> usize {
+        let closest = self.cube().clamp(V3::zero());
+
+        closest.manhattan(V3::zero()) as usize
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd, Default)]
+struct Bot {
+    pos: V3<i64>,
+    radius: i64,
+}
+
+impl Bot {
+    fn in_range(&self, space: Space) -> bool {
+        // Closest point of the space to the bot
+        let closest = space.cube().clamp(self.pos);
+
+        self.pos.manhattan(closest) <= self.radius
+    }
+}
+
+impl FromStr for Bot {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (x, y, z, r) =
+            scan!(s, "pos=<{},{},{}>, r={}", i64, i64, i64, i64)?;
+
+        Ok(Bot {
+            pos: V3::new([x, y, z]),
+            radius: r,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check;
+
+    #[test]
+    fn part1_example_input() {
+        let input = r"
+pos=<0,0,0>, r=4
+pos=<1,0,0>, r=1
+pos=<4,0,0>, r=3
+pos=<0,2,0>, r=1
+pos=<0,5,0>, r=3
+pos=<0,0,3>, r=1
+pos=<1,1,1>, r=1
+pos=<1,1,2>, r=1
+pos=<1,3,1>, r=1
+        ";
+
+        assert_eq!(7, part1(input.trim()).unwrap());
+    }
+
+    #[test]
+    fn part2_example_input() {
+        let input = r"
+pos=<10,12,12>, r=2
+pos=<12,14,12>, r=2
+pos=<16,12,12>, r=4
+pos=<14,14,14>, r=6
+pos=<50,50,50>, r=200
+pos=<10,10,10>, r=5
+        ";
+
+        assert_eq!(36, part2(input.trim()).unwrap());
+    }
+
+    type BotSpec = (i64, i64, i64, i64);
+
+    fn bots_text(bots: &[BotSpec]) -> String {
+        bots.iter()
+            .map(|(x, y, z, r)| format!("pos=<{},{},{}>, r={}\n", x, y, z, r))
+            .collect()
+    }
+
+    /// Checks every point between the bots and the origin
+    fn reference_part2(bots: &[BotSpec]) -> usize {
+        let mut best = (0, 0);
+
+        for x in -12..=12 {
+            for y in -12..=12 {
+                for z in -12..=12 {
+                    let count = bots
+                        .iter()
+                        .filter(|(bx, by, bz, r)| {
+                            (x - bx).abs() + (y - by).abs() + (z - bz).abs()
+                                <= *r
+                        })
+                        .count();
+                    let dist = (x.abs() + y.abs() + z.abs()) as usize;
+
+                    if (count, std::cmp::Reverse(dist))
+                        > (best.0, std::cmp::Reverse(best.1))
+                    {
+                        best = (count, dist);
+                    }
+                }
+            }
+        }
+
+        best.1
+    }
+
+    #[test]
+    fn part2_matches_reference() {
+        check::check(
+            23,
+            200,
+            |rng| {
+                (0..rng.range(1, 6))
+                    .map(|_| {
+                        (
+                            rng.range(-6, 6),
+                            rng.range(-6, 6),
+                            rng.range(-6, 6),
+                            rng.range(0, 6),
+                        )
+                    })
+                    .collect::<Vec<BotSpec>>()
+            },
+            |bots| {
+                check::shrink_vec(bots, 1, |&(x, y, z, r)| {
+                    let mut smaller = Vec::new();
+                    for x in check::shrink_int(x, 0) {
+                        smaller.push((x, y, z, r));
+                    }
+                    for y in check::shrink_int(y, 0) {
+                        smaller.push((x, y, z, r));
+                    }
+                    for z in check::shrink_int(z, 0) {
+                        smaller.push((x, y, z, r));
+                    }
+                    for r in check::shrink_int(r, 0) {
+                        smaller.push((x, y, z, r));
+                    }
+                    smaller
+                })
+            },
+            |bots| {
+                let s = bots_text(bots);
+
+                check::agree(
+                    part2(s.trim()).map_err(|e| e.to_string())?,
+                    reference_part2(bots),
+                )
+            },
+        );
+    }
+}
@@ -1,64 +1,54 @@
-use aoc::{CustomError, Result};
+use crate::pool::Pool;
+use crate::record::{Frame, Recorder};
+use crate::{parse, trace, Answer, Error, Recording, Result, Solution};
 
-use lazy_static::lazy_static;
-use regex::Regex;
 use std::collections::HashMap;
 
-fn main() -> Result<()> {
-    let s = aoc::read_input()?;
+pub struct Day24;
 
-    part1(&s)?;
-    part2(&s)?;
-
-    Ok(())
-}
-
-fn part1(s: &str) -> Result<usize> {
-    let mut teams: HashMap<Team, Vec<Group>> = HashMap::new();
-    let mut current_team = Team::ImmuneSystem;
-
-    let mut groups = Vec::new();
-
-    for (ind, line) in s.lines().enumerate() {
-        if line.starts_with("Immune System:") {
-            current_team = Team::ImmuneSystem;
-            continue;
-        }
-
-        if line.starts_with("Infection:") {
-            current_team = Team::Infection;
-            continue;
-        }
+impl Solution for Day24 {
+    fn day(&self) -> u32 {
+        24
+    }
 
-        if line.trim().is_empty() {
-            continue;
-        }
+    fn part1(&self, input: &str) -> Result<Answer> {
+        part1(input.trim()).map(Answer::from)
+    }
 
-        let mut group = line.parse::<Group>().map_err(|_| {
-            CustomError(format!("Invalid capture at line {}", ind + 1))
-        })?;
+    fn part2(&self, input: &str) -> Result<Answer> {
+        part2(input.trim()).map(Answer::from)
+    }
 
-        group.team = current_team;
-        let len = teams.entry(current_team).or_insert_with(Vec::new).len();
+    fn parse(&self, input: &str) -> Result<bool> {
+        read_groups(input.trim())?;
+        Ok(true)
+    }
 
-        group.id = len as i64 + 1;
+    /// Part 2 records the fight with the smallest winning boost
+    fn record(&self, input: &str, part: u32) -> Result<Option<Recording>> {
+        let mut groups = read_groups(input.trim())?;
+        let mut recorder = Recorder::new(format!("Day 24 part {}", part));
 
-        groups.push(group.clone());
+        let boost = if part == 1 {
+            0
+        } else {
+            smallest_boost(&groups, Pool::current())?
+        };
+        run_with_boost(&mut groups, boost, &mut recorder);
 
-        teams
-            .entry(current_team)
-            .or_insert_with(Vec::new)
-            .push(group);
+        Ok(Some(recorder.finish()))
     }
+}
+
+fn part1(s: &str) -> Result<usize> {
+    let mut groups = read_groups(s)?;
 
-    // eprintln!("Groups: {:?}", groups);
+    trace!("Groups: {:?}", groups);
 
     show_groups(&groups);
 
     while has_both(&groups) {
         run_fight(&mut groups);
-
-        #[cfg(test)]
         show_groups(&groups);
     }
 
@@ -66,12 +56,10 @@ fn part1(s: &str) -> Result<usize> {
 
     let units: i64 = groups.iter().map(|g| g.units).sum();
 
-    eprintln!("part1: {:?}", units);
-
     Ok(units as usize)
 }
 
-fn part2(s: &str) -> Result<i64> {
+fn read_groups(s: &str) -> Result<Vec<Group>> {
     let mut teams: HashMap<Team, Vec<Group>> = HashMap::new();
     let mut current_team = Team::ImmuneSystem;
 
@@ -92,9 +80,9 @@ fn part2(s: &str) -> Result<i64> {
             continue;
         }
 
-        let mut group = line.parse::<Group>().map_err(|_| {
-            CustomError(format!("Invalid capture at line {}", ind + 1))
-        })?;
+        let mut group = line
+            .parse::<Group>()
+            .map_err(|e| e.in_line(ind + 1, line))?;
 
         group.team = current_team;
         let len = teams.entry(current_team).or_insert_with(Vec::new).len();
@@ -109,6 +97,12 @@ fn part2(s: &str) -> Result<i64> {
             .push(group);
     }
 
+    Ok(groups)
+}
+
+fn part2(s: &str) -> Result<i64> {
+    let mut groups = read_groups(s)?;
+
     let original_groups = groups.clone();
 
     /*
@@ -155,44 +149,8 @@ fn part2(s: &str) -> Result<i64> {
     eprintln!("Min {}", min_boost);
     */
 
-    let mut min = 0;
-    let mut max = 1_000_000;
-
-    for step in &[100_000, 10_000, 1000, 100, 10, 5] {
-        match (
-            find_min_boost(&original_groups, *step, min, max),
-            find_max_boost(&original_groups, *step, min, max),
-        ) {
-            (Some(mn), Some(mx)) => {
-                eprintln!("Step {} min {} max {}", step, mn, mx);
-                min = mn;
-                max = mx;
-            }
-
-            other => panic!("{} {:?}", step, other),
-        }
-    }
-
-    eprintln!("Here {} - {}", min, max);
-
-    let mut boost = min;
-
-    let mut prev = false;
-    loop {
-        let mut g = original_groups.clone();
-
-        let c = run_with_boost(&mut g, boost);
-
-        if !prev && c {
-            break;
-        } else {
-            boost += 1;
-            prev = c;
-        }
-    }
-
-    eprintln!("Boost {}", boost);
-    run_with_boost(&mut groups, boost);
+    let boost = smallest_boost(&original_groups, Pool::current())?;
+    run_with_boost(&mut groups, boost, &mut Recorder::off());
 
     // loop {
     //     let mut groups = original_groups.clone();
@@ -206,68 +164,61 @@ fn part2(s: &str) -> Result<i64> {
 
     let units: i64 = groups.iter().map(|g| g.units).sum();
 
-    eprintln!("part2: {:?}", units);
-
     Ok(units)
 }
 
-fn find_max_boost(
-    original_groups: &[Group],
-    step: i64,
-    min: i64,
-    max: i64,
-) -> Option<i64> {
-    let mut current_boost: i64 = max;
-    let mut prev = false;
+/// Largest boost tried before giving up on the immune system
+const MAX_BOOST: i64 = 1 << 32;
 
-    loop {
-        if current_boost < min {
-            return None;
-        }
+/// Smallest boost that lets the immune system win, the boosts are tried in
+/// batches on the threads of the pool
+fn smallest_boost(original_groups: &[Group], pool: Pool) -> Result<i64> {
+    let wins = |boost: i64| {
         let mut groups = original_groups.to_owned();
 
-        let c = run_with_boost(&mut groups, current_boost);
+        run_with_boost(&mut groups, boost, &mut Recorder::off())
+    };
 
-        if prev && !c {
-            return Some(current_boost + step);
-        } else {
-            current_boost -= step;
-            prev = c;
+    // Winning is not monotonic in the boost, so a winning boost only
+    // bounds the search
+    let mut bound = 1;
+    while !wins(bound) {
+        bound *= 2;
+
+        if bound > MAX_BOOST {
+            return Err(Error::unsolvable("The immune system never wins"));
         }
     }
-}
 
-fn find_min_boost(
-    original_groups: &[Group],
-    step: i64,
-    min: i64,
-    max: i64,
-) -> Option<i64> {
-    let mut current_boost: i64 = min;
-    let mut prev = false;
+    // The boosts are tried in batches of consecutive boosts, one per thread
+    let batch = pool.threads() as i64;
 
-    loop {
-        if current_boost > max {
-            return None;
-        }
-        let mut groups = original_groups.to_owned();
+    for start in (0..=bound).step_by(batch as usize) {
+        let boosts: Vec<i64> = (start..=bound.min(start + batch - 1)).collect();
 
-        let c = run_with_boost(&mut groups, current_boost);
+        let found =
+            pool.find_map(
+                &boosts,
+                |&boost| if wins(boost) { Some(()) } else { None },
+            );
 
-        if !prev && c {
-            return Some(current_boost - step);
-        } else {
-            current_boost += step;
-            prev = c;
+        if let Some((index, _)) = found {
+            return Ok(boosts[index]);
         }
     }
+
+    Err(Error::unsolvable("The immune system never wins"))
 }
 
 fn count_units(groups: &[Group]) -> i64 {
     groups.iter().map(|g| g.units).sum()
 }
 
-fn run_with_boost(groups: &mut Vec<Group>, boost: i64) -> bool {
+fn run_with_boost(
+    groups: &mut Vec<Group>,
+    boost: i64,
+    recorder: &mut Recorder,
+) -> bool {
     for g in groups.iter_mut() {
         if g.team == Team::ImmuneSystem {
             g.damage += boost;
@@ -277,23 +228,25 @@ fn run_with_boost(groups: &mut Vec<Group>, boost: i64) -> bool {
     let mut prev_counts: Vec<i64> = Vec::new();
     prev_counts.push(count_units(&groups));
 
-    // #[cfg(test)]
-    // show_groups(&groups);
+    recorder.record(|| fight_frame(groups, 0, boost));
+
+    show_groups(groups);
     while has_both(&groups) {
         run_fight(groups);
 
+        recorder.record(|| fight_frame(groups, prev_counts.len(), boost));
+
         let count = count_units(&groups);
 
         if prev_counts.iter().rev().take(10).all(|&c| c == count) {
-            eprintln!("Stalemate {}", boost);
+            trace!("Stalemate {}", boost);
             break;
         } else {
             prev_counts.push(count);
         }
     }
 
-    // #[cfg(test)]
-    // show_groups(&groups);
+    show_groups(groups);
 
     let e1 = groups.iter().any(|g| g.team == Team::ImmuneSystem);
     let e2 = groups.iter().any(|g| g.team == Team::Infection);
@@ -308,19 +261,38 @@ fn has_both(groups: &[Group]) -> bool {
 }
 
 fn show_groups(groups: &[Group]) {
+    trace!("{}", groups_to_string(groups));
+}
+
+fn groups_to_string(groups: &[Group]) -> String {
     let (immune, infection): (Vec<_>, Vec<_>) =
         groups.iter().partition(|g| g.team == Team::ImmuneSystem);
-    eprintln!();
-    eprintln!("ImmuneSystem: ");
+
+    let mut out = String::from("ImmuneSystem: \n");
     for g in immune {
-        eprintln!("Group {} contains {} units", g.id, g.units);
+        out.push_str(&format!("Group {} contains {} units\n", g.id, g.units));
     }
-    eprintln!();
-    eprintln!("Infection: ");
+    out.push_str("\nInfection: \n");
     for g in infection {
-        eprintln!("Group {} contains {} units", g.id, g.units);
+        out.push_str(&format!("Group {} contains {} units\n", g.id, g.units));
     }
-    eprintln!();
+
+    out
+}
+
+fn fight_frame(groups: &[Group], round: usize, boost: i64) -> Frame {
+    let units = |team| {
+        groups
+            .iter()
+            .filter(|g| g.team == team)
+            .map(|g| g.units)
+            .sum::<i64>()
+    };
+
+    Frame::new(round, groups_to_string(groups))
+        .with("boost", boost)
+        .with("immune_system", units(Team::ImmuneSystem))
+        .with("infection", units(Team::Infection))
 }
 
 fn run_fight(groups: &mut Vec<Group>) {
@@ -336,8 +308,7 @@ fn run_fight(groups: &mut Vec<Group>) {
         let es: Vec<_> = e.collect();
 
         if let Some(id) = select_target(group, &es[..]) {
-            // #[cfg(test)]
-            // eprintln!("{:?} {} attacking {:?}", group.team, group.id, id);
+            trace!("{:?} {} attacking {:?}", group.team, group.id, id);
             attack.insert((group.team, group.id), id);
             defend.insert(id, (group.team, group.id));
         }
@@ -349,8 +320,7 @@ fn run_fight(groups: &mut Vec<Group>) {
 
     for att in attackers {
         if let Some(target) = attack.get(&att) {
-            // #[cfg(test)]
-            // eprintln!("Attacker {:?} attacking {:?}", att, target);
+            trace!("Attacker {:?} attacking {:?}", att, target);
 
             let target_index = groups
                 .iter()
@@ -421,7 +391,7 @@ fn select_target(
         }
     });
 
-    // eprintln!("Enemies: {:?}", enemies);
+    trace!("Enemies: {:?}", enemies);
     // #[cfg(test)]
     // for enemy in enemies.iter() {
     //     let damage = enemy.would_take_damage(attacker);
@@ -458,8 +428,12 @@ impl Group {
 
         let units_to_lose = std::cmp::min(self.units, units_to_lose);
 
-        // #[cfg(test)]
-        // eprintln!("{:?} {} will lose {} units", self.team, self.id, units_to_lose);
+        trace!(
+            "{:?} {} will lose {} units",
+            self.team,
+            self.id,
+            units_to_lose
+        );
 
         self.units -= units_to_lose;
     }
@@ -480,64 +454,40 @@ impl Group {
 }
 
 impl std::str::FromStr for Group {
-    type Err = CustomError;
-
-    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        lazy_static! {
-            // 17 units each with 5390 hit points (weak to radiation, bludgeoning) with an attack that does 4507 fire damage at initiative 2
-            // (\d+) units each with (\d+) hit points (\(.*\))? with an attack that does (\d+) (\w+) damage at initiative (\d+)
-            // static ref RE: Regex = Regex::new(r"(\d+) units each with (\d+) hit points \(.*\)? with an attack that does (\d+) (\w+) damage at initiative (\d+)").unwrap();
-            // (\d+) units each with (\d+) hit points (\(.*\))?\s*with an attack that does (\d+) (\w+) damage at initiative (\d+)
-            // static ref RE: Regex = Regex::new(r"(\d+) units each with (\d+) hit points (\(.*\))*\s*with an attack that does (\d+) (\w+) damage at initiative (\d+)").unwrap();
-            // (?mi)(\d+) units each with (\d+) hit points (\(.*\))*\s*with an attack that does (\d+) (\w+) damage at initiative (\d+)
-            static ref RE: Regex = Regex::new(r"(?i)(\d+) units each with (\d+) hit points\s*(\(.*\))*\s*with an attack that does (\d+) (\w+) damage at initiative (\d+)").unwrap();
-        }
-        let caps = RE
-            .captures(s)
-            .ok_or_else(|| CustomError("Invalid capture".to_string()))?;
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        // 17 units each with 5390 hit points (weak to radiation, bludgeoning) with an attack that does 4507 fire damage at initiative 2
+        let mut values = parse::scan(
+            s,
+            "{} units each with {} hit points {}with an attack that does {} {} damage at initiative {}",
+        )?;
+
+        let units: i64 = values.read()?;
+        let hp: i64 = values.read()?;
+        let traits = values.read_str()?;
 
-        // eprintln!("caps {:?}", caps);
-        let units: i64 = aoc::get_value(&caps, 1)?;
-        let hp: i64 = aoc::get_value(&caps, 2)?;
         let mut weak = Vec::new();
         let mut immune = Vec::new();
-        if let Some(imm) = caps.get(3).map(|v| v.as_str().trim()) {
-            let values: Vec<&str> = imm
-                .trim_matches(|p| p == '(' || p == ')')
-                .split(';')
-                .map(|s| s.trim())
-                .collect();
-            // eprintln!("Imm {:?}", values);
-
-            for line in values.iter() {
-                if line.starts_with("immune to ") {
-                    let tmp: std::result::Result<_, _> = line
-                        .replace("immune to ", "")
-                        .split(',')
-                        .map(|s| s.trim())
-                        .map(|s| s.parse::<DamageType>())
-                        .collect();
-
-                    let mut tmp = tmp?;
-
-                    immune.append(&mut tmp);
-                } else if line.starts_with("weak to ") {
-                    let tmp: std::result::Result<_, _> = line
-                        .replace("weak to ", "")
-                        .split(',')
-                        .map(|s| s.trim())
-                        .map(|s| s.parse::<DamageType>())
-                        .collect();
-
-                    let mut tmp = tmp?;
-
-                    weak.append(&mut tmp);
+
+        let traits = traits.trim_matches(|p| p == '(' || p == ')');
+        for (kind, list) in parse::key_values(traits, ';', " to ")? {
+            match kind {
+                "immune" => immune.append(&mut parse_damage_types(s, list)?),
+                "weak" => weak.append(&mut parse_damage_types(s, list)?),
+                _ => {
+                    return Err(Error::parse(format!(
+                        "unknown trait '{}'",
+                        kind
+                    ))
+                    .at_token(s, kind))
                 }
             }
         }
-        let damage: i64 = aoc::get_value(&caps, 4)?;
-        let tp: DamageType = aoc::get_value(&caps, 5)?;
-        let init: i64 = aoc::get_value(&caps, 6)?;
+
+        let damage: i64 = values.read()?;
+        let tp: DamageType = values.read()?;
+        let init: i64 = values.read()?;
 
         Ok(Group {
             units,
@@ -553,6 +503,15 @@ impl std::str::FromStr for Group {
     }
 }
 
+/// Parses a comma separated list of damage types, `list` must be a slice of
+/// `line`
+fn parse_damage_types(line: &str, list: &str) -> Result<Vec<DamageType>> {
+    list.split(',')
+        .map(|s| s.trim())
+        .map(|s| s.parse::<DamageType>().map_err(|e| e.at_token(line, s)))
+        .collect()
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
 enum Team {
     ImmuneSystem,
@@ -569,15 +528,15 @@ enum DamageType {
 }
 
 impl std::str::FromStr for DamageType {
-    type Err = CustomError;
-    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self> {
         match s.to_ascii_lowercase().as_str() {
             "fire" => Ok(DamageType::Fire),
             "cold" => Ok(DamageType::Cold),
             "slashing" => Ok(DamageType::Slashing),
             "bludgeoning" => Ok(DamageType::Bludgeoning),
             "radiation" => Ok(DamageType::Radiation),
-            other => Err(CustomError(format!("Unknown damagetype {}", other))),
+            _ => Err(Error::parse(format!("unknown damage type '{}'", s))),
         }
     }
 }
@@ -615,4 +574,38 @@ Infection:
 
         assert_eq!(51, part2(input.trim()).unwrap());
     }
+
+    #[test]
+    fn same_boost_on_any_number_of_threads() {
+        let input = r"
+Immune System:
+17 units each with 5390 hit points (weak to radiation, bludgeoning) with an attack that does 4507 fire damage at initiative 2
+989 units each with 1274 hit points (immune to fire; weak to bludgeoning, slashing) with an attack that does 25 slashing damage at initiative 3
+
+Infection:
+801 units each with 4706 hit points (weak to radiation) with an attack that does 116 bludgeoning damage at initiative 1
+4485 units each with 2961 hit points (immune to radiation; weak to fire, cold) with an attack that does 12 slashing damage at initiative 4
+        ";
+        let groups = read_groups(input.trim()).unwrap();
+
+        for threads in 1..=4 {
+            let pool = Pool::new(threads);
+            assert_eq!(1570, smallest_boost(&groups, pool).unwrap());
+        }
+    }
+
+    #[test]
+    fn unknown_damage_type_names_the_position() {
+        let input = r"
+Immune System:
+17 units each with 5390 hit points (weak to plasma) with an attack that does 4507 fire damage at initiative 2
+        ";
+
+        let err = part1(input.trim()).unwrap_err();
+
+        assert_eq!(
+            "line 2, column 45: unknown damage type 'plasma'",
+            err.to_string()
+        );
+    }
 }
@@ -0,0 +1,128 @@
+use crate::{debug, trace, Answer, DisjointSet, Result, Solution, V4};
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    fn day(&self) -> u32 {
+        25
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer> {
+        part1(input.trim()).map(Answer::from)
+    }
+
+    fn part2(&self, _input: &str) -> Result<Answer> {
+        Ok(Answer::None)
+    }
+
+    fn parse(&self, input: &str) -> Result<bool> {
+        read_points(input.trim())?;
+        Ok(true)
+    }
+}
+
+fn read_points(s: &str) -> Result<Vec<V4<i64>>> {
+    let mut points = Vec::new();
+
+    for line in s.lines() {
+        points.push(line.parse::<V4<i64>>()?);
+    }
+
+    Ok(points)
+}
+
+fn part1(s: &str) -> Result<i64> {
+    let points = read_points(s)?;
+    trace!("{:?}", points);
+
+    debug!("Points: {:?}", points.len());
+
+    let constellations = constellations(&points);
+
+    debug!("Constellations {}", constellations.count());
+    trace!("{:?}", constellations.components());
+
+    Ok(constellations.count() as i64)
+}
+
+/// Points joined into constellations, points close to each other are in the
+/// same constellation
+fn constellations(points: &[V4<i64>]) -> DisjointSet {
+    let mut sets = DisjointSet::new(points.len());
+
+    for (index, &me) in points.iter().enumerate() {
+        for (alt, &other) in points.iter().enumerate().skip(index + 1) {
+            if me.manhattan(other) <= 3 {
+                sets.union(index, alt);
+            }
+        }
+    }
+
+    sets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_example_input() {
+        let sample = r"
+0,0,0,0
+ 3,0,0,0
+ 0,3,0,0
+ 0,0,3,0
+ 0,0,0,3
+ 0,0,0,6
+ 9,0,0,0
+12,0,0,0
+        ";
+
+        assert_eq!(2, part1(sample.trim()).unwrap());
+
+        let sample = r"
+-1,2,2,0
+0,0,2,-2
+0,0,0,-2
+-1,2,0,0
+-2,-2,-2,2
+3,0,2,-1
+-1,3,2,2
+-1,0,-1,0
+0,2,1,-2
+3,0,0,0
+        ";
+
+        assert_eq!(4, part1(sample.trim()).unwrap());
+
+        let sample = r"
+1,-1,0,1
+2,0,-1,0
+3,2,-1,0
+0,0,3,1
+0,0,-1,-1
+2,3,-2,0
+-2,2,0,0
+2,-2,0,-1
+1,-1,0,-1
+3,2,0,2
+        ";
+
+        assert_eq!(3, part1(sample.trim()).unwrap());
+
+        let sample = r"
+1,-1,-1,-2
+-2,-2,0,1
+0,2,1,3
+-2,3,-2,1
+0,2,3,-2
+-1,-1,1,-2
+0,-2,-1,0
+-2,2,3,-1
+1,2,2,0
+-1,-2,0,-2
+        ";
+
+        assert_eq!(8, part1(sample.trim()).unwrap());
+    }
+}
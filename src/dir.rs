@@ -0,0 +1,297 @@
+use std::ops::{Add, AddAssign};
+
+use crate::Vector2;
+
+/// Direction along one of the axes, `y` grows downwards
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub enum Dir4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir4 {
+    /// Clockwise starting from up
+    pub const ALL: [Dir4; 4] = [Dir4::Up, Dir4::Right, Dir4::Down, Dir4::Left];
+
+    /// In the reading order of the positions they point to
+    pub const READING_ORDER: [Dir4; 4] =
+        [Dir4::Up, Dir4::Left, Dir4::Right, Dir4::Down];
+
+    /// Change of position when taking a step
+    pub fn offset(self) -> Vector2 {
+        match self {
+            Dir4::Up => Vector2::new(0, -1),
+            Dir4::Right => Vector2::new(1, 0),
+            Dir4::Down => Vector2::new(0, 1),
+            Dir4::Left => Vector2::new(-1, 0),
+        }
+    }
+
+    /// Direction of a single step, `None` for other offsets
+    pub fn from_offset(offset: Vector2) -> Option<Self> {
+        Dir4::ALL.iter().cloned().find(|d| d.offset() == offset)
+    }
+
+    /// Direction after `turns` quarter turns clockwise, negative turns are
+    /// counterclockwise
+    pub fn rotate(self, turns: i32) -> Self {
+        Dir4::ALL[(self as i32 + turns).rem_euclid(4) as usize]
+    }
+
+    pub fn turn_right(self) -> Self {
+        self.rotate(1)
+    }
+
+    pub fn turn_left(self) -> Self {
+        self.rotate(-1)
+    }
+
+    pub fn reverse(self) -> Self {
+        self.rotate(2)
+    }
+
+    pub fn is_horizontal(self) -> bool {
+        self == Dir4::Left || self == Dir4::Right
+    }
+
+    pub fn is_vertical(self) -> bool {
+        !self.is_horizontal()
+    }
+
+    /// Direction of an arrow, one of `^>v<`
+    pub fn from_arrow(c: char) -> Option<Self> {
+        match c {
+            '^' => Some(Dir4::Up),
+            '>' => Some(Dir4::Right),
+            'v' => Some(Dir4::Down),
+            '<' => Some(Dir4::Left),
+            _ => None,
+        }
+    }
+
+    pub fn arrow(self) -> char {
+        match self {
+            Dir4::Up => '^',
+            Dir4::Right => '>',
+            Dir4::Down => 'v',
+            Dir4::Left => '<',
+        }
+    }
+
+    /// Direction of a compass point, one of `NESW` with north up
+    pub fn from_compass(c: char) -> Option<Self> {
+        match c {
+            'N' => Some(Dir4::Up),
+            'E' => Some(Dir4::Right),
+            'S' => Some(Dir4::Down),
+            'W' => Some(Dir4::Left),
+            _ => None,
+        }
+    }
+
+    pub fn compass(self) -> char {
+        match self {
+            Dir4::Up => 'N',
+            Dir4::Right => 'E',
+            Dir4::Down => 'S',
+            Dir4::Left => 'W',
+        }
+    }
+}
+
+/// Direction along one of the axes or diagonals, `y` grows downwards
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub enum Dir8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Dir8 {
+    /// Clockwise starting from up
+    pub const ALL: [Dir8; 8] = [
+        Dir8::Up,
+        Dir8::UpRight,
+        Dir8::Right,
+        Dir8::DownRight,
+        Dir8::Down,
+        Dir8::DownLeft,
+        Dir8::Left,
+        Dir8::UpLeft,
+    ];
+
+    /// In the reading order of the positions they point to
+    pub const READING_ORDER: [Dir8; 8] = [
+        Dir8::UpLeft,
+        Dir8::Up,
+        Dir8::UpRight,
+        Dir8::Left,
+        Dir8::Right,
+        Dir8::DownLeft,
+        Dir8::Down,
+        Dir8::DownRight,
+    ];
+
+    /// Change of position when taking a step
+    pub fn offset(self) -> Vector2 {
+        match self {
+            Dir8::Up => Vector2::new(0, -1),
+            Dir8::UpRight => Vector2::new(1, -1),
+            Dir8::Right => Vector2::new(1, 0),
+            Dir8::DownRight => Vector2::new(1, 1),
+            Dir8::Down => Vector2::new(0, 1),
+            Dir8::DownLeft => Vector2::new(-1, 1),
+            Dir8::Left => Vector2::new(-1, 0),
+            Dir8::UpLeft => Vector2::new(-1, -1),
+        }
+    }
+
+    /// Direction of a single step, `None` for other offsets
+    pub fn from_offset(offset: Vector2) -> Option<Self> {
+        Dir8::ALL.iter().cloned().find(|d| d.offset() == offset)
+    }
+
+    /// Direction after `turns` eighth turns clockwise, negative turns are
+    /// counterclockwise
+    pub fn rotate(self, turns: i32) -> Self {
+        Dir8::ALL[(self as i32 + turns).rem_euclid(8) as usize]
+    }
+
+    pub fn turn_right(self) -> Self {
+        self.rotate(1)
+    }
+
+    pub fn turn_left(self) -> Self {
+        self.rotate(-1)
+    }
+
+    pub fn reverse(self) -> Self {
+        self.rotate(4)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        (self as i32) % 2 == 1
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(dir: Dir4) -> Self {
+        Dir8::ALL[dir as usize * 2]
+    }
+}
+
+impl From<Dir4> for Vector2 {
+    fn from(dir: Dir4) -> Self {
+        dir.offset()
+    }
+}
+
+impl From<Dir8> for Vector2 {
+    fn from(dir: Dir8) -> Self {
+        dir.offset()
+    }
+}
+
+impl Add<Dir4> for Vector2 {
+    type Output = Vector2;
+
+    fn add(self, dir: Dir4) -> Self::Output {
+        self + dir.offset()
+    }
+}
+
+impl Add<Dir8> for Vector2 {
+    type Output = Vector2;
+
+    fn add(self, dir: Dir8) -> Self::Output {
+        self + dir.offset()
+    }
+}
+
+impl AddAssign<Dir4> for Vector2 {
+    fn add_assign(&mut self, dir: Dir4) {
+        *self = *self + dir;
+    }
+}
+
+impl AddAssign<Dir8> for Vector2 {
+    fn add_assign(&mut self, dir: Dir8) {
+        *self = *self + dir;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotation() {
+        assert_eq!(Dir4::Right, Dir4::Up.turn_right());
+        assert_eq!(Dir4::Left, Dir4::Up.turn_left());
+        assert_eq!(Dir4::Up, Dir4::Down.reverse());
+        assert_eq!(Dir4::Down, Dir4::Left.rotate(-5));
+
+        assert_eq!(Dir8::UpLeft, Dir8::Up.turn_left());
+        assert_eq!(Dir8::DownLeft, Dir8::UpRight.reverse());
+        assert_eq!(Dir8::Right, Dir8::Left.rotate(12));
+
+        for &dir in Dir4::ALL.iter() {
+            assert_eq!(dir, dir.turn_left().turn_right());
+            assert_eq!(Vector2::default(), dir.offset() + dir.reverse());
+            assert_eq!(Some(Dir8::from(dir)), Dir8::from_offset(dir.offset()));
+        }
+    }
+
+    #[test]
+    fn offsets() {
+        let pos = Vector2::new(3, 4);
+
+        assert_eq!(pos.up(), pos + Dir4::Up);
+        assert_eq!(pos.down().left(), pos + Dir8::DownLeft);
+        assert_eq!(Some(Dir4::Left), Dir4::from_offset((-1, 0).into()));
+        assert_eq!(None, Dir4::from_offset((1, 1).into()));
+        assert_eq!(Some(Dir8::DownRight), Dir8::from_offset((1, 1).into()));
+        assert!(Dir8::DownRight.is_diagonal() && !Dir8::Down.is_diagonal());
+    }
+
+    #[test]
+    fn characters() {
+        let arrows: String = Dir4::ALL.iter().map(|d| d.arrow()).collect();
+        let compass: String = Dir4::ALL.iter().map(|d| d.compass()).collect();
+
+        assert_eq!("^>v<", arrows);
+        assert_eq!("NESW", compass);
+
+        for &dir in Dir4::ALL.iter() {
+            assert_eq!(Some(dir), Dir4::from_arrow(dir.arrow()));
+            assert_eq!(Some(dir), Dir4::from_compass(dir.compass()));
+        }
+        assert_eq!(None, Dir4::from_arrow('N'));
+    }
+
+    #[test]
+    fn reading_order() {
+        let pos = Vector2::new(1, 1);
+
+        let mut four: Vec<_> =
+            Dir4::READING_ORDER.iter().map(|&d| pos + d).collect();
+        let mut eight: Vec<_> =
+            Dir8::READING_ORDER.iter().map(|&d| pos + d).collect();
+
+        let sorted = |v: &mut Vec<Vector2>| {
+            let before = v.clone();
+            v.sort_by_key(|p| (p.y, p.x));
+            before == *v
+        };
+
+        assert!(sorted(&mut four));
+        assert!(sorted(&mut eight));
+    }
+}
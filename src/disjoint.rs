@@ -0,0 +1,181 @@
+/// Disjoint sets of the elements `0..len`, also known as union-find
+///
+/// Sets are merged by rank and the paths to the roots are compressed while
+/// finding them, so any sequence of operations takes nearly constant time
+/// per operation.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct DisjointSet {
+    parents: Vec<usize>,
+    ranks: Vec<u8>,
+    sizes: Vec<usize>,
+    count: usize,
+}
+
+impl DisjointSet {
+    /// `len` elements, each in a set of its own
+    pub fn new(len: usize) -> Self {
+        DisjointSet {
+            parents: (0..len).collect(),
+            ranks: vec![0; len],
+            sizes: vec![1; len],
+            count: len,
+        }
+    }
+
+    /// Number of elements
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// Number of sets
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Adds a new element in a set of its own, returns the element
+    pub fn push(&mut self) -> usize {
+        let element = self.parents.len();
+
+        self.parents.push(element);
+        self.ranks.push(0);
+        self.sizes.push(1);
+        self.count += 1;
+
+        element
+    }
+
+    /// Representative element of the set containing `element`
+    pub fn find(&mut self, element: usize) -> usize {
+        let root = self.root(element);
+
+        let mut current = element;
+        while current != root {
+            current = std::mem::replace(&mut self.parents[current], root);
+        }
+
+        root
+    }
+
+    /// Merges the sets of `a` and `b`, `false` if they were already the same
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+
+        if a == b {
+            return false;
+        }
+
+        let (parent, child) = if self.ranks[a] < self.ranks[b] {
+            (b, a)
+        } else {
+            (a, b)
+        };
+
+        if self.ranks[parent] == self.ranks[child] {
+            self.ranks[parent] += 1;
+        }
+
+        self.parents[child] = parent;
+        self.sizes[parent] += self.sizes[child];
+        self.count -= 1;
+
+        true
+    }
+
+    /// Whether `a` and `b` are in the same set
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// Number of elements in the set containing `element`
+    pub fn size_of(&mut self, element: usize) -> usize {
+        let root = self.find(element);
+        self.sizes[root]
+    }
+
+    /// Elements of every set in increasing order, the sets ordered by their
+    /// smallest element
+    pub fn components(&self) -> Vec<Vec<usize>> {
+        let mut index_of_root = vec![None; self.len()];
+        let mut components: Vec<Vec<usize>> = Vec::with_capacity(self.count);
+
+        for element in 0..self.len() {
+            let root = self.root(element);
+
+            let index = *index_of_root[root].get_or_insert_with(|| {
+                components.push(Vec::new());
+                components.len() - 1
+            });
+
+            components[index].push(element);
+        }
+
+        components
+    }
+
+    /// Root of `element` without compressing the path
+    fn root(&self, mut element: usize) -> usize {
+        while self.parents[element] != element {
+            element = self.parents[element];
+        }
+        element
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn union_merges_sets() {
+        let mut sets = DisjointSet::new(6);
+        assert_eq!(6, sets.count());
+
+        assert!(sets.union(0, 1));
+        assert!(sets.union(2, 3));
+        assert!(sets.union(1, 3));
+        assert!(!sets.union(0, 2));
+
+        assert_eq!(3, sets.count());
+        assert!(sets.same(0, 3));
+        assert!(!sets.same(0, 4));
+        assert_eq!(4, sets.size_of(2));
+        assert_eq!(1, sets.size_of(5));
+    }
+
+    #[test]
+    fn components_in_order() {
+        let mut sets = DisjointSet::new(7);
+        sets.union(5, 1);
+        sets.union(6, 0);
+        sets.union(3, 5);
+
+        assert_eq!(
+            vec![vec![0, 6], vec![1, 3, 5], vec![2], vec![4]],
+            sets.components()
+        );
+        assert_eq!(sets.count(), sets.components().len());
+
+        let new = sets.push();
+        sets.union(new, 2);
+        assert_eq!(vec![2, 7], sets.components()[2]);
+        assert_eq!(4, sets.count());
+    }
+
+    #[test]
+    fn long_chains_stay_flat() {
+        let mut sets = DisjointSet::new(100);
+        for i in 1..100 {
+            sets.union(i - 1, i);
+        }
+
+        let root = sets.find(99);
+        assert!((0..100).all(|i| sets.parents[i] == root));
+        assert_eq!(1, sets.count());
+        assert_eq!(100, sets.size_of(0));
+        assert!(DisjointSet::new(0).components().is_empty());
+    }
+}
@@ -0,0 +1,514 @@
+use std::fmt;
+use std::ops::{Add, BitAnd, BitOr, Mul};
+use std::str::FromStr;
+
+use crate::{Error, Result};
+
+/// Integer type held in the registers of a `Machine`
+pub trait Word:
+    Copy
+    + Eq
+    + Ord
+    + Default
+    + fmt::Debug
+    + fmt::Display
+    + FromStr
+    + Add<Output = Self>
+    + Mul<Output = Self>
+    + BitAnd<Output = Self>
+    + BitOr<Output = Self>
+{
+    fn from_bool(value: bool) -> Self;
+
+    fn from_usize(value: usize) -> Self;
+
+    /// The value as an index, `None` when it is negative
+    fn to_usize(self) -> Option<usize>;
+}
+
+macro_rules! impl_word {
+    ($($t:ty),*) => {
+        $(
+            impl Word for $t {
+                fn from_bool(value: bool) -> Self {
+                    value as $t
+                }
+
+                fn from_usize(value: usize) -> Self {
+                    value as $t
+                }
+
+                fn to_usize(self) -> Option<usize> {
+                    if self < 0 {
+                        None
+                    } else {
+                        Some(self as usize)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_word!(i32, i64);
+
+/// The sixteen operations of the device
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub enum Opcode {
+    Addr,
+    Addi,
+    Mulr,
+    Muli,
+    Banr,
+    Bani,
+    Borr,
+    Bori,
+    Setr,
+    Seti,
+    Gtir,
+    Gtri,
+    Gtrr,
+    Eqir,
+    Eqri,
+    Eqrr,
+}
+
+impl Opcode {
+    pub const ALL: [Opcode; 16] = [
+        Opcode::Addr,
+        Opcode::Addi,
+        Opcode::Mulr,
+        Opcode::Muli,
+        Opcode::Banr,
+        Opcode::Bani,
+        Opcode::Borr,
+        Opcode::Bori,
+        Opcode::Setr,
+        Opcode::Seti,
+        Opcode::Gtir,
+        Opcode::Gtri,
+        Opcode::Gtrr,
+        Opcode::Eqir,
+        Opcode::Eqri,
+        Opcode::Eqrr,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Opcode::Addr => "addr",
+            Opcode::Addi => "addi",
+            Opcode::Mulr => "mulr",
+            Opcode::Muli => "muli",
+            Opcode::Banr => "banr",
+            Opcode::Bani => "bani",
+            Opcode::Borr => "borr",
+            Opcode::Bori => "bori",
+            Opcode::Setr => "setr",
+            Opcode::Seti => "seti",
+            Opcode::Gtir => "gtir",
+            Opcode::Gtri => "gtri",
+            Opcode::Gtrr => "gtrr",
+            Opcode::Eqir => "eqir",
+            Opcode::Eqri => "eqri",
+            Opcode::Eqrr => "eqrr",
+        }
+    }
+
+    pub fn is_comparison(self) -> bool {
+        matches!(
+            self,
+            Opcode::Gtir
+                | Opcode::Gtri
+                | Opcode::Gtrr
+                | Opcode::Eqir
+                | Opcode::Eqri
+                | Opcode::Eqrr
+        )
+    }
+}
+
+impl fmt::Display for Opcode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Opcode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Opcode::ALL
+            .iter()
+            .cloned()
+            .find(|op| op.name() == s)
+            .ok_or_else(|| Error::parse(format!("unknown opcode '{}'", s)))
+    }
+}
+
+/// A single instruction, whether `a` and `b` name registers or are
+/// immediate values depends on the opcode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub struct Instruction<W> {
+    pub op: Opcode,
+    pub a: W,
+    pub b: W,
+    pub c: W,
+}
+
+impl<W> Instruction<W> {
+    pub fn new(op: Opcode, a: W, b: W, c: W) -> Self {
+        Instruction { op, a, b, c }
+    }
+}
+
+impl<W: fmt::Display> fmt::Display for Instruction<W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} {} {}", self.op, self.a, self.b, self.c)
+    }
+}
+
+impl<W: Word> FromStr for Instruction<W> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let parts: Vec<&str> = s.split_whitespace().collect();
+
+        if parts.len() != 4 {
+            return Err(Error::parse(format!(
+                "expected 'op a b c', got '{}'",
+                s.trim()
+            )));
+        }
+
+        let op = parts[0]
+            .parse::<Opcode>()
+            .map_err(|e| e.at_token(s, parts[0]))?;
+        let mut values = parts[1..].iter().map(|v| {
+            v.parse::<W>().map_err(|_| {
+                Error::parse(format!("invalid value '{}'", v)).at_token(s, v)
+            })
+        });
+
+        // The length was checked above
+        let mut next = || values.next().unwrap();
+
+        Ok(Instruction::new(op, next()?, next()?, next()?))
+    }
+}
+
+/// A parsed program with its optional instruction pointer binding
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Program<W> {
+    pub ip_reg: Option<usize>,
+    pub instructions: Vec<Instruction<W>>,
+}
+
+impl<W: Word> Program<W> {
+    /// Parses one instruction per line, an optional `#ip N` line binds the
+    /// instruction pointer to register `N`
+    pub fn parse(s: &str) -> Result<Self> {
+        let mut program = Program {
+            ip_reg: None,
+            instructions: Vec::new(),
+        };
+
+        for (index, raw) in s.lines().enumerate() {
+            let line = raw.trim();
+            let error = |e: Error| e.in_line(index + 1, raw);
+
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(reg) = line.strip_prefix("#ip") {
+                if program.ip_reg.is_some() {
+                    return Err(error(Error::parse("duplicate #ip directive")));
+                }
+
+                let reg = reg.trim();
+                let reg = reg.parse::<usize>().map_err(|_| {
+                    error(
+                        Error::parse(format!("invalid register '{}'", reg))
+                            .at_token(raw, reg),
+                    )
+                })?;
+
+                program.ip_reg = Some(reg);
+                continue;
+            }
+
+            let instruction = raw.parse::<Instruction<W>>().map_err(error)?;
+
+            program.instructions.push(instruction);
+        }
+
+        Ok(program)
+    }
+}
+
+impl<W> Program<W> {
+    pub fn len(&self) -> usize {
+        self.instructions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.instructions.is_empty()
+    }
+}
+
+/// Device with `N` registers of type `W` running a `Program`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Machine<W, const N: usize> {
+    registers: [W; N],
+    ip: usize,
+    program: Program<W>,
+}
+
+impl<W: Word, const N: usize> Machine<W, N> {
+    /// Creates a machine with zeroed registers which runs `program`
+    pub fn new(program: Program<W>) -> Result<Self> {
+        if let Some(reg) = program.ip_reg.filter(|&reg| reg >= N) {
+            return Err(Error::invalid(format!(
+                "#ip {} is out of range for {} registers",
+                reg, N
+            )));
+        }
+
+        Ok(Machine {
+            registers: [W::default(); N],
+            ip: 0,
+            program,
+        })
+    }
+
+    /// Creates a machine without a program for executing single
+    /// instructions
+    pub fn with_registers(registers: [W; N]) -> Self {
+        Machine {
+            registers,
+            ip: 0,
+            program: Program::default(),
+        }
+    }
+
+    pub fn registers(&self) -> &[W; N] {
+        &self.registers
+    }
+
+    pub fn registers_mut(&mut self) -> &mut [W; N] {
+        &mut self.registers
+    }
+
+    pub fn set_registers(&mut self, registers: [W; N]) {
+        self.registers = registers;
+    }
+
+    pub fn program(&self) -> &Program<W> {
+        &self.program
+    }
+
+    /// Index of the next instruction
+    pub fn ip(&self) -> usize {
+        self.ip
+    }
+
+    /// The next instruction, `None` once the machine has halted
+    pub fn current(&self) -> Option<Instruction<W>> {
+        self.program.instructions.get(self.ip).cloned()
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.ip >= self.program.len()
+    }
+
+    /// Executes the next instruction, returns false if the machine had
+    /// already halted
+    ///
+    /// The instruction pointer is written to the bound register before and
+    /// read back after the instruction. It is not written when it points
+    /// outside of the program.
+    pub fn step(&mut self) -> Result<bool> {
+        let instruction = match self.current() {
+            Some(instruction) => instruction,
+            None => return Ok(false),
+        };
+
+        if let Some(reg) = self.program.ip_reg {
+            self.registers[reg] = W::from_usize(self.ip);
+        }
+
+        self.execute(instruction)?;
+
+        if let Some(reg) = self.program.ip_reg {
+            // Negative pointers are outside of the program as well
+            self.ip = self.registers[reg].to_usize().unwrap_or(usize::MAX);
+        }
+
+        self.ip = self.ip.saturating_add(1);
+
+        Ok(true)
+    }
+
+    /// Runs until the machine halts, returns the number of executed
+    /// instructions
+    pub fn run(&mut self) -> Result<usize> {
+        let mut count = 0;
+
+        while self.step()? {
+            count += 1;
+        }
+
+        Ok(count)
+    }
+
+    /// Runs until the instruction pointer is at `target`
+    pub fn run_until(&mut self, target: usize) -> Result<()> {
+        while self.ip != target {
+            if !self.step()? {
+                return Err(Error::invalid(format!(
+                    "program halted before reaching {}",
+                    target
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Executes a single instruction without touching the instruction
+    /// pointer
+    pub fn execute(&mut self, instruction: Instruction<W>) -> Result<()> {
+        let Instruction { op, a, b, c } = instruction;
+
+        let value = match op {
+            Opcode::Addr => self.reg(a)? + self.reg(b)?,
+            Opcode::Addi => self.reg(a)? + b,
+            Opcode::Mulr => self.reg(a)? * self.reg(b)?,
+            Opcode::Muli => self.reg(a)? * b,
+            Opcode::Banr => self.reg(a)? & self.reg(b)?,
+            Opcode::Bani => self.reg(a)? & b,
+            Opcode::Borr => self.reg(a)? | self.reg(b)?,
+            Opcode::Bori => self.reg(a)? | b,
+            Opcode::Setr => self.reg(a)?,
+            Opcode::Seti => a,
+            Opcode::Gtir => W::from_bool(a > self.reg(b)?),
+            Opcode::Gtri => W::from_bool(self.reg(a)? > b),
+            Opcode::Gtrr => W::from_bool(self.reg(a)? > self.reg(b)?),
+            Opcode::Eqir => W::from_bool(a == self.reg(b)?),
+            Opcode::Eqri => W::from_bool(self.reg(a)? == b),
+            Opcode::Eqrr => W::from_bool(self.reg(a)? == self.reg(b)?),
+        };
+
+        let index = self.index(c)?;
+        self.registers[index] = value;
+
+        Ok(())
+    }
+
+    fn index(&self, reg: W) -> Result<usize> {
+        reg.to_usize().filter(|&i| i < N).ok_or_else(|| {
+            Error::invalid(format!("register {} is out of range", reg))
+        })
+    }
+
+    fn reg(&self, reg: W) -> Result<W> {
+        self.index(reg).map(|i| self.registers[i])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &'static str = r"
+#ip 0
+seti 5 0 1
+seti 6 0 2
+addi 0 1 0
+addr 1 2 3
+setr 1 0 0
+seti 8 0 4
+seti 9 0 5
+    ";
+
+    #[test]
+    fn parse_program() {
+        let program = Program::<i32>::parse(INPUT.trim()).unwrap();
+
+        assert_eq!(Some(0), program.ip_reg);
+        assert_eq!(7, program.len());
+        assert_eq!(
+            Instruction::new(Opcode::Addi, 0, 1, 0),
+            program.instructions[2]
+        );
+        assert_eq!("setr 1 0 0", program.instructions[4].to_string());
+    }
+
+    #[test]
+    fn parse_errors_name_line() {
+        let err = Program::<i32>::parse("seti 5 0 1\nmove 1 2 3").unwrap_err();
+        assert_eq!("line 2, column 1: unknown opcode 'move'", err.to_string());
+
+        let err = Program::<i64>::parse("#ip 1\naddr 1 x 3").unwrap_err();
+        assert_eq!("line 2, column 8: invalid value 'x'", err.to_string());
+
+        let err = Program::<i32>::parse("#ip 1\n#ip 2").unwrap_err();
+        assert_eq!("line 2: duplicate #ip directive", err.to_string());
+    }
+
+    #[test]
+    fn run_with_bound_ip() {
+        let program = Program::parse(INPUT.trim()).unwrap();
+        let mut machine = Machine::<i32, 6>::new(program).unwrap();
+
+        assert_eq!(5, machine.run().unwrap());
+        assert_eq!(&[6, 5, 6, 0, 0, 9], machine.registers());
+        assert!(machine.is_halted());
+        assert!(!machine.step().unwrap());
+    }
+
+    #[test]
+    fn step_and_run_until() {
+        let program = Program::parse(INPUT.trim()).unwrap();
+        let mut machine = Machine::<i64, 6>::new(program).unwrap();
+
+        assert!(machine.step().unwrap());
+        assert_eq!(1, machine.ip());
+        assert_eq!(
+            Some(Instruction::new(Opcode::Seti, 6, 0, 2)),
+            machine.current()
+        );
+
+        machine.run_until(4).unwrap();
+        assert_eq!(&[3, 5, 6, 0, 0, 0], machine.registers());
+
+        assert!(machine.run_until(2).is_err());
+    }
+
+    #[test]
+    fn execute_checks_registers() {
+        let mut machine = Machine::with_registers([3, 2, 1, 1]);
+
+        machine
+            .execute(Instruction::new(Opcode::Mulr, 2, 1, 2))
+            .unwrap();
+        assert_eq!(&[3, 2, 2, 1], machine.registers());
+
+        machine
+            .execute(Instruction::new(Opcode::Gtir, 4, 0, 3))
+            .unwrap();
+        assert_eq!(&[3, 2, 2, 1], machine.registers());
+
+        assert!(machine
+            .execute(Instruction::new(Opcode::Addr, 4, 0, 0))
+            .is_err());
+        assert!(machine
+            .execute(Instruction::new(Opcode::Seti, 4, 0, 4))
+            .is_err());
+        assert!(Machine::<i32, 4>::new(Program {
+            ip_reg: Some(4),
+            instructions: Vec::new(),
+        })
+        .is_err());
+    }
+}
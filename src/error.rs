@@ -0,0 +1,301 @@
+use std::error;
+use std::fmt;
+use std::io;
+use std::num::ParseIntError;
+use std::path::{Path, PathBuf};
+
+/// Where in the input an error was found, every part is optional
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Position {
+    pub path: Option<PathBuf>,
+    /// 1-based line number
+    pub line: Option<usize>,
+    /// 1-based column in characters
+    pub column: Option<usize>,
+    /// The offending line
+    pub snippet: Option<String>,
+}
+
+impl Position {
+    fn is_empty(&self) -> bool {
+        self.path.is_none() && self.line.is_none()
+    }
+}
+
+/// Formats as `path:line:column`, or `line L, column C` without a path
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.path {
+            Some(path) => {
+                write!(f, "{}", path.display())?;
+                if let Some(line) = self.line {
+                    write!(f, ":{}", line)?;
+                    if let Some(column) = self.column {
+                        write!(f, ":{}", column)?;
+                    }
+                }
+                Ok(())
+            }
+            None => match (self.line, self.column) {
+                (Some(line), Some(column)) => {
+                    write!(f, "line {}, column {}", line, column)
+                }
+                (Some(line), None) => write!(f, "line {}", line),
+                _ => Ok(()),
+            },
+        }
+    }
+}
+
+/// Error of reading an input or solving a puzzle
+#[derive(Debug)]
+pub enum Error {
+    /// Reading a file or stdin failed
+    Io {
+        path: Option<PathBuf>,
+        source: io::Error,
+    },
+    /// The input is not in the expected format
+    Parse { message: String, position: Position },
+    /// The input is well-formed but breaks an assumption of the puzzle
+    Invalid { message: String, position: Position },
+    /// No answer could be found for the input
+    Unsolvable(String),
+}
+
+impl Error {
+    pub fn io(path: &Path, source: io::Error) -> Self {
+        Error::Io {
+            path: Some(path.to_owned()),
+            source,
+        }
+    }
+
+    pub fn parse<S: Into<String>>(message: S) -> Self {
+        Error::Parse {
+            message: message.into(),
+            position: Position::default(),
+        }
+    }
+
+    pub fn invalid<S: Into<String>>(message: S) -> Self {
+        Error::Invalid {
+            message: message.into(),
+            position: Position::default(),
+        }
+    }
+
+    pub fn unsolvable<S: Into<String>>(message: S) -> Self {
+        Error::Unsolvable(message.into())
+    }
+
+    /// Where in the input the error was found, if it is about the input
+    pub fn position(&self) -> Option<&Position> {
+        match self {
+            Error::Parse { position, .. } | Error::Invalid { position, .. } => {
+                Some(position)
+            }
+            _ => None,
+        }
+    }
+
+    fn position_mut(&mut self) -> Option<&mut Position> {
+        match self {
+            Error::Parse { position, .. } | Error::Invalid { position, .. } => {
+                Some(position)
+            }
+            _ => None,
+        }
+    }
+
+    /// Sets the line number unless it is already known
+    pub fn at_line(mut self, line: usize) -> Self {
+        if let Some(position) = self.position_mut() {
+            position.line = position.line.or(Some(line));
+        }
+        self
+    }
+
+    /// Sets the column unless it is already known
+    pub fn at_column(mut self, column: usize) -> Self {
+        if let Some(position) = self.position_mut() {
+            position.column = position.column.or(Some(column));
+        }
+        self
+    }
+
+    /// Sets the column to where `token` starts in `line`, `token` must be
+    /// a slice of `line`
+    pub fn at_token(self, line: &str, token: &str) -> Self {
+        let start = line.as_ptr() as usize;
+        let offset = (token.as_ptr() as usize).wrapping_sub(start);
+
+        match line.get(..offset) {
+            Some(before) => self.at_column(before.chars().count() + 1),
+            None => self,
+        }
+    }
+
+    /// Sets the line number and the offending line unless they are already
+    /// known
+    pub fn in_line(mut self, nr: usize, line: &str) -> Self {
+        if let Some(position) = self.position_mut() {
+            position.line = position.line.or(Some(nr));
+            if position.snippet.is_none() {
+                position.snippet = Some(line.to_owned());
+            }
+        }
+        self
+    }
+
+    /// Sets the path of the file the error is about unless it is already
+    /// known
+    pub fn with_path(mut self, path: &Path) -> Self {
+        match &mut self {
+            Error::Io { path: p, .. } => {
+                if p.is_none() {
+                    *p = Some(path.to_owned());
+                }
+            }
+            Error::Parse { position, .. } | Error::Invalid { position, .. } => {
+                if position.path.is_none() {
+                    position.path = Some(path.to_owned());
+                }
+            }
+            Error::Unsolvable(_) => {}
+        }
+        self
+    }
+}
+
+/// The alternate form `{:#}` also shows the offending line with a marker
+/// under the column
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io {
+                path: Some(path),
+                source,
+            } => return write!(f, "{}: {}", path.display(), source),
+            Error::Io { path: None, source } => return write!(f, "{}", source),
+            Error::Unsolvable(message) => {
+                return write!(f, "no solution: {}", message)
+            }
+            _ => {}
+        }
+
+        let (message, position) = match self {
+            Error::Parse { message, position }
+            | Error::Invalid { message, position } => (message, position),
+            _ => unreachable!(),
+        };
+
+        if position.is_empty() {
+            write!(f, "{}", message)?;
+        } else {
+            write!(f, "{}: {}", position, message)?;
+        }
+
+        if let (true, Some(snippet)) = (f.alternate(), &position.snippet) {
+            write!(f, "\n    | {}", snippet)?;
+            if let Some(column) = position.column {
+                write!(f, "\n    | {:>width$}", "^", width = column)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(source: io::Error) -> Self {
+        Error::Io { path: None, source }
+    }
+}
+
+impl From<ParseIntError> for Error {
+    fn from(e: ParseIntError) -> Self {
+        Error::parse(e.to_string())
+    }
+}
+
+/// Parses every non-blank line of `s` with `f`, errors get the line number
+/// and the offending line attached
+pub fn parse_lines<T, F>(s: &str, mut f: F) -> Result<Vec<T>, Error>
+where
+    F: FnMut(&str) -> Result<T, Error>,
+{
+    s.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| f(line).map_err(|e| e.in_line(index + 1, line)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unknown_damage(line: &str) -> Result<(), Error> {
+        let token = line.split(' ').find(|t| t.starts_with("plasma"));
+
+        match token {
+            Some(token) => Err(Error::parse("unknown damage type 'plasma'")
+                .at_token(line, token)),
+            None => Ok(()),
+        }
+    }
+
+    #[test]
+    fn errors_name_the_position() {
+        let input = "weak to fire\n\nweak to plasma";
+
+        let err = parse_lines(input, unknown_damage).unwrap_err();
+        assert_eq!(
+            "line 3, column 9: unknown damage type 'plasma'",
+            err.to_string()
+        );
+
+        let err = err.with_path(Path::new("input/day24.txt"));
+        assert_eq!(
+            "input/day24.txt:3:9: unknown damage type 'plasma'",
+            err.to_string()
+        );
+        assert_eq!(
+            "input/day24.txt:3:9: unknown damage type 'plasma'\n    \
+             | weak to plasma\n    |         ^",
+            format!("{:#}", err)
+        );
+    }
+
+    #[test]
+    fn inner_positions_are_kept() {
+        let err = Error::invalid("bad").at_line(2).at_line(5).at_column(3);
+
+        assert_eq!("line 2, column 3: bad", err.to_string());
+        assert_eq!(Some(2), err.position().and_then(|p| p.line));
+
+        let err = Error::unsolvable("no path").at_line(1);
+        assert_eq!("no solution: no path", err.to_string());
+        assert_eq!(None, err.position());
+    }
+
+    #[test]
+    fn converts_std_errors() {
+        let err: Error = "x".parse::<i32>().unwrap_err().into();
+        assert_eq!("invalid digit found in string", err.to_string());
+
+        let err = Error::from(io::Error::new(io::ErrorKind::Other, "closed"))
+            .with_path(Path::new("in.txt"));
+        assert_eq!("in.txt: closed", err.to_string());
+    }
+}
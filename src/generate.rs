@@ -0,0 +1,1441 @@
+//! Seeded generators of synthetic puzzle inputs
+//!
+//! Every generator produces input in the format of its day that the
+//! solvers can solve, the same seed and size always produce the same
+//! input. Useful for stress testing and benchmarking the solvers with
+//! inputs larger or smaller than the real ones.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt::Write;
+
+use crate::elfcode::{Instruction, Machine, Opcode, Program};
+use crate::{Error, Result};
+
+/// Small deterministic random number generator (SplitMix64), produces the
+/// same sequence for a seed on every platform
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value in `0..n`, `n` must not be zero
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "empty range");
+
+        ((u128::from(self.next_u64()) * n as u128) >> 64) as usize
+    }
+
+    /// Uniform value in `low..=high`
+    pub fn range(&mut self, low: i64, high: i64) -> i64 {
+        assert!(low <= high, "empty range {}..={}", low, high);
+
+        let span = (high as i128 - low as i128 + 1) as u128;
+        (low as i128 + ((u128::from(self.next_u64()) * span) >> 64) as i128)
+            as i64
+    }
+
+    /// True with probability `p`
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i + 1);
+            items.swap(i, j);
+        }
+    }
+}
+
+/// Size of the real input of the day, see `generate` for what the size
+/// means for each day
+pub fn default_size(day: u32) -> Option<usize> {
+    let size = match day {
+        1 => 1000,
+        2 => 250,
+        3 => 1300,
+        4 => 400,
+        5 => 50000,
+        6 => 50,
+        7 => 26,
+        8 => 2000,
+        9 => 70000,
+        10 => 8,
+        11 => 1,
+        12 => 100,
+        13 => 8,
+        14 => 6,
+        15 => 32,
+        16 => 800,
+        17 => 500,
+        18 => 50,
+        19 => 1,
+        20 => 14000,
+        21 => 1,
+        22 => 700,
+        23 => 1000,
+        24 => 10,
+        25 => 1400,
+        _ => return None,
+    };
+
+    Some(size)
+}
+
+/// Generates an input for `day`
+///
+/// `size` is the number of lines or items for list inputs, the side of
+/// the map for map inputs and the length of the regex on day 20. Day 10
+/// spells `size` letters, day 13 has `size` pairs of colliding carts,
+/// day 14 has `size` digits up to 7 and day 22 has the target `size` deep.
+/// Day 23 needs at least 16 bots. Days 11, 19 and 21 ignore the size.
+///
+/// Day 10 reads the font from `day10_letters.txt` in the input directory.
+pub fn generate(day: u32, seed: u64, size: usize) -> Result<String> {
+    let mut rng = Rng::new(seed);
+    let rng = &mut rng;
+    let size = size.max(1);
+
+    let input = match day {
+        1 => frequency_changes(rng, size),
+        2 => box_ids(rng, size),
+        3 => claims(rng, size),
+        4 => guard_log(rng, size),
+        5 => polymer(rng, size),
+        6 => coordinates(rng, size),
+        7 => step_dependencies(rng, size),
+        8 => license_tree(rng, size),
+        9 => marble_game(rng, size),
+        10 => light_vectors(rng, size)?,
+        11 => format!("{}\n", rng.range(1000, 9999)),
+        12 => plant_rules(rng, size),
+        13 => track_map(rng, size),
+        14 => recipe_count(rng, size),
+        15 => combat_map(rng, size),
+        16 => sample_dump(rng, size)?,
+        17 => clay_veins(rng, size),
+        18 => lumber_map(rng, size),
+        19 => divisor_program(rng)?,
+        20 => door_regex(rng, size),
+        21 => halting_program(rng)?,
+        22 => cave_scan(rng, size),
+        23 if size < MIN_NANOBOTS => {
+            return Err(Error::invalid(format!(
+                "Day 23 needs a size of at least {}",
+                MIN_NANOBOTS
+            )))
+        }
+        23 => nanobots(rng, size),
+        24 => army_groups(rng, size),
+        25 => points_4d(rng, size),
+        _ => {
+            return Err(Error::invalid(format!("No generator for day {}", day)))
+        }
+    };
+
+    Ok(input)
+}
+
+/// Joins the lines with a newline after each line
+fn lines<I: IntoIterator<Item = String>>(lines: I) -> String {
+    lines.into_iter().fold(String::new(), |mut out, line| {
+        out.push_str(&line);
+        out.push('\n');
+        out
+    })
+}
+
+/// At least 3 changes for which part 2 always finds a repeated frequency
+///
+/// The last change makes a pass add up to the difference of the totals
+/// after two earlier changes `j < k`. The next pass then reaches the total
+/// after `k` where the first one was after `j`, if nothing repeated before.
+fn frequency_changes(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(3);
+
+    let mut changes: Vec<i64> = (0..size - 1)
+        .map(|_| match rng.range(-20, 19) {
+            0 => 20,
+            change => change,
+        })
+        .collect();
+
+    let totals: Vec<i64> = Some(0)
+        .into_iter()
+        .chain(changes.iter().scan(0, |total, change| {
+            *total += change;
+            Some(*total)
+        }))
+        .collect();
+
+    // With j = 1 and k = size - 1 the last change is minus the first one,
+    // so a change other than zero is always found
+    let last = loop {
+        let k = rng.range(1, size as i64 - 1) as usize;
+        let j = rng.below(k);
+
+        match totals[k] - totals[j] - totals[size - 1] {
+            0 => continue,
+            last => break last,
+        }
+    };
+    changes.push(last);
+
+    lines(changes.into_iter().map(|change| format!("{:+}", change)))
+}
+
+/// Random ids with exactly one pair of ids that differ by a single letter
+fn box_ids(rng: &mut Rng, size: usize) -> String {
+    let random_id = |rng: &mut Rng| -> Vec<u8> {
+        (0..26).map(|_| b'a' + rng.below(26) as u8).collect()
+    };
+
+    let mut ids: Vec<Vec<u8>> =
+        (0..size.max(2) - 1).map(|_| random_id(rng)).collect();
+
+    let mut pair = rng.pick(&ids).clone();
+    let index = rng.below(pair.len());
+    pair[index] = b'a' + (pair[index] - b'a' + rng.range(1, 25) as u8) % 26;
+    ids.push(pair);
+    rng.shuffle(&mut ids);
+
+    lines(ids.into_iter().map(|id| String::from_utf8(id).unwrap()))
+}
+
+/// Overlapping claims, one claim is placed apart from the others
+fn claims(rng: &mut Rng, size: usize) -> String {
+    let alone = rng.below(size);
+
+    lines((0..size).map(|index| {
+        let (x, y, w, h) = if index == alone {
+            (
+                rng.range(950, 980),
+                rng.range(0, 980),
+                rng.range(5, 19),
+                rng.range(5, 19),
+            )
+        } else {
+            (
+                rng.range(0, 900),
+                rng.range(0, 970),
+                rng.range(10, 29),
+                rng.range(10, 29),
+            )
+        };
+
+        format!("#{} @ {},{}: {}x{}", index + 1, x, y, w, h)
+    }))
+}
+
+/// Date `days` days after 1518-01-01
+fn date(mut days: usize) -> (usize, usize, usize) {
+    let mut year = 1518;
+
+    loop {
+        let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+        let lengths = [
+            31,
+            if leap { 29 } else { 28 },
+            31,
+            30,
+            31,
+            30,
+            31,
+            31,
+            30,
+            31,
+            30,
+            31,
+        ];
+
+        for (month, &length) in lengths.iter().enumerate() {
+            if days < length {
+                return (year, month + 1, days + 1);
+            }
+            days -= length;
+        }
+
+        year += 1;
+    }
+}
+
+/// `size` shifts of a few guards in random order, guards begin their shift
+/// shortly before or after midnight and take naps during the midnight hour
+fn guard_log(rng: &mut Rng, size: usize) -> String {
+    let guards: Vec<i64> = (0..(size / 20).max(2))
+        .map(|_| rng.range(10, 3500))
+        .collect();
+
+    let stamp = |days: usize, hour: i64, minute: i64| {
+        let (year, month, day) = date(days);
+        format!(
+            "[{}-{:02}-{:02} {:02}:{:02}]",
+            year, month, day, hour, minute
+        )
+    };
+
+    let mut log = Vec::new();
+
+    for shift in 1..=size {
+        let guard = rng.pick(&guards);
+
+        if rng.chance(0.5) {
+            let minute = rng.range(45, 59);
+            log.push(format!(
+                "{} Guard #{} begins shift",
+                stamp(shift - 1, 23, minute),
+                guard
+            ));
+        } else {
+            let minute = rng.range(0, 5);
+            log.push(format!(
+                "{} Guard #{} begins shift",
+                stamp(shift, 0, minute),
+                guard
+            ));
+        }
+
+        let naps = if shift == 1 {
+            rng.range(1, 3)
+        } else {
+            rng.range(0, 3)
+        };
+        let mut minutes = BTreeSet::new();
+        while minutes.len() < 2 * naps as usize {
+            minutes.insert(rng.range(6, 59));
+        }
+
+        for (index, minute) in minutes.into_iter().enumerate() {
+            let event = if index % 2 == 0 {
+                "falls asleep"
+            } else {
+                "wakes up"
+            };
+            log.push(format!("{} {}", stamp(shift, 0, minute), event));
+        }
+    }
+
+    rng.shuffle(&mut log);
+
+    lines(log)
+}
+
+/// Units that mostly react with a unit opened earlier, so the polymer
+/// shrinks a lot
+fn polymer(rng: &mut Rng, size: usize) -> String {
+    let mut out = String::with_capacity(size + 1);
+    let mut open: Vec<u8> = Vec::new();
+
+    while out.len() < size {
+        match open.pop() {
+            Some(unit) if rng.chance(0.45) => out.push((unit ^ 0x20) as char),
+            top => {
+                open.extend(top);
+
+                let unit = b'a' + rng.below(26) as u8;
+                let unit = if rng.chance(0.5) {
+                    unit.to_ascii_uppercase()
+                } else {
+                    unit
+                };
+                out.push(unit as char);
+                open.push(unit);
+            }
+        }
+    }
+
+    out.push('\n');
+    out
+}
+
+/// Distinct coordinates, the first five form a cross so at least one area
+/// is finite
+fn coordinates(rng: &mut Rng, size: usize) -> String {
+    let (x, y, d) =
+        (rng.range(150, 250), rng.range(150, 250), rng.range(20, 60));
+    let mut points =
+        vec![(x, y), (x - d, y), (x + d, y), (x, y - d), (x, y + d)];
+    let mut seen: HashSet<_> = points.iter().cloned().collect();
+
+    while points.len() < size.max(5) {
+        let point = (rng.range(40, 360), rng.range(40, 360));
+        if seen.insert(point) {
+            points.push(point);
+        }
+    }
+
+    rng.shuffle(&mut points);
+
+    lines(points.into_iter().map(|(x, y)| format!("{}, {}", x, y)))
+}
+
+/// Dependencies between `size` steps (at most 26) that follow a random
+/// order so they never form a cycle
+fn step_dependencies(rng: &mut Rng, size: usize) -> String {
+    let mut steps: Vec<char> = (b'A'..=b'Z').map(char::from).collect();
+    rng.shuffle(&mut steps);
+    steps.truncate(size.clamp(2, 26));
+
+    let mut edges = Vec::new();
+
+    for after in 1..steps.len() {
+        let before = edges.len();
+
+        for first in 0..after {
+            if rng.chance(0.3) {
+                edges.push((steps[first], steps[after]));
+            }
+        }
+
+        if edges.len() == before {
+            edges.push((steps[rng.below(after)], steps[after]));
+        }
+    }
+
+    rng.shuffle(&mut edges);
+
+    lines(edges.into_iter().map(|(first, then)| {
+        format!(
+            "Step {} must be finished before step {} can begin.",
+            first, then
+        )
+    }))
+}
+
+/// Tree of `size` nodes, each node picks a random earlier node as its
+/// parent
+fn license_tree(rng: &mut Rng, size: usize) -> String {
+    let mut children: Vec<Vec<usize>> = vec![Vec::new(); size];
+    for node in 1..size {
+        let parent = rng.below(node);
+        children[parent].push(node);
+    }
+
+    let mut numbers = Vec::new();
+    write_node(rng, &children, 0, &mut numbers);
+
+    let numbers: Vec<String> = numbers.iter().map(|n| n.to_string()).collect();
+
+    numbers.join(" ") + "\n"
+}
+
+fn write_node(
+    rng: &mut Rng,
+    children: &[Vec<usize>],
+    node: usize,
+    numbers: &mut Vec<usize>,
+) {
+    let count = children[node].len();
+    let metadata = rng.range(1, 5) as usize;
+    numbers.push(count);
+    numbers.push(metadata);
+
+    for &child in &children[node] {
+        write_node(rng, children, child, numbers);
+    }
+
+    // Metadata of nodes with children mostly refers to existing children
+    let highest = if count == 0 { 9 } else { count as i64 + 1 };
+    for _ in 0..metadata {
+        numbers.push(rng.range(1, highest) as usize);
+    }
+}
+
+fn marble_game(rng: &mut Rng, size: usize) -> String {
+    format!(
+        "{} players; last marble is worth {} points\n",
+        rng.range(9, 480),
+        size.max(23)
+    )
+}
+
+/// Lit points of a letter
+type Glyph = Vec<(i64, i64)>;
+
+/// Letters of the 10 row high font in `day10_letters.txt`
+fn read_font() -> Result<Vec<(char, Glyph)>> {
+    let path = crate::input_dir().join("day10_letters.txt");
+    let font = crate::read_path(&path)?;
+    let names = "ABCEFGHHIJKLNPRXZ".chars();
+
+    let letters = font
+        .split("\n\n")
+        .zip(names)
+        .filter(|(glyph, _)| glyph.trim_matches('\n').lines().count() == 10)
+        .map(|(glyph, name)| {
+            let points = glyph
+                .trim_matches('\n')
+                .lines()
+                .enumerate()
+                .flat_map(|(y, line)| {
+                    line.char_indices()
+                        .filter(|&(_, ch)| ch == '#')
+                        .map(move |(x, _)| (x as i64, y as i64))
+                })
+                .collect();
+
+            (name, points)
+        })
+        .collect();
+
+    Ok(letters)
+}
+
+/// Lights that spell `size` letters of the font after a few thousand
+/// seconds
+fn light_vectors(rng: &mut Rng, size: usize) -> Result<String> {
+    let font = read_font()?;
+    if font.is_empty() {
+        return Err(Error::invalid("The font has no letters"));
+    }
+
+    let seconds = rng.range(1000, 12000);
+    let (left, top) = (rng.range(-50, 50), rng.range(-50, 50));
+    let mut lights = Vec::new();
+
+    for index in 0..size {
+        let (_, points) = rng.pick(&font);
+
+        for &(x, y) in points {
+            let (vx, vy) = loop {
+                match (rng.range(-5, 5), rng.range(-5, 5)) {
+                    (0, 0) => continue,
+                    velocity => break velocity,
+                }
+            };
+
+            let x = left + 8 * index as i64 + x - vx * seconds;
+            let y = top + y - vy * seconds;
+
+            lights.push(format!(
+                "position=<{:6}, {:6}> velocity=<{:2}, {:2}>",
+                x, y, vx, vy
+            ));
+        }
+    }
+
+    rng.shuffle(&mut lights);
+
+    Ok(lines(lights))
+}
+
+/// Ids of the plants after one generation
+fn grow(plants: &[i64], rules: &[bool; 32]) -> Vec<i64> {
+    let (first, last) = match (plants.first(), plants.last()) {
+        (Some(&first), Some(&last)) => (first, last),
+        _ => return Vec::new(),
+    };
+    let set: HashSet<i64> = plants.iter().cloned().collect();
+
+    (first - 2..=last + 2)
+        .filter(|id| {
+            let pattern = (-2..=2).fold(0, |acc, offset| {
+                acc << 1 | set.contains(&(id + offset)) as usize
+            });
+            rules[pattern]
+        })
+        .collect()
+}
+
+/// Initial state and rules whose plants settle into a repeating shape
+/// within a few hundred generations
+fn plant_rules(rng: &mut Rng, size: usize) -> String {
+    let (initial, rules) = loop {
+        let initial: Vec<bool> = (0..size).map(|_| rng.chance(0.5)).collect();
+        let mut rules = [false; 32];
+        for rule in rules.iter_mut().skip(1) {
+            *rule = rng.chance(0.5);
+        }
+
+        if settles(&initial, &rules, 4 * size + 100) {
+            break (initial, rules);
+        }
+    };
+
+    let pot = |plant: bool| if plant { '#' } else { '.' };
+    let mut out: String = "initial state: ".into();
+    out.extend(initial.iter().map(|&plant| pot(plant)));
+    out.push_str("\n\n");
+
+    for (pattern, &plant) in rules.iter().enumerate() {
+        let pots: String = (0..5)
+            .rev()
+            .map(|bit| pot(pattern >> bit & 1 == 1))
+            .collect();
+        writeln!(out, "{} => {}", pots, pot(plant)).unwrap();
+    }
+
+    out
+}
+
+/// Whether the shape of the plants repeats within 300 generations while
+/// some plants survive and there are never more than `limit` plants
+fn settles(initial: &[bool], rules: &[bool; 32], limit: usize) -> bool {
+    let mut plants: Vec<i64> = (0..initial.len() as i64)
+        .filter(|&id| initial[id as usize])
+        .collect();
+    let mut seen = HashSet::new();
+
+    for _ in 0..300 {
+        if plants.is_empty() || plants.len() > limit {
+            return false;
+        }
+
+        let shape: Vec<i64> = plants.iter().map(|id| id - plants[0]).collect();
+        if !seen.insert(shape) {
+            return true;
+        }
+
+        plants = grow(&plants, rules);
+    }
+
+    false
+}
+
+/// Draws a rectangular loop, crossing tracks become intersections
+fn draw_loop(
+    map: &mut [Vec<u8>],
+    (x0, y0): (usize, usize),
+    (x1, y1): (usize, usize),
+) {
+    let mut put = |x: usize, y: usize, track: u8| {
+        let cell = &mut map[y][x];
+        *cell = match (*cell, track) {
+            (b' ', track) => track,
+            (b'-', b'|') | (b'|', b'-') => b'+',
+            (_, track) => track,
+        };
+    };
+
+    for x in x0 + 1..x1 {
+        put(x, y0, b'-');
+        put(x, y1, b'-');
+    }
+
+    for y in y0 + 1..y1 {
+        put(x0, y, b'|');
+        put(x1, y, b'|');
+    }
+
+    put(x0, y0, b'/');
+    put(x1, y0, b'\\');
+    put(x0, y1, b'\\');
+    put(x1, y1, b'/');
+}
+
+/// Straight cells of a loop with the cart heading clockwise on each
+fn clockwise(
+    (x0, y0): (usize, usize),
+    (x1, y1): (usize, usize),
+) -> Vec<(usize, usize, u8)> {
+    let mut cells = Vec::new();
+    cells.extend((x0 + 1..x1).map(|x| (x, y0, b'>')));
+    cells.extend((y0 + 1..y1).map(|y| (x1, y, b'v')));
+    cells.extend((x0 + 1..x1).map(|x| (x, y1, b'<')));
+    cells.extend((y0 + 1..y1).map(|y| (x0, y, b'^')));
+    cells
+}
+
+fn reverse(cart: u8) -> u8 {
+    match cart {
+        b'>' => b'<',
+        b'<' => b'>',
+        b'^' => b'v',
+        _ => b'^',
+    }
+}
+
+/// `size` loops with two carts heading towards each other and a pair of
+/// crossing loops with a single cart which is left after every crash
+fn track_map(rng: &mut Rng, size: usize) -> String {
+    const CELL: usize = 24;
+
+    let loops = size + 1;
+    let columns = (1..).find(|c| c * c >= loops).unwrap();
+    let rows = loops.div_ceil(columns);
+    let alone = rng.below(loops);
+
+    let mut map = vec![vec![b' '; columns * CELL]; rows * CELL];
+
+    for index in 0..loops {
+        let (cx, cy) = ((index % columns) * CELL, (index / columns) * CELL);
+
+        if index == alone {
+            let corners = |rng: &mut Rng, start: usize| {
+                let a = start + rng.range(0, 2) as usize;
+                let b = a + rng.range(2, 4) as usize;
+                let c = b + rng.range(2, 7) as usize;
+                (a, b, c, c + rng.range(2, 4) as usize)
+            };
+            let (x0, x1, x2, x3) = corners(rng, cx);
+            let (y0, y1, y2, y3) = corners(rng, cy);
+
+            draw_loop(&mut map, (x0, y1), (x3, y2));
+            draw_loop(&mut map, (x1, y0), (x2, y3));
+
+            let cart = if rng.chance(0.5) { b'^' } else { b'v' };
+            map[rng.range(y1 as i64 + 1, y2 as i64 - 1) as usize][x0] = cart;
+        } else {
+            let x0 = cx + rng.range(0, 4) as usize;
+            let y0 = cy + rng.range(0, 4) as usize;
+            let start = (x0, y0);
+            let end = (
+                x0 + rng.range(3, 16) as usize,
+                y0 + rng.range(3, 16) as usize,
+            );
+
+            draw_loop(&mut map, start, end);
+
+            let mut cells = clockwise(start, end);
+            rng.shuffle(&mut cells);
+            let (x, y, cart) = cells[0];
+            map[y][x] = cart;
+            let (x, y, cart) = cells[1];
+            map[y][x] = reverse(cart);
+        }
+    }
+
+    lines(map.into_iter().map(|row| String::from_utf8(row).unwrap()))
+}
+
+/// Most digits `recipe_count` generates, part 1 makes as many recipes as
+/// the number and part 2 gives up after a billion recipes
+const MAX_RECIPE_DIGITS: usize = 7;
+
+/// Number of recipes with `size` digits, at most `MAX_RECIPE_DIGITS`
+///
+/// Not every sequence of digits appears on the scoreboard, so the digits
+/// are taken from its first million recipes.
+fn recipe_count(rng: &mut Rng, size: usize) -> String {
+    let size = size.min(MAX_RECIPE_DIGITS);
+    let mut start = rng.below(1_000_000);
+
+    let mut recipes: Vec<u8> = vec![3, 7];
+    let (mut elf1, mut elf2) = (0, 1);
+
+    loop {
+        // Part 1 reads the digits as a number without a leading zero
+        while recipes.get(start) == Some(&0) {
+            start += 1;
+        }
+
+        if recipes.len() >= start + size {
+            break;
+        }
+
+        let sum = recipes[elf1] + recipes[elf2];
+        if sum >= 10 {
+            recipes.push(sum / 10);
+        }
+        recipes.push(sum % 10);
+
+        elf1 = (elf1 + 1 + recipes[elf1] as usize) % recipes.len();
+        elf2 = (elf2 + 1 + recipes[elf2] as usize) % recipes.len();
+    }
+
+    let mut out: String = recipes[start..start + size]
+        .iter()
+        .map(|d| char::from(b'0' + d))
+        .collect();
+    out.push('\n');
+    out
+}
+
+/// Connected cave dug by a random walk, surrounded by walls
+fn combat_map(rng: &mut Rng, size: usize) -> String {
+    let side = size.max(7);
+    let mut map = vec![vec![b'#'; side]; side];
+    let inner = (side - 2) * (side - 2);
+
+    let (mut x, mut y) = (side / 2, side / 2);
+    let mut open = Vec::new();
+
+    while open.len() * 20 < inner * 9 {
+        if map[y][x] == b'#' {
+            map[y][x] = b'.';
+            open.push((x, y));
+        }
+
+        match rng.below(4) {
+            0 if x > 1 => x -= 1,
+            1 if x < side - 2 => x += 1,
+            2 if y > 1 => y -= 1,
+            3 if y < side - 2 => y += 1,
+            _ => {}
+        }
+    }
+
+    rng.shuffle(&mut open);
+    let units = (open.len() / 20).max(2);
+
+    for (index, &(x, y)) in open.iter().take(units).enumerate() {
+        map[y][x] = match index {
+            0 => b'E',
+            1 => b'G',
+            _ if rng.chance(0.4) => b'E',
+            _ => b'G',
+        };
+    }
+
+    lines(map.into_iter().map(|row| String::from_utf8(row).unwrap()))
+}
+
+/// Whether the elimination of the solver finds the opcode of every number
+/// from the candidates consistent with the samples
+fn resolves(candidates: &[HashSet<Opcode>]) -> bool {
+    let mut known: HashSet<Opcode> = HashSet::new();
+    let mut solved = vec![false; candidates.len()];
+
+    loop {
+        let mut progress = false;
+
+        for (number, ops) in candidates.iter().enumerate() {
+            let left: Vec<_> = ops.difference(&known).collect();
+            if !solved[number] && left.len() == 1 {
+                known.insert(*left[0]);
+                solved[number] = true;
+                progress = true;
+            }
+        }
+
+        if !progress {
+            return solved.iter().all(|&s| s);
+        }
+    }
+}
+
+/// Register dump in the `[a, b, c, d]` format
+fn registers(values: &[i64; 4]) -> String {
+    format!(
+        "[{}, {}, {}, {}]",
+        values[0], values[1], values[2], values[3]
+    )
+}
+
+/// At least `size` samples, more until they identify every opcode, and a
+/// test program of `size` instructions
+fn sample_dump(rng: &mut Rng, size: usize) -> Result<String> {
+    let mut opcodes = Opcode::ALL.to_vec();
+    rng.shuffle(&mut opcodes);
+
+    let mut candidates =
+        vec![Opcode::ALL.iter().cloned().collect::<HashSet<_>>(); 16];
+    let mut out = String::new();
+    let mut sampled = [false; 16];
+    let mut samples = 0;
+
+    // A number without samples is never identified by the solver
+    while samples < size
+        || !sampled.iter().all(|&s| s)
+        || !resolves(&candidates)
+    {
+        let number = rng.below(16);
+        let random = |rng: &mut Rng| rng.range(0, 3);
+        let before = [random(rng), random(rng), random(rng), random(rng)];
+        let (a, b, c) = (random(rng), random(rng), random(rng));
+
+        let run = |op| -> Result<[i64; 4]> {
+            let mut machine = Machine::with_registers(before);
+            machine.execute(Instruction::new(op, a, b, c))?;
+            Ok(*machine.registers())
+        };
+
+        let after = run(opcodes[number])?;
+        for &op in Opcode::ALL.iter() {
+            if run(op)? != after {
+                candidates[number].remove(&op);
+            }
+        }
+
+        writeln!(out, "Before: {}", registers(&before)).unwrap();
+        writeln!(out, "{} {} {} {}", number, a, b, c).unwrap();
+        writeln!(out, "After:  {}\n", registers(&after)).unwrap();
+        sampled[number] = true;
+        samples += 1;
+    }
+
+    out.push_str("\n\n");
+
+    // Keep the values small so the program never overflows
+    let mut machine = Machine::with_registers([0i64; 4]);
+
+    for _ in 0..size.max(3) {
+        loop {
+            let number = rng.below(16);
+            let (a, b, c) = (rng.range(0, 3), rng.range(0, 3), rng.range(0, 3));
+            let mut next = machine.clone();
+            next.execute(Instruction::new(opcodes[number], a, b, c))?;
+
+            if next.registers().iter().all(|&value| value < 1 << 20) {
+                machine = next;
+                writeln!(out, "{} {} {} {}", number, a, b, c).unwrap();
+                break;
+            }
+        }
+    }
+
+    Ok(out)
+}
+
+/// Non-overlapping basins of clay below the spring at x=500
+fn clay_veins(rng: &mut Rng, size: usize) -> String {
+    let spread = 20 + 4 * (size as i64);
+    let depth = 50 + 6 * (size as i64);
+    let mut basins: Vec<(i64, i64, i64, i64)> = Vec::new();
+    let mut veins = Vec::new();
+
+    for _ in 0..size * 20 {
+        if basins.len() == size {
+            break;
+        }
+
+        let left = 500 + rng.range(-spread.min(200), spread.min(200));
+        let right = left + rng.range(2, 20);
+        let bottom = rng.range(8, depth);
+        let top = bottom - rng.range(2, 15).min(bottom - 3);
+
+        let overlaps = basins.iter().any(|&(l, r, t, b)| {
+            left <= r + 2 && l <= right + 2 && top <= b + 2 && t <= bottom + 2
+        });
+        if overlaps {
+            continue;
+        }
+
+        basins.push((left, right, top, bottom));
+
+        let walls = [
+            (left, rng.range(top, bottom - 1)),
+            (right, rng.range(top, bottom - 1)),
+        ];
+        for &(x, top) in walls.iter() {
+            veins.push(format!("x={}, y={}..{}", x, top, bottom));
+        }
+        veins.push(format!("y={}, x={}..{}", bottom, left, right));
+    }
+
+    rng.shuffle(&mut veins);
+
+    lines(veins)
+}
+
+/// Random acres with roughly as many trees as open ground
+fn lumber_map(rng: &mut Rng, size: usize) -> String {
+    lines((0..size).map(|_| {
+        (0..size)
+            .map(|_| match rng.below(10) {
+                0..=3 => '.',
+                4..=6 => '|',
+                _ => '#',
+            })
+            .collect()
+    }))
+}
+
+/// Renames the registers used by `instruction`, immediate values are kept
+fn rename(
+    instruction: &Instruction<i32>,
+    names: &[i32; 6],
+) -> Instruction<i32> {
+    use crate::elfcode::Opcode::*;
+
+    let Instruction { op, a, b, c } = *instruction;
+    let register_a = !matches!(op, Seti | Gtir | Eqir);
+    let register_b =
+        matches!(op, Addr | Mulr | Banr | Borr | Gtir | Gtrr | Eqir | Eqrr);
+
+    let name = |value: i32| names[value as usize];
+
+    Instruction::new(
+        op,
+        if register_a { name(a) } else { a },
+        if register_b { name(b) } else { b },
+        name(c),
+    )
+}
+
+/// Writes `program` with its registers except register 0 shuffled
+fn shuffled_registers(rng: &mut Rng, program: &Program<i32>) -> String {
+    let mut names = [0, 1, 2, 3, 4, 5];
+    rng.shuffle(&mut names[1..]);
+
+    let mut out = String::new();
+
+    if let Some(ip) = program.ip_reg {
+        writeln!(out, "#ip {}", names[ip]).unwrap();
+    }
+
+    for instruction in &program.instructions {
+        writeln!(out, "{}", rename(instruction, &names)).unwrap();
+    }
+
+    out
+}
+
+const DIVISOR_PROGRAM: &str = "#ip 2
+addi 2 16 2
+seti 1 1 1
+seti 1 8 5
+mulr 1 5 4
+eqrr 4 3 4
+addr 4 2 2
+addi 2 1 2
+addr 1 0 0
+addi 5 1 5
+gtrr 5 3 4
+addr 2 4 2
+seti 2 0 2
+addi 1 1 1
+gtrr 1 3 4
+addr 4 2 2
+seti 1 1 2
+mulr 2 2 2
+addi 3 2 3
+mulr 3 3 3
+mulr 2 3 3
+muli 3 11 3
+addi 4 7 4
+mulr 4 2 4
+addi 4 6 4
+addr 3 4 3
+addr 2 0 2
+seti 0 3 2
+setr 2 0 4
+mulr 4 2 4
+addr 2 4 4
+mulr 2 4 4
+muli 4 14 4
+mulr 4 2 4
+addr 3 4 3
+seti 0 4 0
+seti 0 4 2";
+
+/// Program that sums the divisors of a number built from random constants
+fn divisor_program(rng: &mut Rng) -> Result<String> {
+    let mut program = Program::parse(DIVISOR_PROGRAM)?;
+
+    program.instructions[21].b = rng.range(1, 20) as i32;
+    program.instructions[23].b = rng.range(1, 20) as i32;
+
+    Ok(shuffled_registers(rng, &program))
+}
+
+type Room = (i64, i64);
+
+const DOORS: [(char, Room); 4] =
+    [('N', (0, -1)), ('E', (1, 0)), ('S', (0, 1)), ('W', (-1, 0))];
+
+/// Regex of a maze of `size` doors without loops, grown like a tree from
+/// the first room
+///
+/// Rooms with several doors onward end the route with a branch for each
+/// door so the number of routes the solver follows stays small.
+fn door_regex(rng: &mut Rng, size: usize) -> String {
+    let side = (1..).find(|s| s * s > size).unwrap() as i64;
+    let inside = |(x, y): Room| x.abs() <= side / 2 && y.abs() <= side / 2;
+
+    let mut doors: HashMap<Room, Vec<(char, Room)>> = HashMap::new();
+    let mut visited = HashSet::new();
+    let mut growing = vec![(0, 0)];
+    visited.insert((0, 0));
+
+    let mut opened = 0;
+
+    // Mostly grows from the newest room for long corridors
+    while opened < size && !growing.is_empty() {
+        let index = if rng.chance(0.95) {
+            growing.len() - 1
+        } else {
+            rng.below(growing.len())
+        };
+
+        let room = growing[index];
+        let next: Vec<_> = DOORS
+            .iter()
+            .map(|&(door, (dx, dy))| (door, (room.0 + dx, room.1 + dy)))
+            .filter(|&(_, next)| inside(next) && !visited.contains(&next))
+            .collect();
+
+        if next.is_empty() {
+            growing.remove(index);
+            continue;
+        }
+
+        let (door, next) = *rng.pick(&next);
+        visited.insert(next);
+        growing.push(next);
+        doors.entry(room).or_default().push((door, next));
+        opened += 1;
+    }
+
+    enum Part {
+        Room(Room),
+        Text(char),
+    }
+
+    let mut out = String::from("^");
+    let mut parts = vec![Part::Room((0, 0))];
+
+    while let Some(part) = parts.pop() {
+        let room = match part {
+            Part::Text(ch) => {
+                out.push(ch);
+                continue;
+            }
+            Part::Room(room) => room,
+        };
+
+        match doors.get(&room).map(|d| &d[..]) {
+            None | Some([]) => {}
+            Some(&[(door, next)]) => {
+                parts.push(Part::Room(next));
+                parts.push(Part::Text(door));
+            }
+            Some(branches) => {
+                parts.push(Part::Text(')'));
+                for (index, &(door, next)) in branches.iter().enumerate().rev()
+                {
+                    parts.push(Part::Room(next));
+                    parts.push(Part::Text(door));
+                    if index > 0 {
+                        parts.push(Part::Text('|'));
+                    }
+                }
+                parts.push(Part::Text('('));
+            }
+        }
+    }
+
+    out.push_str("$\n");
+    out
+}
+
+const HALTING_PROGRAM: &str = "#ip 4
+seti 123 0 1
+bani 1 456 1
+eqri 1 72 1
+addr 1 4 4
+seti 0 0 4
+seti 0 6 1
+bori 1 65536 3
+seti 6780005 8 1
+bani 3 255 2
+addr 1 2 1
+bani 1 16777215 1
+muli 1 65899 1
+bani 1 16777215 1
+gtir 256 3 2
+addr 2 4 4
+addi 4 1 4
+seti 27 5 4
+seti 0 5 2
+addi 2 1 5
+muli 5 256 5
+gtrr 5 3 5
+addr 5 4 4
+addi 4 1 4
+seti 25 4 4
+addi 2 1 2
+seti 17 7 4
+setr 2 1 3
+seti 7 3 4
+eqrr 1 0 2
+addr 2 4 4
+seti 5 4 4";
+
+/// Program that halts when register 0 matches a hash seeded by a random
+/// constant
+fn halting_program(rng: &mut Rng) -> Result<String> {
+    let mut program = Program::parse(HALTING_PROGRAM)?;
+
+    program.instructions[7].a = rng.range(0, 0xff_ffff) as i32;
+
+    Ok(shuffled_registers(rng, &program))
+}
+
+fn cave_scan(rng: &mut Rng, size: usize) -> String {
+    format!(
+        "depth: {}\ntarget: {},{}\n",
+        // Like in the real inputs the mouth and the target are rocky
+        3 * rng.range(1000, 4000),
+        rng.range(5, 15),
+        size
+    )
+}
+
+/// Fewest bots `nanobots` generates
+const MIN_NANOBOTS: usize = 16;
+
+/// Most of the bots barely reach a common point like in the real inputs
+///
+/// The bots reaching it surround it from every octant in turn. Bots on one
+/// side only reach a large region which the search of part 2 is slow to
+/// narrow down, so the first `MIN_NANOBOTS` bots always reach the point.
+fn nanobots(rng: &mut Rng, size: usize) -> String {
+    const EXTENT: i64 = 100_000_000;
+
+    let coordinate = |rng: &mut Rng| rng.range(-EXTENT, EXTENT);
+    let center = [coordinate(rng), coordinate(rng), coordinate(rng)];
+    let mut octant = 0;
+
+    lines((0..size).map(|i| {
+        if i >= MIN_NANOBOTS && !rng.chance(0.95) {
+            let pos = [coordinate(rng), coordinate(rng), coordinate(rng)];
+            let radius = rng.range(50_000_000, 100_000_000);
+
+            return format!(
+                "pos=<{},{},{}>, r={}",
+                pos[0], pos[1], pos[2], radius
+            );
+        }
+
+        let mut pos = center;
+        let mut distance = 0;
+
+        for (axis, coordinate) in pos.iter_mut().enumerate() {
+            let offset = rng.range(1, EXTENT);
+            distance += offset;
+
+            if octant >> axis & 1 == 0 {
+                *coordinate += offset;
+            } else {
+                *coordinate -= offset;
+            }
+        }
+
+        octant = (octant + 1) % 8;
+        let radius = distance + rng.range(0, 1000);
+
+        format!("pos=<{},{},{}>, r={}", pos[0], pos[1], pos[2], radius)
+    }))
+}
+
+const DAMAGE_TYPES: [&str; 5] =
+    ["bludgeoning", "cold", "fire", "radiation", "slashing"];
+
+/// Two armies of `size` groups each, the infection is stronger but every
+/// group can be damaged by some attack of the other army
+fn army_groups(rng: &mut Rng, size: usize) -> String {
+    let mut initiatives: Vec<usize> = (1..=2 * size).collect();
+    rng.shuffle(&mut initiatives);
+    let mut initiatives = initiatives.into_iter();
+
+    let immune_attacks: Vec<usize> = (0..size).map(|_| rng.below(5)).collect();
+    let infection_attacks: Vec<usize> =
+        (0..size).map(|_| rng.below(5)).collect();
+
+    let mut army = |rng: &mut Rng,
+                    attacks: &[usize],
+                    enemy_attacks: &[usize],
+                    power: (i64, i64),
+                    hp: i64| {
+        let mut out = String::new();
+
+        for &attack in attacks {
+            let units = rng.range(100, 8000);
+            let mut weak = Vec::new();
+            let mut immune = Vec::new();
+
+            for kind in 0..DAMAGE_TYPES.len() {
+                match rng.below(10) {
+                    0 | 1 => weak.push(kind),
+                    2 => immune.push(kind),
+                    _ => {}
+                }
+            }
+
+            if enemy_attacks.iter().all(|kind| immune.contains(kind)) {
+                immune.clear();
+            }
+
+            let names = |kinds: &[usize]| {
+                let names: Vec<_> =
+                    kinds.iter().map(|&k| DAMAGE_TYPES[k]).collect();
+                names.join(", ")
+            };
+            let modifiers = match (weak.is_empty(), immune.is_empty()) {
+                (true, true) => String::new(),
+                (false, true) => format!(" (weak to {})", names(&weak)),
+                (true, false) => format!(" (immune to {})", names(&immune)),
+                (false, false) => {
+                    format!(
+                        " (weak to {}; immune to {})",
+                        names(&weak),
+                        names(&immune)
+                    )
+                }
+            };
+
+            writeln!(
+                out,
+                "{} units each with {} hit points{} with an attack that does {} {} damage at initiative {}",
+                units,
+                rng.range(hp / 10, hp),
+                modifiers,
+                (rng.range(power.0, power.1) / units).max(1),
+                DAMAGE_TYPES[attack],
+                initiatives.next().unwrap()
+            )
+            .unwrap();
+        }
+
+        out
+    };
+
+    let immune = army(
+        rng,
+        &immune_attacks,
+        &infection_attacks,
+        (20_000, 50_000),
+        12_000,
+    );
+    let infection = army(
+        rng,
+        &infection_attacks,
+        &immune_attacks,
+        (40_000, 100_000),
+        50_000,
+    );
+
+    format!("Immune System:\n{}\nInfection:\n{}", immune, infection)
+}
+
+fn points_4d(rng: &mut Rng, size: usize) -> String {
+    lines((0..size).map(|_| {
+        let mut coordinate = || rng.range(-8, 8);
+        format!(
+            "{},{},{},{}",
+            coordinate(),
+            coordinate(),
+            coordinate(),
+            coordinate()
+        )
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rng_is_deterministic() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+
+        for _ in 0..1000 {
+            let value = a.range(-3, 3);
+            assert!((-3..=3).contains(&value));
+            assert!(a.below(7) < 7);
+        }
+    }
+
+    #[test]
+    fn same_seed_same_input() {
+        for day in 1..=25 {
+            if day == 10 {
+                continue;
+            }
+
+            let first = generate(day, 7, 20).unwrap();
+            assert_eq!(first, generate(day, 7, 20).unwrap(), "day {}", day);
+            assert_ne!(first, generate(day, 8, 20).unwrap(), "day {}", day);
+        }
+
+        assert!(generate(26, 7, 20).is_err());
+        assert!(generate(23, 7, MIN_NANOBOTS - 1).is_err());
+    }
+
+    #[test]
+    fn generated_inputs_solve() {
+        // Day, size and the number of seeds, the days which solve quickly
+        // are tried with more seeds
+        let cases = [
+            (1, 1, 500),
+            (1, 3, 500),
+            (1, 200, 200),
+            (2, 50, 100),
+            (3, 100, 50),
+            (4, 60, 50),
+            (5, 500, 100),
+            (6, 20, 5),
+            (7, 10, 50),
+            (8, 100, 100),
+            (9, 500, 50),
+            (12, 30, 5),
+            (13, 3, 100),
+            (14, 3, 10),
+            (14, 20, 3),
+            (15, 10, 50),
+            (16, 100, 100),
+            (17, 20, 50),
+            (18, 20, 10),
+            (20, 300, 30),
+            (22, 20, 300),
+            (23, 16, 3),
+            (23, 200, 3),
+            (24, 4, 3),
+            (25, 100, 100),
+        ];
+
+        for &(day, size, seeds) in cases.iter() {
+            let solution = crate::days::get(day).unwrap();
+
+            for seed in 0..seeds {
+                let input = generate(day, seed, size).unwrap();
+
+                for part in 1..=2 {
+                    let result = if part == 1 {
+                        solution.part1(&input)
+                    } else {
+                        solution.part2(&input)
+                    };
+
+                    assert!(
+                        result.is_ok(),
+                        "day {} part {} seed {}: {:?}",
+                        day,
+                        part,
+                        seed,
+                        result
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn generated_inputs_parse() {
+        for day in 1..=25 {
+            let solution = crate::days::get(day).unwrap();
+            let size = default_size(day).unwrap_or(1);
+            let input = generate(day, 1, size).unwrap();
+
+            let result = solution.parse(&input);
+            assert!(result.is_ok(), "day {}: {:?}", day, result);
+        }
+
+        let day1 = crate::days::get(1).unwrap();
+        assert!(day1.parse("+1\nnot a change\n").is_err());
+    }
+}
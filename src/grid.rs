@@ -0,0 +1,408 @@
+use std::ops::{Index, IndexMut};
+
+use crate::{Dir4, Dir8, Error, Rect, Result, ToIndex, Vector2};
+
+/// Dense two dimensional grid of cells stored in reading order
+///
+/// Positions are `Vector2`s, the cell at `offset` is the top left corner of
+/// the grid. All lookups are bounds-checked, `get` returns `None` outside of
+/// the grid and indexing panics.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd, Default)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+    offset: Vector2,
+}
+
+impl<T: Clone> Grid<T> {
+    /// Creates a `width` x `height` grid filled with `value`
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Grid {
+            cells: vec![value; width * height],
+            width,
+            height,
+            offset: Vector2::default(),
+        }
+    }
+
+    /// Creates a grid filled with `value` covering every point of `rect`
+    pub fn covering(rect: Rect<i32>, value: T) -> Self {
+        let mut grid =
+            Grid::new(rect.width() as usize, rect.height() as usize, value);
+        grid.set_offset(rect.min().into());
+        grid
+    }
+
+    /// Resizes the grid keeping the offset, cells keep their positions and
+    /// new cells are filled with `value`
+    pub fn resize(&mut self, width: usize, height: usize, value: T) {
+        let mut cells = Vec::with_capacity(width * height);
+
+        for y in 0..height {
+            for x in 0..width {
+                let cell = if x < self.width && y < self.height {
+                    self.cells[y * self.width + x].clone()
+                } else {
+                    value.clone()
+                };
+                cells.push(cell);
+            }
+        }
+
+        self.cells = cells;
+        self.width = width;
+        self.height = height;
+    }
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from rows of equal length
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self> {
+        let width = rows.first().map_or(0, |row| row.len());
+        let height = rows.len();
+
+        if let Some(y) = rows.iter().position(|row| row.len() != width) {
+            return Err(Error::invalid(format!(
+                "row {} has {} cells, expected {}",
+                y,
+                rows[y].len(),
+                width
+            )));
+        }
+
+        Ok(Grid {
+            cells: rows.into_iter().flatten().collect(),
+            width,
+            height,
+            offset: Vector2::default(),
+        })
+    }
+
+    /// Parses a character map, one row per line
+    ///
+    /// `f` maps the position and character of each cell into its value and
+    /// returns `None` for invalid characters. Lines shorter than the longest
+    /// one are padded with spaces.
+    pub fn parse<F>(s: &str, mut f: F) -> Result<Self>
+    where
+        F: FnMut(Vector2, char) -> Option<T>,
+    {
+        let lines: Vec<&str> = s.lines().collect();
+        let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+        let mut cells = Vec::with_capacity(width * lines.len());
+
+        for (y, line) in lines.iter().enumerate() {
+            let mut chars = line.chars();
+
+            for x in 0..width {
+                let c = chars.next().unwrap_or(' ');
+                let pos = Vector2::new(x as i32, y as i32);
+
+                let cell = f(pos, c).ok_or_else(|| {
+                    Error::parse(format!("unexpected '{}'", c))
+                        .at_column(x + 1)
+                        .in_line(y + 1, line)
+                })?;
+
+                cells.push(cell);
+            }
+        }
+
+        Ok(Grid {
+            cells,
+            width,
+            height: lines.len(),
+            offset: Vector2::default(),
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Position of the top left cell
+    pub fn offset(&self) -> Vector2 {
+        self.offset
+    }
+
+    /// Moves the grid so that the top left cell is at `offset`
+    pub fn set_offset(&mut self, offset: Vector2) {
+        self.offset = offset;
+    }
+
+    pub fn contains<P: Into<Vector2>>(&self, pos: P) -> bool {
+        self.index_of(pos.into()).is_some()
+    }
+
+    pub fn get<P: Into<Vector2>>(&self, pos: P) -> Option<&T> {
+        self.index_of(pos.into()).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut<P: Into<Vector2>>(&mut self, pos: P) -> Option<&mut T> {
+        let i = self.index_of(pos.into())?;
+        Some(&mut self.cells[i])
+    }
+
+    /// Neighbouring positions inside the grid in reading order,
+    /// up, left, right and down
+    pub fn neighbors4<P: Into<Vector2>>(
+        &self,
+        pos: P,
+    ) -> impl Iterator<Item = Vector2> + '_ {
+        let pos = pos.into();
+
+        let around = Dir4::READING_ORDER.map(|dir| pos + dir);
+
+        IntoIterator::into_iter(around).filter(move |p| self.contains(*p))
+    }
+
+    /// Neighbouring positions, including diagonals, inside the grid in
+    /// reading order
+    pub fn neighbors8<P: Into<Vector2>>(
+        &self,
+        pos: P,
+    ) -> impl Iterator<Item = Vector2> + '_ {
+        let pos = pos.into();
+
+        let around = Dir8::READING_ORDER.map(|dir| pos + dir);
+
+        IntoIterator::into_iter(around).filter(move |p| self.contains(*p))
+    }
+
+    /// Cells of row `y`
+    pub fn row(&self, y: i32) -> Option<&[T]> {
+        let y = y - self.offset.y;
+
+        if y < 0 || y as usize >= self.height {
+            return None;
+        }
+
+        let start = y as usize * self.width;
+        Some(&self.cells[start..start + self.width])
+    }
+
+    /// Rows from top to bottom
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Cells of column `x` from top to bottom
+    pub fn column(&self, x: i32) -> Option<impl Iterator<Item = &T>> {
+        let x = x - self.offset.x;
+
+        if x < 0 || x as usize >= self.width {
+            return None;
+        }
+
+        Some(self.cells.iter().skip(x as usize).step_by(self.width))
+    }
+
+    /// Positions and cells in reading order
+    pub fn iter(&self) -> impl Iterator<Item = (Vector2, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| (self.position(i), cell))
+    }
+
+    /// Positions and mutable cells in reading order
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Vector2, &mut T)> {
+        let (width, offset) = (self.width, self.offset);
+
+        self.cells.iter_mut().enumerate().map(move |(i, cell)| {
+            let pos = Vector2::new((i % width) as i32, (i / width) as i32);
+            (pos + offset, cell)
+        })
+    }
+
+    /// Cells in reading order
+    pub fn values(&self) -> std::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    /// Maps every cell into a new grid of the same size and offset
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+            offset: self.offset,
+        }
+    }
+
+    /// Renders the grid one line per row, `f` gives the character of a cell
+    pub fn render<F: Fn(&T) -> char>(&self, f: F) -> String {
+        let mut buf = String::with_capacity((self.width + 1) * self.height);
+
+        for row in self.rows() {
+            buf.extend(row.iter().map(&f));
+            buf.push('\n');
+        }
+
+        buf
+    }
+
+    fn index_of(&self, pos: Vector2) -> Option<usize> {
+        let pos = pos - self.offset;
+
+        if pos.y as usize >= self.height {
+            return None;
+        }
+
+        Some(pos.to_index(self.width)).filter(|&i| i < self.cells.len())
+    }
+
+    fn position(&self, index: usize) -> Vector2 {
+        let x = (index % self.width) as i32;
+        let y = (index / self.width) as i32;
+
+        Vector2::new(x, y) + self.offset
+    }
+}
+
+impl<T, P: Into<Vector2>> Index<P> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: P) -> &T {
+        let pos = pos.into();
+
+        match self.index_of(pos) {
+            Some(i) => &self.cells[i],
+            None => panic!(
+                "{} is outside of the {}x{} grid at {}",
+                pos, self.width, self.height, self.offset
+            ),
+        }
+    }
+}
+
+impl<T, P: Into<Vector2>> IndexMut<P> for Grid<T> {
+    fn index_mut(&mut self, pos: P) -> &mut T {
+        let pos = pos.into();
+
+        match self.index_of(pos) {
+            Some(i) => &mut self.cells[i],
+            None => panic!(
+                "{} is outside of the {}x{} grid at {}",
+                pos, self.width, self.height, self.offset
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &'static str = r"
+#.#
+..#
+#..
+    ";
+
+    fn parse(s: &str) -> Grid<bool> {
+        Grid::parse(s, |_, c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+        .unwrap()
+    }
+
+    fn render(grid: &Grid<bool>) -> String {
+        grid.render(|&wall| if wall { '#' } else { '.' })
+    }
+
+    #[test]
+    fn parse_and_render() {
+        let grid = parse(INPUT.trim());
+
+        assert_eq!(3, grid.width());
+        assert_eq!(3, grid.height());
+        assert_eq!(Some(&true), grid.get((2, 1)));
+        assert_eq!("#.#\n..#\n#..\n", render(&grid));
+    }
+
+    #[test]
+    fn parse_errors_name_position() {
+        let err = Grid::parse("..\n.x", |_, c| Some(c).filter(|&c| c == '.'))
+            .unwrap_err();
+
+        assert_eq!("line 2, column 2: unexpected 'x'", err.to_string());
+    }
+
+    #[test]
+    fn lookups_are_bounds_checked() {
+        let mut grid = parse(INPUT.trim());
+        grid.set_offset(Vector2::new(-1, 5));
+
+        assert_eq!(Some(&true), grid.get((-1, 5)));
+        assert_eq!(Some(&false), grid.get((0, 6)));
+        assert_eq!(None, grid.get((2, 5)));
+        assert_eq!(None, grid.get((-2, 6)));
+        assert_eq!(None, grid.get((0, 8)));
+        assert_eq!(None, grid.get((0, 4)));
+        assert!(!grid.contains((0, 0)));
+    }
+
+    #[test]
+    fn neighbors_in_reading_order() {
+        let grid = Grid::new(3, 3, 0);
+
+        let corner: Vec<_> = grid.neighbors4((0, 0)).collect();
+        assert_eq!(vec![Vector2::new(1, 0), Vector2::new(0, 1)], corner);
+
+        let center: Vec<_> = grid.neighbors8((1, 1)).collect();
+        assert_eq!(8, center.len());
+        assert_eq!(Vector2::new(0, 0), center[0]);
+        assert_eq!(Vector2::new(2, 2), center[7]);
+
+        assert_eq!(3, grid.neighbors8((0, 0)).count());
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = parse(INPUT.trim());
+
+        assert_eq!(Some(&[false, false, true][..]), grid.row(1));
+        assert_eq!(None, grid.row(3));
+        assert_eq!(3, grid.rows().count());
+
+        let column: Vec<_> = grid.column(2).unwrap().cloned().collect();
+        assert_eq!(vec![true, true, false], column);
+        assert!(grid.column(-1).is_none());
+    }
+
+    #[test]
+    fn resize_keeps_positions() {
+        let mut grid = parse(INPUT.trim());
+        grid.set_offset(Vector2::new(10, 10));
+
+        grid.resize(4, 2, true);
+
+        assert_eq!("#.##\n..##\n", render(&grid));
+        assert_eq!(Some(&true), grid.get((12, 11)));
+        assert_eq!(None, grid.get((12, 12)));
+    }
+
+    #[test]
+    fn from_rows_requires_equal_rows() {
+        let grid = Grid::from_rows(vec![vec![1, 2], vec![3, 4]]).unwrap();
+        assert_eq!(4, grid[(1, 1)]);
+
+        assert!(Grid::from_rows(vec![vec![1, 2], vec![3]]).is_err());
+    }
+}
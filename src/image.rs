@@ -0,0 +1,341 @@
+use std::fs;
+use std::path::Path;
+
+use crate::{Error, Grid, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd, Default)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    pub const BLACK: Color = Color::rgb(0, 0, 0);
+    pub const WHITE: Color = Color::rgb(255, 255, 255);
+
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Color { r, g, b }
+    }
+
+    /// Color between `self` and `other`, `t` is clamped to `0.0..=1.0`
+    pub fn lerp(self, other: Color, t: f64) -> Color {
+        let t = t.clamp(0.0, 1.0);
+        let mix = |a: u8, b: u8| {
+            (f64::from(a) + (f64::from(b) - f64::from(a)) * t).round() as u8
+        };
+
+        Color::rgb(
+            mix(self.r, other.r),
+            mix(self.g, other.g),
+            mix(self.b, other.b),
+        )
+    }
+
+    /// Bright color for `index`, consecutive indices get clearly different
+    /// hues
+    pub fn distinct(index: usize) -> Color {
+        // Stepping by the golden ratio spreads the hues evenly
+        let hue = (index as f64 * 0.618_033_988_75).fract() * 6.0;
+        let x = 1.0 - (hue % 2.0 - 1.0).abs();
+
+        let (r, g, b) = match hue as u32 {
+            0 => (1.0, x, 0.0),
+            1 => (x, 1.0, 0.0),
+            2 => (0.0, 1.0, x),
+            3 => (0.0, x, 1.0),
+            4 => (x, 0.0, 1.0),
+            _ => (1.0, 0.0, x),
+        };
+
+        let channel = |v: f64| (55.0 + v * 200.0) as u8;
+
+        Color::rgb(channel(r), channel(g), channel(b))
+    }
+}
+
+/// RGB image which can be written as PPM or PNG
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Color>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, background: Color) -> Self {
+        Image {
+            width,
+            height,
+            pixels: vec![background; width * height],
+        }
+    }
+
+    /// Creates an image with the color of each pixel given by `f(x, y)`
+    pub fn from_fn<F>(width: usize, height: usize, mut f: F) -> Self
+    where
+        F: FnMut(usize, usize) -> Color,
+    {
+        let mut pixels = Vec::with_capacity(width * height);
+
+        for y in 0..height {
+            for x in 0..width {
+                pixels.push(f(x, y));
+            }
+        }
+
+        Image {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// One pixel per cell of the grid, `f` gives the color of a cell
+    pub fn from_grid<T, F>(grid: &Grid<T>, mut f: F) -> Self
+    where
+        F: FnMut(&T) -> Color,
+    {
+        Image {
+            width: grid.width(),
+            height: grid.height(),
+            pixels: grid.values().map(&mut f).collect(),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<Color> {
+        if x < self.width && y < self.height {
+            Some(self.pixels[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    /// Sets the color of a pixel, positions outside of the image are
+    /// ignored
+    pub fn set(&mut self, x: usize, y: usize, color: Color) {
+        if x < self.width && y < self.height {
+            self.pixels[y * self.width + x] = color;
+        }
+    }
+
+    /// Image where every pixel is a `factor` x `factor` square
+    pub fn scaled(&self, factor: usize) -> Image {
+        Image::from_fn(self.width * factor, self.height * factor, |x, y| {
+            self.pixels[(y / factor) * self.width + x / factor]
+        })
+    }
+
+    /// Binary PPM (P6)
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut out =
+            format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+
+        for pixel in &self.pixels {
+            out.extend_from_slice(&[pixel.r, pixel.g, pixel.b]);
+        }
+
+        out
+    }
+
+    /// 8-bit RGB PNG, the pixels are stored without compression
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut raw = Vec::with_capacity((self.width * 3 + 1) * self.height);
+
+        for row in self.pixels.chunks(self.width.max(1)) {
+            // Filter type None
+            raw.push(0);
+            for pixel in row {
+                raw.extend_from_slice(&[pixel.r, pixel.g, pixel.b]);
+            }
+        }
+
+        let mut header = Vec::with_capacity(13);
+        header.extend_from_slice(&(self.width as u32).to_be_bytes());
+        header.extend_from_slice(&(self.height as u32).to_be_bytes());
+        // Bit depth 8, color type RGB, default compression, filtering and
+        // no interlacing
+        header.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+        let mut out = vec![0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+        write_chunk(&mut out, b"IHDR", &header);
+        write_chunk(&mut out, b"IDAT", &zlib_stored(&raw));
+        write_chunk(&mut out, b"IEND", &[]);
+
+        out
+    }
+
+    /// Writes the image in the format given by the extension of `path`,
+    /// `.png` or `.ppm`
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let bytes = match path.extension().and_then(|e| e.to_str()) {
+            Some("png") => self.to_png(),
+            Some("ppm") => self.to_ppm(),
+            _ => {
+                return Err(Error::invalid(format!(
+                    "{}: unsupported image format, use .png or .ppm",
+                    path.display()
+                )))
+            }
+        };
+
+        fs::write(path, bytes).map_err(|e| Error::io(path, e))
+    }
+}
+
+fn write_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+
+    let start = out.len();
+    out.extend_from_slice(kind);
+    out.extend_from_slice(data);
+
+    let crc = crc32(&out[start..]);
+    out.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// Zlib stream of uncompressed deflate blocks
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    const MAX_BLOCK: usize = 0xffff;
+
+    let blocks = data.len() / MAX_BLOCK + 1;
+    let mut out = Vec::with_capacity(data.len() + blocks * 5 + 6);
+
+    // Deflate with a 32K window, no preset dictionary
+    out.extend_from_slice(&[0x78, 0x01]);
+
+    let mut chunks = data.chunks(MAX_BLOCK).peekable();
+
+    if chunks.peek().is_none() {
+        out.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+
+    while let Some(chunk) = chunks.next() {
+        let last = chunks.peek().is_none();
+        let len = chunk.len() as u16;
+
+        out.push(if last { 1 } else { 0 });
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(chunk);
+    }
+
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+
+    out
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut table = [0u32; 256];
+
+    for (n, entry) in table.iter_mut().enumerate() {
+        let mut c = n as u32;
+        for _ in 0..8 {
+            c = if c & 1 == 1 {
+                0xedb8_8320 ^ (c >> 1)
+            } else {
+                c >> 1
+            };
+        }
+        *entry = c;
+    }
+
+    let crc = data.iter().fold(0xffff_ffff, |crc, &byte| {
+        table[((crc ^ u32::from(byte)) & 0xff) as usize] ^ (crc >> 8)
+    });
+
+    crc ^ 0xffff_ffff
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    const MOD: u32 = 65521;
+
+    let (a, b) = data.iter().fold((1, 0), |(a, b), &byte| {
+        let a = (a + u32::from(byte)) % MOD;
+        (a, (b + a) % MOD)
+    });
+
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkerboard() -> Image {
+        Image::from_fn(2, 2, |x, y| {
+            if (x + y) % 2 == 0 {
+                Color::WHITE
+            } else {
+                Color::BLACK
+            }
+        })
+    }
+
+    #[test]
+    fn checksums() {
+        assert_eq!(0xcbf4_3926, crc32(b"123456789"));
+        assert_eq!(0x11e6_0398, adler32(b"Wikipedia"));
+    }
+
+    #[test]
+    fn ppm_and_scaling() {
+        let image = checkerboard().scaled(2);
+
+        assert_eq!(4, image.width());
+        assert_eq!(Some(Color::WHITE), image.get(1, 1));
+        assert_eq!(Some(Color::BLACK), image.get(2, 1));
+        assert_eq!(None, image.get(4, 0));
+
+        let ppm = checkerboard().to_ppm();
+        assert!(ppm.starts_with(b"P6\n2 2\n255\n"));
+        assert_eq!(11 + 2 * 2 * 3, ppm.len());
+    }
+
+    #[test]
+    fn png_stores_scanlines() {
+        let png = checkerboard().to_png();
+
+        assert_eq!(b"\x89PNG\r\n\x1a\n", &png[..8]);
+        assert_eq!(b"IHDR", &png[12..16]);
+        assert_eq!(&[0, 0, 0, 2, 0, 0, 0, 2, 8, 2, 0, 0, 0], &png[16..29]);
+        assert_eq!(crc32(&png[12..29]).to_be_bytes(), png[29..33]);
+
+        // IDAT follows IHDR: zlib header, a single final stored block with
+        // two filtered scanlines and the Adler-32 of the scanlines
+        assert_eq!(b"IDAT", &png[37..41]);
+        let raw = [0, 255, 255, 255, 0, 0, 0, 0, 0, 0, 0, 255, 255, 255];
+        assert_eq!(&[0x78, 0x01, 1, 14, 0, !14, 0xff], &png[41..48]);
+        assert_eq!(&raw, &png[48..62]);
+        assert_eq!(adler32(&raw).to_be_bytes(), png[62..66]);
+
+        assert!(png.ends_with(b"IEND\xae\x42\x60\x82"));
+    }
+
+    #[test]
+    fn large_images_use_several_blocks() {
+        let data = vec![7; 0x1_0000 + 10];
+
+        let zlib = zlib_stored(&data);
+
+        assert_eq!(2 + 5 + 0xffff + 5 + 11 + 4, zlib.len());
+        assert_eq!(0, zlib[2]);
+        assert_eq!(1, zlib[2 + 5 + 0xffff]);
+    }
+
+    #[test]
+    fn colors() {
+        let gray = Color::BLACK.lerp(Color::WHITE, 0.5);
+        assert_eq!(Color::rgb(128, 128, 128), gray);
+        assert_eq!(Color::WHITE, Color::BLACK.lerp(Color::WHITE, 2.0));
+        assert_ne!(Color::distinct(0), Color::distinct(1));
+    }
+}
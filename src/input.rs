@@ -0,0 +1,117 @@
+use std::env;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use crate::{Error, Result};
+
+/// Environment variable overriding the directory the inputs are read from
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Directory the inputs are read from, `$AOC_INPUT_DIR` or `input`
+pub fn input_dir() -> PathBuf {
+    env::var_os(INPUT_DIR_VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("input"))
+}
+
+/// Where the puzzle input is read from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Input {
+    /// `dayNN.txt` in the input directory
+    Day(u32),
+    Path(PathBuf),
+    Stdin,
+    Inline(String),
+}
+
+impl Input {
+    /// Parses a command line argument, `-` means stdin
+    pub fn from_arg(arg: &str) -> Self {
+        if arg == "-" {
+            Input::Stdin
+        } else {
+            Input::Path(PathBuf::from(arg))
+        }
+    }
+
+    /// Reads the whole input without trimming it
+    pub fn read(&self) -> Result<String> {
+        match self {
+            Input::Day(_) | Input::Path(_) => {
+                read_path(&self.path().unwrap_or_default())
+            }
+            Input::Stdin => {
+                let mut s = String::new();
+                io::stdin().read_to_string(&mut s)?;
+                Ok(s)
+            }
+            Input::Inline(s) => Ok(s.clone()),
+        }
+    }
+
+    /// Path of the file the input is read from, if any
+    pub fn path(&self) -> Option<PathBuf> {
+        match self {
+            Input::Day(day) => {
+                Some(input_dir().join(format!("day{:02}.txt", day)))
+            }
+            Input::Path(path) => Some(path.clone()),
+            Input::Stdin | Input::Inline(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for Input {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Input::Day(_) | Input::Path(_) => {
+                write!(f, "{}", self.path().unwrap_or_default().display())
+            }
+            Input::Stdin => write!(f, "-"),
+            Input::Inline(_) => write!(f, "<inline>"),
+        }
+    }
+}
+
+/// Reads the file at `path`, the error names the path that was tried
+pub fn read_path(path: &Path) -> Result<String> {
+    let mut s = String::new();
+
+    File::open(path)
+        .and_then(|mut file| file.read_to_string(&mut s))
+        .map_err(|e| Error::io(path, e))?;
+
+    Ok(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_arg() {
+        assert_eq!(Input::Stdin, Input::from_arg("-"));
+        assert_eq!(
+            Input::Path(PathBuf::from("input/day01.txt")),
+            Input::from_arg("input/day01.txt")
+        );
+    }
+
+    #[test]
+    fn read_inline() {
+        let input = Input::Inline("  +1\n-2\n".to_owned());
+
+        assert_eq!("  +1\n-2\n", input.read().unwrap());
+    }
+
+    #[test]
+    fn missing_file_names_path() {
+        let input = Input::from_arg("does/not/exist.txt");
+
+        let err = input.read().unwrap_err().to_string();
+
+        assert!(err.contains("does/not/exist.txt"), "{}", err);
+    }
+}
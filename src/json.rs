@@ -0,0 +1,44 @@
+//! Helpers for writing JSON lines
+
+/// Quotes `s` as a JSON string
+pub fn string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                out.push_str(&format!("\\u{:04x}", c as u32))
+            }
+            c => out.push(c),
+        }
+    }
+
+    out.push('"');
+    out
+}
+
+/// `string(s)` or `null`
+pub fn optional_string(s: Option<&str>) -> String {
+    s.map_or_else(|| "null".to_owned(), string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strings_are_escaped() {
+        assert_eq!(r#""FPRBRRZA""#, string("FPRBRRZA"));
+        assert_eq!(
+            r#""input\\day24.txt:2: \"plasma\"\n\t| x\u0007""#,
+            string("input\\day24.txt:2: \"plasma\"\n\t| x\u{7}")
+        );
+        assert_eq!("null", optional_string(None));
+    }
+}
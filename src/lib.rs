@@ -1,91 +1,95 @@
 use regex;
 use std::convert::From;
-use std::env;
-use std::error;
 use std::fmt;
-use std::fs::File;
-use std::io::prelude::*;
 use std::ops::{Add, AddAssign, Mul, Sub, SubAssign};
 
+mod answer;
+mod answers;
+mod arena;
+pub mod bench;
+#[cfg(test)]
+mod check;
+mod circular;
+pub mod cycle;
+pub mod days;
+mod dir;
+mod disjoint;
+pub mod elfcode;
+mod error;
+pub mod generate;
+mod grid;
+mod image;
+mod input;
+pub mod json;
+pub mod log;
+pub mod parse;
+pub mod pool;
+pub mod record;
+mod rect;
+pub mod search;
+mod sparse;
+mod vector;
+
+pub use crate::answer::Answer;
+pub use crate::answers::Answers;
+pub use crate::arena::{Arena, Handle};
+pub use crate::circular::CircularList;
+pub use crate::dir::{Dir4, Dir8};
+pub use crate::disjoint::DisjointSet;
+pub use crate::error::{parse_lines, Error, Position};
+pub use crate::grid::Grid;
+pub use crate::image::{Color, Image};
+pub use crate::input::{input_dir, read_path, Input, INPUT_DIR_VAR};
+pub use crate::record::Recording;
+pub use crate::rect::{BoxN, Cuboid, Rect};
+pub use crate::sparse::SparseGrid;
+pub use crate::vector::{Integer, VecN, V2, V3, V4};
+
 pub fn get_value<'a, T: std::str::FromStr>(
     caps: &regex::Captures<'a>,
     index: usize,
-) -> std::result::Result<T, CustomError> {
-    caps.get(index)
-        .and_then(|v| v.as_str().trim().parse::<T>().ok())
-        .ok_or_else::<CustomError, _>(|| {
-            CustomError(format!("Invalid {}", index))
-        })
-}
-
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct CustomError(pub String);
-
-impl fmt::Display for CustomError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Error: {}", self.0)
-    }
-}
-
-// This is important for other errors to wrap this one.
-impl error::Error for CustomError {
-    fn description(&self) -> &str {
-        &self.0
-    }
-
-    fn cause(&self) -> Option<&error::Error> {
-        // Generic error, underlying cause isn't tracked.
-        None
-    }
-}
-
-pub type Result<T> = ::std::result::Result<T, Box<::std::error::Error>>;
-
-/// Reads the input from input/<day>.txt
-/// Trims excess whitespace
-pub fn read_input() -> Result<String> {
-    let mut s = String::new();
-
-    let exe_name = env::current_exe()?;
-
-    let name =
-        exe_name.file_stem().ok_or_else::<Box<CustomError>, _>(|| {
-            CustomError("Unable to get file_stem".to_owned()).into()
-        })?;
-
-    let mut file = File::open(format!("input/{}.txt", name.to_string_lossy()))?;
-
-    file.read_to_string(&mut s)?;
-
-    Ok(s.trim().to_owned())
+) -> Result<T> {
+    let value = caps
+        .get(index)
+        .ok_or_else(|| Error::parse(format!("missing capture {}", index)))?;
+
+    value.as_str().trim().parse::<T>().map_err(|_| {
+        Error::parse(format!("invalid value '{}'", value.as_str()))
+            .at_column(value.start() + 1)
+    })
 }
 
-pub fn read_input_untrimmed() -> Result<String> {
-    let mut s = String::new();
-
-    let exe_name = env::current_exe()?;
+pub type Result<T> = ::std::result::Result<T, Error>;
 
-    let name =
-        exe_name.file_stem().ok_or_else::<Box<CustomError>, _>(|| {
-            CustomError("Unable to get file_stem".to_owned()).into()
-        })?;
+/// A solution for a single day of the puzzle, shared by the threads running
+/// the days
+pub trait Solution: Sync {
+    /// Day number of the puzzle, 1-25
+    fn day(&self) -> u32;
 
-    let mut file = File::open(format!("input/{}.txt", name.to_string_lossy()))?;
+    /// Solves part 1 using the untrimmed input
+    fn part1(&self, input: &str) -> Result<Answer>;
 
-    file.read_to_string(&mut s)?;
+    /// Solves part 2 using the untrimmed input
+    fn part2(&self, input: &str) -> Result<Answer>;
 
-    Ok(s)
-}
-
-/// Reads the input from input/<day>.txt
-pub fn read_file(file_name: &str) -> Result<String> {
-    let mut s = String::new();
-
-    let mut file = File::open(file_name)?;
+    /// Parses the untrimmed input without solving it, so that parsing can be
+    /// timed on its own, `false` if the day parses only while solving
+    fn parse(&self, _input: &str) -> Result<bool> {
+        Ok(false)
+    }
 
-    file.read_to_string(&mut s)?;
+    /// Draws the puzzle for the untrimmed input, `None` if the day has
+    /// nothing to draw
+    fn image(&self, _input: &str) -> Result<Option<Image>> {
+        Ok(None)
+    }
 
-    Ok(s)
+    /// Records the simulation of a part frame by frame, `None` if the part
+    /// is not a simulation
+    fn record(&self, _input: &str, _part: u32) -> Result<Option<Recording>> {
+        Ok(None)
+    }
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd, Default)]
@@ -101,23 +105,23 @@ impl Vector2 {
 
     /// [up, right, down, left]
     pub fn around(&self) -> [Vector2; 4] {
-        [self.up(), self.right(), self.down(), self.left()]
+        Dir4::ALL.map(|dir| *self + dir)
     }
 
     pub fn up(&self) -> Self {
-        *self + (0, -1)
+        *self + Dir4::Up
     }
 
     pub fn down(&self) -> Self {
-        *self + (0, 1)
+        *self + Dir4::Down
     }
 
     pub fn left(&self) -> Self {
-        *self + (-1, 0)
+        *self + Dir4::Left
     }
 
     pub fn right(&self) -> Self {
-        *self + (1, 0)
+        *self + Dir4::Right
     }
 }
 
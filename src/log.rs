@@ -0,0 +1,292 @@
+//! Leveled logging to stderr with a level for each day
+//!
+//! Messages are logged with the `error!`, `warn!`, `info!`, `debug!` and
+//! `trace!` macros. The target of a message is the last part of the module
+//! path it was logged from, so the messages of day 23 have the target
+//! `day23`. Only errors and warnings are shown unless the filter is changed
+//! with `set_filter` or `$AOC_LOG`, e.g. `AOC_LOG=day23=trace,info`.
+
+use std::env;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::RwLock;
+
+use crate::{Error, Result};
+
+/// Environment variable with the filter of the logged messages
+pub const LOG_VAR: &str = "AOC_LOG";
+
+/// Importance of a message, from the most to the least important
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub enum Level {
+    Error = 1,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    /// Level of `-v` repeated `count` times, `None` if not given
+    pub fn from_verbosity(count: usize) -> Option<Level> {
+        match count {
+            0 => None,
+            1 => Some(Level::Info),
+            2 => Some(Level::Debug),
+            _ => Some(Level::Trace),
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        };
+
+        f.pad(name)
+    }
+}
+
+impl FromStr for Level {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "error" => Ok(Level::Error),
+            "warn" => Ok(Level::Warn),
+            "info" => Ok(Level::Info),
+            "debug" => Ok(Level::Debug),
+            "trace" => Ok(Level::Trace),
+            _ => Err(Error::invalid(format!("Unknown log level '{}'", s))),
+        }
+    }
+}
+
+/// Most detailed level shown for each target, `None` shows nothing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Filter {
+    default: Option<Level>,
+    targets: Vec<(String, Option<Level>)>,
+}
+
+impl Filter {
+    /// Shows messages up to `level` for every target
+    pub fn new(level: Option<Level>) -> Self {
+        Filter {
+            default: level,
+            targets: Vec::new(),
+        }
+    }
+
+    /// Filter from `$AOC_LOG`, the default filter if it is not set
+    pub fn from_env() -> Result<Self> {
+        match env::var(LOG_VAR) {
+            Ok(s) => s.parse(),
+            Err(_) => Ok(Filter::default()),
+        }
+    }
+
+    /// Shows at least messages up to `level` for the targets without their
+    /// own level
+    pub fn at_least(mut self, level: Level) -> Self {
+        self.default = self.default.max(Some(level));
+        self
+    }
+
+    /// Most detailed level shown for `target`
+    pub fn level(&self, target: &str) -> Option<Level> {
+        self.targets
+            .iter()
+            .rev()
+            .find(|(name, _)| name == target)
+            .map_or(self.default, |&(_, level)| level)
+    }
+
+    pub fn enabled(&self, level: Level, target: &str) -> bool {
+        self.level(target).is_some_and(|shown| level <= shown)
+    }
+
+    /// Most detailed level shown for any target
+    fn max_level(&self) -> Option<Level> {
+        self.targets
+            .iter()
+            .map(|&(_, level)| level)
+            .fold(self.default, |a, b| a.max(b))
+    }
+}
+
+impl Default for Filter {
+    fn default() -> Self {
+        Filter::new(Some(Level::Warn))
+    }
+}
+
+/// Comma separated levels, `target=level` sets the level of one target and
+/// a bare level the level of the rest. `off` shows nothing.
+impl FromStr for Filter {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut filter = Filter::default();
+
+        let level = |s: &str| -> Result<Option<Level>> {
+            match s.trim() {
+                "off" => Ok(None),
+                level => level.parse().map(Some),
+            }
+        };
+
+        for part in s.split(',').filter(|part| !part.trim().is_empty()) {
+            match part.find('=') {
+                Some(eq) => {
+                    let target = part[..eq].trim().to_owned();
+                    filter.targets.push((target, level(&part[eq + 1..])?));
+                }
+                None => filter.default = level(part)?,
+            }
+        }
+
+        Ok(filter)
+    }
+}
+
+/// Filter used by the macros, `None` until set or first used
+static FILTER: RwLock<Option<Filter>> = RwLock::new(None);
+
+/// Most detailed level of `FILTER` as a number, 0 shows nothing
+static MAX_LEVEL: AtomicUsize = AtomicUsize::new(UNSET);
+
+const UNSET: usize = usize::MAX;
+
+/// Replaces the filter of the logged messages
+pub fn set_filter(filter: Filter) {
+    let mut current = FILTER.write().unwrap_or_else(|e| e.into_inner());
+
+    MAX_LEVEL.store(
+        filter.max_level().map_or(0, |level| level as usize),
+        Ordering::Relaxed,
+    );
+    *current = Some(filter);
+}
+
+/// Target of messages logged from `module_path`
+pub fn target(module_path: &str) -> &str {
+    module_path.rsplit("::").next().unwrap_or(module_path)
+}
+
+/// Whether messages at `level` from `module_path` are shown
+pub fn enabled(level: Level, module_path: &str) -> bool {
+    match MAX_LEVEL.load(Ordering::Relaxed) {
+        UNSET => {
+            set_filter(Filter::from_env().unwrap_or_default());
+            enabled(level, module_path)
+        }
+        max if level as usize > max => false,
+        _ => FILTER
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_ref()
+            .is_some_and(|filter| filter.enabled(level, target(module_path))),
+    }
+}
+
+/// Writes a message, used by the macros after checking `enabled`
+pub fn write(level: Level, module_path: &str, args: fmt::Arguments) {
+    eprintln!("[{:<5} {}] {}", level, target(module_path), args);
+}
+
+/// Logs a message at the given level
+#[macro_export]
+macro_rules! log {
+    ($level:expr, $($arg:tt)+) => {{
+        let level = $level;
+        if $crate::log::enabled(level, module_path!()) {
+            $crate::log::write(level, module_path!(), format_args!($($arg)+));
+        }
+    }};
+}
+
+/// Whether messages at the given level are shown, for skipping work done
+/// only to log it
+#[macro_export]
+macro_rules! log_enabled {
+    ($level:expr) => {
+        $crate::log::enabled($level, module_path!())
+    };
+}
+
+#[macro_export]
+macro_rules! error {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Error, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! warn {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Warn, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Info, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Debug, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Trace, $($arg)+) };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_filters() {
+        let filter: Filter = "day23=trace, info,day10=off".parse().unwrap();
+
+        assert_eq!(Some(Level::Trace), filter.level("day23"));
+        assert_eq!(None, filter.level("day10"));
+        assert_eq!(Some(Level::Info), filter.level("day01"));
+        assert_eq!(Some(Level::Trace), filter.max_level());
+
+        assert_eq!(Filter::default(), "".parse().unwrap());
+        assert_eq!(Filter::new(None), "off".parse().unwrap());
+        assert!("day23=loud".parse::<Filter>().is_err());
+    }
+
+    #[test]
+    fn shows_messages_up_to_level() {
+        let filter: Filter = "day05=debug".parse().unwrap();
+
+        assert!(filter.enabled(Level::Debug, "day05"));
+        assert!(!filter.enabled(Level::Trace, "day05"));
+        assert!(filter.enabled(Level::Warn, "day06"));
+        assert!(!filter.enabled(Level::Info, "day06"));
+    }
+
+    #[test]
+    fn verbosity_raises_default_level() {
+        let filter: Filter = "day10=off".parse().unwrap();
+        let filter = filter.at_least(Level::Debug);
+
+        assert_eq!(Some(Level::Debug), filter.level("day01"));
+        assert_eq!(None, filter.level("day10"));
+        assert_eq!(Filter::default(), Filter::default().at_least(Level::Error));
+    }
+
+    #[test]
+    fn targets_are_last_module() {
+        assert_eq!("day23", target("aoc::days::day23"));
+        assert_eq!("aoc", target("aoc"));
+    }
+}
@@ -0,0 +1,892 @@
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+use std::process;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use aoc::bench::{self, CountingAllocator, Sample, Stats};
+use aoc::log::{self, Filter, Level};
+use aoc::pool::{self, Pool};
+use aoc::record::{Control, Player};
+use aoc::{json, Answer, Answers, Error, Input, Recording, Result, Solution};
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const USAGE: &str = "Usage: aoc run <day|all> [--part <1|2>] [--input <path|->] [--format <text|json>] [--threads <n>]
+       aoc verify <day|all> [--part <1|2>] [--answers <path>] [--threads <n>]
+       aoc bench <day|all> [--part <1|2>] [--runs <n>] [--format <text|json>]
+       aoc image <day|all> [--input <path|->] [--scale <n>] [--output <path>]
+       aoc record <day> [--part <1|2>] [--input <path|->] [--output <path>]
+       aoc replay <day|path> [--part <1|2>] [--input <path|->]
+       aoc generate <day> [--seed <n>] [--size <n>] [--output <path>]
+
+Inputs are read from input/dayNN.txt unless overridden with --input
+or by setting the AOC_INPUT_DIR environment variable.
+verify compares the answers with input/answers.toml by default.
+run --format json prints a JSON object per part with the day, part,
+answer, elapsed time and error.
+run and verify solve the days concurrently, a single day that searches
+independent cases splits them between threads. The number of threads
+defaults to AOC_THREADS or the number of cores.
+-v, --verbose shows the log messages of the days, repeat it (-vv, -vvv)
+for more detail. AOC_LOG sets the levels of single days, for example
+AOC_LOG=day23=trace,info.
+bench runs reading the input, parsing it and each part 5 times by
+default. Parsing is timed on its own for the days that can parse without
+solving, the parts still parse the input so their times include it.
+image writes dayNN.png unless overridden with --output, which accepts
+.png and .ppm paths.
+record writes the frames of a simulation to dayNN-partP.rec unless
+overridden with --output, replay shows a saved recording or records a day
+and shows it. Part 1 is recorded by default.
+generate prints a random input for the day, the same seed and size
+always give the same input. The size defaults to the size of the real
+input.";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Command {
+    Run,
+    Verify,
+    Bench,
+    Image,
+    Record,
+    Replay,
+    Generate,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Days {
+    All,
+    Day(u32),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Options {
+    command: Command,
+    days: Days,
+    part: Option<u32>,
+    input: Option<Input>,
+    answers: Option<PathBuf>,
+    runs: usize,
+    format: Format,
+    scale: usize,
+    output: Option<PathBuf>,
+    /// Saved recording to replay
+    recording: Option<PathBuf>,
+    seed: u64,
+    /// Size of the generated input, the size of the real input by default
+    size: Option<usize>,
+    /// Number of threads, `$AOC_THREADS` or the number of cores by default
+    threads: Option<usize>,
+    /// Number of times `-v` was given
+    verbose: usize,
+}
+
+fn main() {
+    let options = match parse_args(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}", e);
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    };
+
+    let filter = match Filter::from_env() {
+        Ok(filter) => filter,
+        Err(e) => {
+            eprintln!("{}: {}", log::LOG_VAR, e);
+            process::exit(2);
+        }
+    };
+
+    log::set_filter(match Level::from_verbosity(options.verbose) {
+        Some(level) => filter.at_least(level),
+        None => filter,
+    });
+
+    if let Some(threads) = options.threads {
+        pool::set_threads(threads);
+    }
+
+    let solutions = match options.days {
+        Days::All => aoc::days::all(),
+        Days::Day(day) => aoc::days::get(day).into_iter().collect(),
+    };
+
+    let ok = match options.command {
+        Command::Run => run_all(&solutions, &options),
+        Command::Verify => verify_all(&solutions, &options),
+        Command::Bench => bench_all(&solutions, &options),
+        Command::Image => image_all(&solutions, &options),
+        Command::Record => record_all(&solutions, &options),
+        Command::Replay => replay_all(&solutions, &options),
+        Command::Generate => generate(&options),
+    };
+
+    if !ok {
+        process::exit(1);
+    }
+}
+
+fn parse_args<I: Iterator<Item = String>>(mut args: I) -> Result<Options> {
+    let command = match args.next().as_ref().map(|s| s.as_str()) {
+        Some("run") => Command::Run,
+        Some("verify") => Command::Verify,
+        Some("bench") => Command::Bench,
+        Some("image") => Command::Image,
+        Some("record") => Command::Record,
+        Some("replay") => Command::Replay,
+        Some("generate") => Command::Generate,
+        Some(cmd) => {
+            return Err(Error::invalid(format!("Unknown command '{}'", cmd)))
+        }
+        None => return Err(Error::invalid("Missing command")),
+    };
+
+    let mut recording = None;
+
+    let days = match args.next() {
+        Some(ref s) if s == "all" => Days::All,
+        Some(s) if command == Command::Replay && parse_day(&s).is_err() => {
+            recording = Some(PathBuf::from(s));
+            Days::All
+        }
+        Some(s) => Days::Day(parse_day(&s)?),
+        None => return Err(Error::invalid("Missing day")),
+    };
+
+    let mut part = None;
+    let mut input = None;
+    let mut answers = None;
+    let mut runs = 5;
+    let mut format = Format::Text;
+    let mut scale = 1;
+    let mut output = None;
+    let mut seed = 0;
+    let mut size = None;
+    let mut threads = None;
+    let mut verbose = 0;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" | "-p" => {
+                part = Some(parse_part(&value_for(&arg, &mut args)?)?);
+            }
+            "--input" | "-i" => {
+                input = Some(Input::from_arg(&value_for(&arg, &mut args)?));
+            }
+            "--answers" => {
+                answers = Some(PathBuf::from(value_for(&arg, &mut args)?));
+            }
+            "--runs" | "-n" => {
+                runs = parse_runs(&value_for(&arg, &mut args)?)?;
+            }
+            "--format" | "-f" => {
+                format = parse_format(&value_for(&arg, &mut args)?)?;
+            }
+            "--scale" | "-s" => {
+                scale = parse_scale(&value_for(&arg, &mut args)?)?;
+            }
+            "--output" | "-o" => {
+                output = Some(PathBuf::from(value_for(&arg, &mut args)?));
+            }
+            "--seed" => {
+                seed = parse_seed(&value_for(&arg, &mut args)?)?;
+            }
+            "--size" => {
+                size = Some(parse_size(&value_for(&arg, &mut args)?)?);
+            }
+            "--threads" | "-j" => {
+                threads = Some(parse_threads(&value_for(&arg, &mut args)?)?);
+            }
+            "--verbose" => verbose += 1,
+            _ if is_verbose_flag(&arg) => verbose += arg.len() - 1,
+            _ => {
+                return Err(Error::invalid(format!(
+                    "Unknown argument '{}'",
+                    arg
+                )))
+            }
+        }
+    }
+
+    if days == Days::All && input.is_some() {
+        return Err(Error::invalid("--input requires a single day"));
+    }
+
+    if days == Days::All && output.is_some() {
+        return Err(Error::invalid("--output requires a single day"));
+    }
+
+    let simulation = matches!(command, Command::Record | Command::Replay);
+    if simulation && days == Days::All && recording.is_none() {
+        return Err(Error::invalid("record and replay require a single day"));
+    }
+
+    if command == Command::Generate && days == Days::All {
+        return Err(Error::invalid("generate requires a single day"));
+    }
+
+    Ok(Options {
+        command,
+        days,
+        part,
+        input,
+        answers,
+        runs,
+        format,
+        scale,
+        output,
+        recording,
+        seed,
+        size,
+        threads,
+        verbose,
+    })
+}
+
+/// `-v`, `-vv` and so on
+fn is_verbose_flag(arg: &str) -> bool {
+    arg.len() > 1 && arg.starts_with('-') && arg[1..].chars().all(|c| c == 'v')
+}
+
+fn value_for<I: Iterator<Item = String>>(
+    arg: &str,
+    args: &mut I,
+) -> Result<String> {
+    args.next()
+        .ok_or_else(|| Error::invalid(format!("Missing value for {}", arg)))
+}
+
+fn parse_day(s: &str) -> Result<u32> {
+    match s.parse::<u32>() {
+        Ok(day) if (1..=25).contains(&day) => Ok(day),
+        _ => Err(Error::invalid(format!("Invalid day '{}'", s))),
+    }
+}
+
+fn parse_runs(s: &str) -> Result<usize> {
+    match s.parse::<usize>() {
+        Ok(runs) if runs > 0 => Ok(runs),
+        _ => Err(Error::invalid(format!("Invalid number of runs '{}'", s))),
+    }
+}
+
+fn parse_scale(s: &str) -> Result<usize> {
+    match s.parse::<usize>() {
+        Ok(scale) if scale > 0 => Ok(scale),
+        _ => Err(Error::invalid(format!("Invalid scale '{}'", s))),
+    }
+}
+
+fn parse_seed(s: &str) -> Result<u64> {
+    s.parse::<u64>()
+        .map_err(|_| Error::invalid(format!("Invalid seed '{}'", s)))
+}
+
+fn parse_size(s: &str) -> Result<usize> {
+    match s.parse::<usize>() {
+        Ok(size) if size > 0 => Ok(size),
+        _ => Err(Error::invalid(format!("Invalid size '{}'", s))),
+    }
+}
+
+fn parse_threads(s: &str) -> Result<usize> {
+    match s.parse::<usize>() {
+        Ok(threads) if threads > 0 => Ok(threads),
+        _ => Err(Error::invalid(format!("Invalid number of threads '{}'", s))),
+    }
+}
+
+fn parse_format(s: &str) -> Result<Format> {
+    match s {
+        "text" => Ok(Format::Text),
+        "json" => Ok(Format::Json),
+        _ => Err(Error::invalid(format!("Invalid format '{}'", s))),
+    }
+}
+
+fn parse_part(s: &str) -> Result<u32> {
+    match s {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => Err(Error::invalid(format!("Invalid part '{}'", s))),
+    }
+}
+
+fn input_for(options: &Options, solution: &dyn Solution) -> Input {
+    options
+        .input
+        .clone()
+        .unwrap_or_else(|| Input::Day(solution.day()))
+}
+
+/// Names the input file in errors about its contents
+fn in_input(e: Error, input: &Input) -> Error {
+    match input.path() {
+        Some(path) => e.with_path(&path),
+        None => e,
+    }
+}
+
+fn parts(part: Option<u32>) -> Vec<u32> {
+    match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    }
+}
+
+/// Runs a single part and measures how long it took
+fn solve(
+    solution: &dyn Solution,
+    input: &str,
+    part: u32,
+) -> (Result<Answer>, Duration) {
+    let start = Instant::now();
+
+    let result = if part == 1 {
+        solution.part1(input)
+    } else {
+        solution.part2(input)
+    };
+
+    (result, start.elapsed())
+}
+
+/// Answer of a part and how long solving it took
+type PartRun = (u32, Result<Answer>, Duration);
+
+/// Results of the requested parts of a day
+struct DayRun {
+    day: u32,
+    /// Results of the parts, `Err` if the input could not be read
+    parts: Result<Vec<PartRun>>,
+}
+
+/// Solves the requested parts of every day, the days are solved
+/// concurrently on the threads of the pool
+fn run_days(solutions: &[Box<dyn Solution>], options: &Options) -> Vec<DayRun> {
+    Pool::current().map(solutions, |solution| {
+        let source = input_for(options, solution.as_ref());
+
+        let parts = source.read().map(|input| {
+            parts(options.part)
+                .into_iter()
+                .map(|part| {
+                    let (result, elapsed) =
+                        solve(solution.as_ref(), &input, part);
+
+                    (part, result.map_err(|e| in_input(e, &source)), elapsed)
+                })
+                .collect()
+        });
+
+        DayRun {
+            day: solution.day(),
+            parts,
+        }
+    })
+}
+
+/// Runs the requested parts of every day, returns false if any part failed
+fn run_all(solutions: &[Box<dyn Solution>], options: &Options) -> bool {
+    let mut ok = true;
+
+    for run in run_days(solutions, options) {
+        let day = run.day;
+
+        let results = match run.parts {
+            Ok(results) => results,
+            Err(e) => {
+                match options.format {
+                    Format::Text => eprintln!("Day {:02}: {}", day, e),
+                    Format::Json => {
+                        for part in parts(options.part) {
+                            print_result(day, part, &Err(e.to_string()), None);
+                        }
+                    }
+                }
+                ok = false;
+                continue;
+            }
+        };
+
+        for (part, result, elapsed) in results {
+            ok &= result.is_ok();
+
+            match (options.format, result) {
+                (Format::Text, Ok(answer)) => {
+                    println!("Day {:02} part {}: {}", day, part, answer)
+                }
+                (Format::Text, Err(e)) => {
+                    eprintln!("Day {:02} part {}: {:#}", day, part, e)
+                }
+                (Format::Json, result) => {
+                    let result = result.map_err(|e| e.to_string());
+                    print_result(day, part, &result, Some(elapsed));
+                }
+            }
+        }
+    }
+
+    ok
+}
+
+/// Prints the result of a part as a line of JSON, `elapsed` is `None` when
+/// the part was not run
+fn print_result(
+    day: u32,
+    part: u32,
+    result: &std::result::Result<Answer, String>,
+    elapsed: Option<Duration>,
+) {
+    let (answer, error) = match result {
+        Ok(answer) => (answer.to_json(), None),
+        Err(e) => ("null".to_owned(), Some(e.as_str())),
+    };
+
+    let elapsed = elapsed.map_or_else(
+        || "null".to_owned(),
+        |elapsed| format!("{:.3}", bench::millis(elapsed)),
+    );
+
+    println!(
+        "{{\"day\":{},\"part\":{},\"answer\":{},\"elapsed_ms\":{},\"error\":{}}}",
+        day,
+        part,
+        answer,
+        elapsed,
+        json::optional_string(error)
+    );
+}
+
+/// Compares the answers of the requested parts with the known answers,
+/// returns false if any part failed or did not match
+fn verify_all(solutions: &[Box<dyn Solution>], options: &Options) -> bool {
+    let path = options
+        .answers
+        .clone()
+        .unwrap_or_else(|| aoc::input_dir().join("answers.toml"));
+
+    let answers = match Answers::read(&path) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("{}", e);
+            return false;
+        }
+    };
+
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+
+    for run in run_days(solutions, options) {
+        let day = run.day;
+
+        let results = match run.parts {
+            Ok(results) => results,
+            Err(e) => {
+                println!("Day {:02}: ERROR {}", day, e);
+                failed += parts(options.part).len();
+                continue;
+            }
+        };
+
+        for (part, result, elapsed) in results {
+            let expected = answers.get(day, part);
+
+            let status = match (result, expected) {
+                (Ok(Answer::None), None) => {
+                    passed += 1;
+                    "PASS -".to_owned()
+                }
+                (Ok(answer), Some(expected)) => {
+                    let answer = answer.to_string();
+                    if answer == expected {
+                        passed += 1;
+                        format!("PASS {}", answer)
+                    } else {
+                        failed += 1;
+                        format!("FAIL expected {}, got {}", expected, answer)
+                    }
+                }
+                (Ok(answer), None) => {
+                    missing += 1;
+                    format!("MISSING got {}", answer)
+                }
+                (Err(e), _) => {
+                    failed += 1;
+                    format!("ERROR {}", e)
+                }
+            };
+
+            println!(
+                "Day {:02} part {}: {} ({:.3} ms)",
+                day,
+                part,
+                status,
+                bench::millis(elapsed)
+            );
+        }
+    }
+
+    println!("{} passed, {} failed, {} missing", passed, failed, missing);
+
+    failed == 0
+}
+
+/// Measures reading the input and the requested parts of every day,
+/// returns false if any of them failed
+///
+/// The "read" phase only reads the input, the "parse" phase is measured for
+/// the days that can parse the input on their own. The days still parse the
+/// input in each part so parsing is included in the times of the parts too.
+fn bench_all(solutions: &[Box<dyn Solution>], options: &Options) -> bool {
+    let mut ok = true;
+
+    for solution in solutions {
+        let day = solution.day();
+        let source = input_for(options, solution.as_ref());
+
+        let input = match bench_phase(options.runs, || source.read()) {
+            Ok((input, stats)) => {
+                report(options.format, day, "read", &stats);
+                input
+            }
+            Err(e) => {
+                eprintln!("Day {:02}: {}", day, e);
+                ok = false;
+                continue;
+            }
+        };
+
+        match bench_phase(options.runs, || solution.parse(&input)) {
+            Ok((true, stats)) => report(options.format, day, "parse", &stats),
+            Ok((false, _)) => {}
+            Err(e) => {
+                let e = in_input(e, &source);
+                eprintln!("Day {:02} parse: {:#}", day, e);
+                ok = false;
+                continue;
+            }
+        }
+
+        for part in parts(options.part) {
+            let phase = if part == 1 { "part1" } else { "part2" };
+
+            let result = bench_phase(options.runs, || {
+                solve(solution.as_ref(), &input, part).0
+            });
+
+            match result {
+                Ok((_, stats)) => report(options.format, day, phase, &stats),
+                Err(e) => {
+                    let e = in_input(e, &source);
+                    eprintln!("Day {:02} {}: {:#}", day, phase, e);
+                    ok = false;
+                }
+            }
+        }
+    }
+
+    ok
+}
+
+/// Writes a generated input for the day to the output or stdout
+fn generate(options: &Options) -> bool {
+    let day = match options.days {
+        Days::Day(day) => day,
+        Days::All => return false,
+    };
+
+    let size = options
+        .size
+        .or_else(|| aoc::generate::default_size(day))
+        .unwrap_or(1);
+
+    let input = match aoc::generate::generate(day, options.seed, size) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Day {:02}: {:#}", day, e);
+            return false;
+        }
+    };
+
+    let written = match options.output {
+        Some(ref path) => {
+            fs::write(path, input).map_err(|e| Error::io(path, e))
+        }
+        None => io::stdout()
+            .write_all(input.as_bytes())
+            .map_err(Error::from),
+    };
+
+    match written {
+        Ok(()) => true,
+        Err(e) => {
+            eprintln!("Day {:02}: {}", day, e);
+            false
+        }
+    }
+}
+
+/// Draws every day which has something to draw, returns false if any of
+/// them failed
+fn image_all(solutions: &[Box<dyn Solution>], options: &Options) -> bool {
+    let mut ok = true;
+
+    for solution in solutions {
+        let day = solution.day();
+        let source = input_for(options, solution.as_ref());
+
+        let input = match source.read() {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("Day {:02}: {}", day, e);
+                ok = false;
+                continue;
+            }
+        };
+
+        let image = match solution.image(&input) {
+            Ok(Some(image)) => image.scaled(options.scale),
+            Ok(None) => {
+                if options.days == Days::Day(day) {
+                    eprintln!("Day {:02}: nothing to draw", day);
+                }
+                continue;
+            }
+            Err(e) => {
+                eprintln!("Day {:02}: {:#}", day, in_input(e, &source));
+                ok = false;
+                continue;
+            }
+        };
+
+        let path = options
+            .output
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(format!("day{:02}.png", day)));
+
+        match image.save(&path) {
+            Ok(()) => println!(
+                "Day {:02}: {}x{} image written to {}",
+                day,
+                image.width(),
+                image.height(),
+                path.display()
+            ),
+            Err(e) => {
+                eprintln!("Day {:02}: {}", day, e);
+                ok = false;
+            }
+        }
+    }
+
+    ok
+}
+
+/// Records the simulation of a single part
+fn record(
+    solution: &dyn Solution,
+    options: &Options,
+) -> Result<Option<Recording>> {
+    let source = input_for(options, solution);
+    let input = source.read()?;
+
+    solution
+        .record(&input, options.part.unwrap_or(1))
+        .map_err(|e| in_input(e, &source))
+}
+
+/// Saves the recordings of the requested days, returns false if any of
+/// them failed
+fn record_all(solutions: &[Box<dyn Solution>], options: &Options) -> bool {
+    let mut ok = true;
+
+    for solution in solutions {
+        let day = solution.day();
+        let part = options.part.unwrap_or(1);
+
+        let recording = match record(solution.as_ref(), options) {
+            Ok(Some(recording)) => recording,
+            Ok(None) => {
+                eprintln!("Day {:02}: nothing to record", day);
+                ok = false;
+                continue;
+            }
+            Err(e) => {
+                eprintln!("Day {:02}: {:#}", day, e);
+                ok = false;
+                continue;
+            }
+        };
+
+        let path = options.output.clone().unwrap_or_else(|| {
+            PathBuf::from(format!("day{:02}-part{}.rec", day, part))
+        });
+
+        match recording.save(&path) {
+            Ok(()) => println!(
+                "Day {:02} part {}: {} frames ({} keyframes) written to {}",
+                day,
+                part,
+                recording.len(),
+                recording.keyframes(),
+                path.display()
+            ),
+            Err(e) => {
+                eprintln!("Day {:02}: {}", day, e);
+                ok = false;
+            }
+        }
+    }
+
+    ok
+}
+
+/// Shows a saved recording or records the requested day and shows it
+fn replay_all(solutions: &[Box<dyn Solution>], options: &Options) -> bool {
+    let recording = match (&options.recording, solutions.first()) {
+        (Some(path), _) => Recording::load(path).map(Some),
+        (None, Some(solution)) => record(solution.as_ref(), options),
+        (None, None) => Ok(None),
+    };
+
+    let result = match recording {
+        Ok(Some(recording)) => replay(&recording),
+        Ok(None) => Err(Error::invalid("nothing to record")),
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(()) => true,
+        Err(e) => {
+            eprintln!("{:#}", e);
+            false
+        }
+    }
+}
+
+/// Size of the terminal from `$LINES` and `$COLUMNS`
+fn terminal_size() -> (usize, usize) {
+    let var = |name: &str, default: usize| {
+        env::var(name)
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default)
+    };
+
+    (var("LINES", 24), var("COLUMNS", 80))
+}
+
+/// Shows the recording in the terminal, reading a command per line
+fn replay(recording: &Recording) -> Result<()> {
+    let (rows, columns) = terminal_size();
+    // Status, info and help lines and the prompt
+    let mut player = Player::new(recording, rows.saturating_sub(4), columns)?;
+
+    let (sender, commands) = mpsc::channel();
+    thread::spawn(move || {
+        for line in io::stdin().lock().lines().map_while(|l| l.ok()) {
+            if sender.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut message = String::new();
+
+    loop {
+        print!("\x1b[2J\x1b[H{}{}> ", player.screen(), message);
+        io::stdout().flush()?;
+
+        let line = if player.is_playing() {
+            match commands.recv_timeout(player.delay()) {
+                Ok(line) => line,
+                Err(RecvTimeoutError::Timeout) => {
+                    player.advance();
+                    continue;
+                }
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+        } else {
+            match commands.recv() {
+                Ok(line) => line,
+                Err(_) => break,
+            }
+        };
+
+        message.clear();
+
+        match line.parse::<Control>() {
+            Ok(control) => {
+                if !player.apply(control) {
+                    break;
+                }
+            }
+            Err(e) => message = format!("{}\n", e),
+        }
+    }
+
+    println!();
+
+    Ok(())
+}
+
+/// Runs `f` the given number of times, stops at the first error
+fn bench_phase<T, F: FnMut() -> Result<T>>(
+    runs: usize,
+    mut f: F,
+) -> Result<(T, Stats)> {
+    let mut samples: Vec<Sample> = Vec::with_capacity(runs);
+    let mut last = None;
+
+    for _ in 0..runs {
+        let (value, sample) = bench::measure(&mut f);
+        last = Some(value?);
+        samples.push(sample);
+    }
+
+    let value = last.ok_or_else(|| Error::invalid("No runs were made"))?;
+
+    Ok((value, Stats::from_samples(&samples)))
+}
+
+fn report(format: Format, day: u32, phase: &str, stats: &Stats) {
+    match format {
+        Format::Text => println!(
+            "Day {:02} {:<5}: min {:>10.3} ms  median {:>10.3} ms  \
+             max {:>10.3} ms  allocs {:>9} ({} bytes)",
+            day,
+            phase,
+            bench::millis(stats.min),
+            bench::millis(stats.median),
+            bench::millis(stats.max),
+            stats.allocations,
+            stats.allocated_bytes
+        ),
+        Format::Json => println!(
+            "{{\"day\":{},\"phase\":\"{}\",\"runs\":{},\
+             \"min_ms\":{:.3},\"median_ms\":{:.3},\"max_ms\":{:.3},\
+             \"allocations\":{},\"allocated_bytes\":{}}}",
+            day,
+            phase,
+            stats.runs,
+            bench::millis(stats.min),
+            bench::millis(stats.median),
+            bench::millis(stats.max),
+            stats.allocations,
+            stats.allocated_bytes
+        ),
+    }
+}
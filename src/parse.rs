@@ -0,0 +1,338 @@
+//! Helpers for reading the puzzle inputs line by line
+//!
+//! Errors point at the offending token, `parse_lines` adds the line number
+//! and the line itself.
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::{Error, Result};
+
+/// Matches `line` against `pattern` and parses the values of its `{}`
+/// placeholders in order, evaluates to a `Result` of a tuple
+///
+/// ```
+/// # use aoc::scan;
+/// let (id, x, y) = scan!("#3 @ 5,5", "#{} @ {},{}", u32, i32, i32).unwrap();
+/// assert_eq!((3, 5, 5), (id, x, y));
+/// ```
+#[macro_export]
+macro_rules! scan {
+    ($line:expr, $pattern:expr, $($t:ty),+ $(,)?) => {
+        $crate::parse::scan($line, $pattern)
+            .and_then(|mut values| Ok(($(values.read::<$t>()?,)+)))
+    };
+}
+
+/// Values of the placeholders matched by `scan`
+#[derive(Debug, Clone)]
+pub struct Captures<'a> {
+    line: &'a str,
+    values: std::vec::IntoIter<&'a str>,
+}
+
+impl<'a> Captures<'a> {
+    /// Parses the next value, surrounding whitespace is ignored
+    pub fn read<T>(&mut self) -> Result<T>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let value = self.read_str()?;
+
+        value
+            .parse::<T>()
+            .map_err(|e| Error::parse(e.to_string()).at_token(self.line, value))
+    }
+
+    /// Next value as it is, without surrounding whitespace
+    pub fn read_str(&mut self) -> Result<&'a str> {
+        self.values
+            .next()
+            .ok_or_else(|| Error::parse("no values left to read"))
+    }
+}
+
+/// Matches `line` against `pattern` where each `{}` captures a value
+///
+/// Whitespace in the pattern matches any run of whitespace. A placeholder
+/// ends at the first match of the text following it in the pattern, a
+/// placeholder at the end of the pattern takes the rest of the line.
+pub fn scan<'a>(line: &'a str, pattern: &str) -> Result<Captures<'a>> {
+    let mismatch = |expected: &str, rest: &'a str| {
+        Error::parse(format!("expected '{}' to match '{}'", expected, pattern))
+            .at_token(line, rest)
+    };
+
+    let mut literals = pattern.split("{}");
+    let first = literals.next().unwrap_or("");
+
+    let mut rest = match match_literal(first, line) {
+        Some(len) => &line[len..],
+        None => return Err(mismatch(first, line)),
+    };
+
+    let mut values = Vec::new();
+
+    for literal in literals {
+        if literal.is_empty() {
+            values.push(rest.trim());
+            rest = &rest[rest.len()..];
+            continue;
+        }
+
+        let (start, len) = rest
+            .char_indices()
+            .find_map(|(i, _)| {
+                match_literal(literal, &rest[i..]).map(|n| (i, n))
+            })
+            .ok_or_else(|| mismatch(literal, rest))?;
+
+        values.push(rest[..start].trim());
+        rest = &rest[start + len..];
+    }
+
+    if !rest.trim().is_empty() {
+        return Err(Error::parse(format!(
+            "unexpected '{}' after '{}'",
+            rest.trim(),
+            pattern
+        ))
+        .at_token(line, rest.trim_start()));
+    }
+
+    Ok(Captures {
+        line,
+        values: values.into_iter(),
+    })
+}
+
+/// Length of the prefix of `s` matching `literal`
+fn match_literal(literal: &str, s: &str) -> Option<usize> {
+    let mut input = s.char_indices().peekable();
+    let mut literal = literal.chars().peekable();
+
+    while let Some(expected) = literal.next() {
+        if expected.is_whitespace() {
+            while literal.peek().is_some_and(|c| c.is_whitespace()) {
+                literal.next();
+            }
+
+            input.next_if(|(_, c)| c.is_whitespace())?;
+            while input.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+        } else {
+            input.next_if(|&(_, c)| c == expected)?;
+        }
+    }
+
+    Some(input.peek().map_or(s.len(), |&(i, _)| i))
+}
+
+/// Parses a number with an optional sign such as `+7` or `-3`
+pub fn signed<T>(s: &str) -> Result<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let token = s.trim();
+
+    token.parse::<T>().map_err(|e| {
+        Error::parse(format!("invalid number '{}': {}", token, e))
+            .at_token(s, token)
+    })
+}
+
+/// Every integer in `s`, such as `[3, -2, 1]` in `pos=<3,-2,1>`
+pub fn integers<T>(s: &str) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let bytes = s.as_bytes();
+    let mut result = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let signed = matches!(bytes[i], b'-' | b'+')
+            && bytes.get(i + 1).is_some_and(|b| b.is_ascii_digit());
+
+        if !signed && !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        i += 1;
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+
+        let token = &s[start..i];
+        let value = token.parse::<T>().map_err(|e| {
+            Error::parse(format!("invalid number '{}': {}", token, e))
+                .at_token(s, token)
+        })?;
+
+        result.push(value);
+    }
+
+    Ok(result)
+}
+
+/// Values separated by `delimiter`, such as `fire, cold`
+pub fn list<T>(s: &str, delimiter: char) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    s.split(delimiter)
+        .map(str::trim)
+        .map(|token| {
+            token
+                .parse::<T>()
+                .map_err(|e| Error::parse(e.to_string()).at_token(s, token))
+        })
+        .collect()
+}
+
+/// Entries of the form `key<separator>value` separated by `delimiter`,
+/// such as `weak to fire; immune to cold`
+pub fn key_values<'a>(
+    s: &'a str,
+    delimiter: char,
+    separator: &str,
+) -> Result<Vec<(&'a str, &'a str)>> {
+    s.split(delimiter)
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| match entry.find(separator) {
+            Some(i) => {
+                Ok((entry[..i].trim(), entry[i + separator.len()..].trim()))
+            }
+            None => Err(Error::parse(format!(
+                "expected '{}' in '{}'",
+                separator, entry
+            ))
+            .at_token(s, entry)),
+        })
+        .collect()
+}
+
+/// Blocks of lines separated by blank lines
+pub fn blocks(s: &str) -> impl Iterator<Item = &str> {
+    s.split("\n\n")
+        .map(|block| block.trim_matches('\n'))
+        .filter(|block| !block.trim().is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scan_placeholders() {
+        let claim = scan!(
+            "#123 @ 3,2: 5x4",
+            "#{} @ {},{}: {}x{}",
+            u32,
+            i32,
+            i32,
+            u32,
+            u32
+        );
+        assert_eq!((123, 3, 2, 5, 4), claim.unwrap());
+
+        // Whitespace in the pattern matches any amount of whitespace
+        let light = scan!(
+            "position=< 9,  1> velocity=< 0, -2>",
+            "position=<{}, {}> velocity=<{}, {}>",
+            i32,
+            i32,
+            i32,
+            i32
+        );
+        assert_eq!((9, 1, 0, -2), light.unwrap());
+
+        let mut values = scan(
+            "17 units each with 5390 hit points with an attack",
+            "{} units each with {} hit points {}with an attack",
+        )
+        .unwrap();
+        assert_eq!(17, values.read::<i64>().unwrap());
+        assert_eq!("5390", values.read_str().unwrap());
+        assert_eq!("", values.read_str().unwrap());
+        assert!(values.read_str().is_err());
+    }
+
+    #[test]
+    fn scan_errors_point_at_the_token() {
+        let err = scan!(
+            "#1 @ 3;2: 5x4",
+            "#{} @ {},{}: {}x{}",
+            u32,
+            i32,
+            i32,
+            u32,
+            u32
+        )
+        .unwrap_err();
+        assert_eq!(
+            "expected ',' to match '#{} @ {},{}: {}x{}'",
+            err.to_string()
+        );
+        assert_eq!(Some(6), err.position().and_then(|p| p.column));
+
+        let err = scan!(
+            "#1 @ 3,x: 5x4",
+            "#{} @ {},{}: {}x{}",
+            u32,
+            i32,
+            i32,
+            u32,
+            u32
+        )
+        .unwrap_err();
+        assert_eq!(Some(8), err.position().and_then(|p| p.column));
+
+        let err =
+            scan!("Guard #10 begins shift now", "Guard #{} begins shift", u32)
+                .unwrap_err();
+        assert_eq!(Some(24), err.position().and_then(|p| p.column));
+    }
+
+    #[test]
+    fn numbers() {
+        assert_eq!(7, signed::<i32>("+7").unwrap());
+        assert_eq!(-3, signed::<i32>(" -3 ").unwrap());
+        assert!(signed::<i32>("3-").is_err());
+
+        let values: Vec<i64> = integers("pos=<3,-2,+1>, r=4 - 5").unwrap();
+        assert_eq!(vec![3, -2, 1, 4, 5], values);
+
+        let err = integers::<u8>("1, 300").unwrap_err();
+        assert_eq!(Some(4), err.position().and_then(|p| p.column));
+    }
+
+    #[test]
+    fn lists_and_key_values() {
+        assert_eq!(vec![1, 2, 3], list::<u32>("1, 2,3", ',').unwrap());
+
+        let entries =
+            key_values("weak to fire, cold; immune to slashing", ';', " to ")
+                .unwrap();
+        assert_eq!(
+            vec![("weak", "fire, cold"), ("immune", "slashing")],
+            entries
+        );
+
+        let err = key_values("weak to fire; immune", ';', " to ").unwrap_err();
+        assert_eq!(Some(15), err.position().and_then(|p| p.column));
+    }
+
+    #[test]
+    fn blank_line_separated_blocks() {
+        let input = "a\nb\n\nc\n\n\n\nd\n";
+
+        assert_eq!(vec!["a\nb", "c", "d"], blocks(input).collect::<Vec<_>>());
+    }
+}
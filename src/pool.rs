@@ -0,0 +1,212 @@
+//! Running independent work on several threads
+//!
+//! The work is split into items that are handed out to the threads one at a
+//! time, and the results are returned in the order of the items, so the
+//! outcome never depends on the number of threads or on their timing.
+
+use std::cell::Cell;
+use std::env;
+use std::panic;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+/// Environment variable setting the number of threads
+pub const THREADS_VAR: &str = "AOC_THREADS";
+
+/// Number of threads set with `set_threads`, 0 if not set
+static THREADS: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    /// Whether the thread is one of the threads of a pool
+    static IN_POOL: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Sets the number of threads of `Pool::current`
+pub fn set_threads(threads: usize) {
+    THREADS.store(threads.max(1), Ordering::Relaxed);
+}
+
+/// Number of threads set with `set_threads`, `$AOC_THREADS` or the number
+/// of cores
+pub fn threads() -> usize {
+    match THREADS.load(Ordering::Relaxed) {
+        0 => env::var(THREADS_VAR)
+            .ok()
+            .and_then(|threads| threads.parse::<usize>().ok())
+            .filter(|&threads| threads > 0)
+            .or_else(|| thread::available_parallelism().ok().map(usize::from))
+            .unwrap_or(1),
+        threads => threads,
+    }
+}
+
+/// Runs work on a fixed number of threads
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pool {
+    threads: usize,
+}
+
+impl Pool {
+    /// Pool of at least one thread
+    pub fn new(threads: usize) -> Self {
+        Pool {
+            threads: threads.max(1),
+        }
+    }
+
+    /// Pool with the number of threads from `threads`, or a single thread
+    /// when called from the threads of another pool which already keeps
+    /// the cores busy
+    pub fn current() -> Self {
+        if IN_POOL.with(Cell::get) {
+            Pool::new(1)
+        } else {
+            Pool::new(threads())
+        }
+    }
+
+    pub fn threads(self) -> usize {
+        self.threads
+    }
+
+    /// Applies `f` to every item, the results are in the order of the items
+    pub fn map<T, R, F>(self, items: &[T], f: F) -> Vec<R>
+    where
+        T: Sync,
+        R: Send,
+        F: Fn(&T) -> R + Sync,
+    {
+        let threads = self.threads.min(items.len());
+
+        if threads <= 1 {
+            return items.iter().map(f).collect();
+        }
+
+        let next = AtomicUsize::new(0);
+        let mut results: Vec<Option<R>> = items.iter().map(|_| None).collect();
+
+        thread::scope(|scope| {
+            let workers: Vec<_> = (0..threads)
+                .map(|_| {
+                    scope.spawn(|| {
+                        IN_POOL.with(|in_pool| in_pool.set(true));
+                        let mut done = Vec::new();
+
+                        loop {
+                            let index = next.fetch_add(1, Ordering::Relaxed);
+
+                            match items.get(index) {
+                                Some(item) => done.push((index, f(item))),
+                                None => return done,
+                            }
+                        }
+                    })
+                })
+                .collect();
+
+            for worker in workers {
+                let done =
+                    worker.join().unwrap_or_else(|e| panic::resume_unwind(e));
+
+                for (index, result) in done {
+                    results[index] = Some(result);
+                }
+            }
+        });
+
+        results
+            .into_iter()
+            .map(|result| result.expect("every item is mapped"))
+            .collect()
+    }
+
+    /// Index and result of the first item, in the order of the items, for
+    /// which `f` returns `Some`. The items are mapped in batches, so items
+    /// after a found one are only mapped within the same batch.
+    pub fn find_map<T, R, F>(self, items: &[T], f: F) -> Option<(usize, R)>
+    where
+        T: Sync,
+        R: Send,
+        F: Fn(&T) -> Option<R> + Sync,
+    {
+        for (batch, chunk) in items.chunks(self.threads).enumerate() {
+            let found = self
+                .map(chunk, &f)
+                .into_iter()
+                .enumerate()
+                .find_map(|(index, result)| result.map(|r| (index, r)));
+
+            if let Some((index, result)) = found {
+                return Some((batch * self.threads + index, result));
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn map_keeps_item_order() {
+        let items: Vec<u64> = (0..100).collect();
+
+        for threads in 1..=8 {
+            let squares = Pool::new(threads).map(&items, |&i| i * i);
+
+            assert_eq!(
+                items.iter().map(|i| i * i).collect::<Vec<_>>(),
+                squares
+            );
+        }
+    }
+
+    #[test]
+    fn find_map_returns_first_match() {
+        let items: Vec<u64> = (0..100).collect();
+
+        for threads in 1..=8 {
+            let found = Pool::new(threads).find_map(&items, |&i| {
+                if i % 7 == 3 {
+                    Some(i * 2)
+                } else {
+                    None
+                }
+            });
+
+            assert_eq!(Some((3, 6)), found);
+        }
+
+        assert_eq!(None, Pool::new(4).find_map(&items, |_| None::<u64>));
+    }
+
+    #[test]
+    fn nested_pools_use_one_thread() {
+        assert_eq!(threads(), Pool::current().threads());
+
+        let inner = Pool::new(2).map(&[0, 1], |_| Pool::current().threads());
+        assert_eq!(vec![1, 1], inner);
+
+        // A single item is mapped on the calling thread
+        let inner = Pool::new(2).map(&[0], |_| Pool::current().threads());
+        assert_eq!(vec![threads()], inner);
+    }
+
+    #[test]
+    fn map_of_nothing() {
+        assert!(Pool::new(4).map(&[] as &[u64], |&i| i).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "item 5")]
+    fn map_passes_panics_on() {
+        Pool::new(3).map(&[1, 5, 2], |&i| {
+            if i == 5 {
+                panic!("item 5");
+            }
+            i
+        });
+    }
+}
@@ -0,0 +1,809 @@
+//! Recording the rendered state of a simulation tick by tick and replaying
+//! it frame by frame
+//!
+//! Only the changed part of each line is stored between keyframes, a new
+//! keyframe is stored once the changes since the previous one would take
+//! more space than the whole frame.
+
+use std::fmt::{self, Display};
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::{Error, Result};
+
+const HEADER: &str = "aoc-recording 1";
+
+/// Most lines a frame of a saved recording may have
+const MAX_LINES: usize = 1_000_000;
+
+/// Rendered state of a simulation after a tick
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Frame {
+    pub tick: usize,
+    pub text: String,
+    /// Values shown next to the frame, such as the number of units alive
+    pub info: Vec<(String, String)>,
+}
+
+impl Frame {
+    pub fn new<S: Into<String>>(tick: usize, text: S) -> Self {
+        Frame {
+            tick,
+            text: text.into(),
+            info: Vec::new(),
+        }
+    }
+
+    /// Adds a value shown next to the frame, whitespace in the key and line
+    /// breaks in the value are replaced
+    pub fn with<V: Display>(mut self, key: &str, value: V) -> Self {
+        let key = key.split_whitespace().collect::<Vec<_>>().join("_");
+        let value = value.to_string().replace(['\r', '\n'], " ");
+
+        self.info.push((key, value));
+        self
+    }
+}
+
+/// Replaces `removed` bytes at `start` of a line with `text`
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Edit {
+    line: usize,
+    start: usize,
+    removed: usize,
+    text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Stored {
+    tick: usize,
+    info: Vec<(String, String)>,
+    /// Number of lines in the frame
+    lines: usize,
+    /// Keyframes are edits of an empty frame
+    keyframe: bool,
+    edits: Vec<Edit>,
+}
+
+impl Stored {
+    fn bytes(&self) -> usize {
+        self.edits.iter().map(|e| e.text.len() + 3 * 8).sum()
+    }
+}
+
+/// Frames captured by a `Recorder`
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Recording {
+    title: String,
+    frames: Vec<Stored>,
+}
+
+impl Recording {
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Number of keyframes, the other frames only store their changes
+    pub fn keyframes(&self) -> usize {
+        self.frames.iter().filter(|f| f.keyframe).count()
+    }
+
+    /// Rebuilds the frame at `index`
+    pub fn frame(&self, index: usize) -> Option<Frame> {
+        let stored = self.frames.get(index)?;
+
+        Some(Frame {
+            tick: stored.tick,
+            text: self.lines_at(index).join("\n"),
+            info: stored.info.clone(),
+        })
+    }
+
+    /// Index of the first frame at or after `tick`
+    pub fn index_of_tick(&self, tick: usize) -> Option<usize> {
+        self.frames.iter().position(|f| f.tick >= tick)
+    }
+
+    fn lines_at(&self, index: usize) -> Vec<String> {
+        let keyframe = self.frames[..=index]
+            .iter()
+            .rposition(|f| f.keyframe)
+            .unwrap_or(0);
+
+        let mut lines = Vec::new();
+        for stored in &self.frames[keyframe..=index] {
+            apply(&mut lines, stored);
+        }
+
+        lines
+    }
+
+    /// First and last changed line of the frame at `index` and the byte
+    /// offset of the first change
+    fn changes(&self, index: usize) -> Option<(usize, usize, usize)> {
+        let stored = &self.frames[index];
+
+        if stored.keyframe && index > 0 {
+            return None;
+        }
+
+        let first = stored.edits.first()?;
+        let last = stored.edits.last()?;
+
+        Some((first.line, last.line, first.start))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, self.to_string()).map_err(|e| Error::io(path, e))
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let s = fs::read_to_string(path).map_err(|e| Error::io(path, e))?;
+
+        s.parse::<Recording>().map_err(|e| e.with_path(path))
+    }
+}
+
+fn apply(lines: &mut Vec<String>, stored: &Stored) {
+    if stored.keyframe {
+        lines.clear();
+    }
+
+    lines.resize(stored.lines, String::new());
+
+    // The edits were checked against the frame when they were read
+    for edit in &stored.edits {
+        let end = edit.start + edit.removed;
+        lines[edit.line].replace_range(edit.start..end, &edit.text);
+    }
+}
+
+/// Edits turning `old` into `new` line by line
+fn diff(old: &[String], new: &[&str]) -> Vec<Edit> {
+    let mut edits = Vec::new();
+
+    for (index, &new_line) in new.iter().enumerate() {
+        let old_line = old.get(index).map_or("", String::as_str);
+
+        if old_line == new_line {
+            continue;
+        }
+
+        let (a, b) = (old_line.as_bytes(), new_line.as_bytes());
+
+        let mut prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+        while !old_line.is_char_boundary(prefix) {
+            prefix -= 1;
+        }
+
+        let mut suffix = a[prefix..]
+            .iter()
+            .rev()
+            .zip(b[prefix..].iter().rev())
+            .take_while(|(x, y)| x == y)
+            .count();
+        while !old_line.is_char_boundary(a.len() - suffix)
+            || !new_line.is_char_boundary(b.len() - suffix)
+        {
+            suffix -= 1;
+        }
+
+        edits.push(Edit {
+            line: index,
+            start: prefix,
+            removed: a.len() - prefix - suffix,
+            text: new_line[prefix..b.len() - suffix].to_owned(),
+        });
+    }
+
+    edits
+}
+
+/// Captures frames of a simulation, a recorder which is off ignores them
+/// without rendering them
+#[derive(Debug, Clone)]
+pub struct Recorder {
+    on: bool,
+    recording: Recording,
+    current: Vec<String>,
+    since_keyframe: usize,
+}
+
+impl Recorder {
+    pub fn new<S: Into<String>>(title: S) -> Self {
+        Recorder {
+            on: true,
+            recording: Recording {
+                title: title.into(),
+                frames: Vec::new(),
+            },
+            current: Vec::new(),
+            since_keyframe: 0,
+        }
+    }
+
+    pub fn off() -> Self {
+        Recorder {
+            on: false,
+            ..Recorder::new("")
+        }
+    }
+
+    pub fn is_on(&self) -> bool {
+        self.on
+    }
+
+    /// Stores the frame returned by `f`, which is only called when the
+    /// recorder is on
+    pub fn record<F: FnOnce() -> Frame>(&mut self, f: F) {
+        if !self.on {
+            return;
+        }
+
+        let frame = f();
+        let lines: Vec<&str> = frame.text.lines().collect();
+
+        let mut stored = Stored {
+            tick: frame.tick,
+            info: frame.info,
+            lines: lines.len(),
+            keyframe: false,
+            edits: diff(&self.current, &lines),
+        };
+
+        let size: usize = lines.iter().map(|l| l.len()).sum();
+        let changes = stored.bytes();
+
+        if self.recording.is_empty() || self.since_keyframe + changes > size {
+            stored.keyframe = true;
+            stored.edits = diff(&[], &lines);
+            self.since_keyframe = 0;
+        } else {
+            self.since_keyframe += changes;
+        }
+
+        apply(&mut self.current, &stored);
+        self.recording.frames.push(stored);
+    }
+
+    pub fn finish(self) -> Recording {
+        self.recording
+    }
+}
+
+/// Line based text format, one `frame` line followed by its `info` and
+/// `edit` lines
+impl fmt::Display for Recording {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}", HEADER)?;
+        writeln!(f, "title {}", self.title)?;
+
+        for stored in &self.frames {
+            writeln!(
+                f,
+                "frame {} {} {}",
+                stored.tick,
+                stored.lines,
+                if stored.keyframe { "key" } else { "delta" }
+            )?;
+
+            for (key, value) in &stored.info {
+                writeln!(f, "info {} {}", key, value)?;
+            }
+
+            for edit in &stored.edits {
+                writeln!(
+                    f,
+                    "edit {} {} {} {}",
+                    edit.line, edit.start, edit.removed, edit.text
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Recording {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut lines = s.lines().enumerate();
+
+        match lines.next() {
+            Some((_, HEADER)) => {}
+            _ => {
+                return Err(
+                    Error::parse(format!("expected '{}'", HEADER)).at_line(1)
+                )
+            }
+        }
+
+        let mut recording = Recording::default();
+        // Lines of the frame being read, to check the edits
+        let mut current = Vec::new();
+
+        for (index, line) in lines {
+            let (kind, rest) = line.split_once(' ').unwrap_or((line, ""));
+
+            read_line(&mut recording, &mut current, kind, rest)
+                .map_err(|e| e.in_line(index + 1, line))?;
+        }
+
+        Ok(recording)
+    }
+}
+
+fn read_line(
+    recording: &mut Recording,
+    current: &mut Vec<String>,
+    kind: &str,
+    rest: &str,
+) -> Result<()> {
+    let unexpected = || Error::parse(format!("unexpected '{}'", kind));
+
+    match kind {
+        "title" => recording.title = rest.to_owned(),
+        "frame" => {
+            let (tick, lines, keyframe) =
+                crate::scan!(rest, "{} {} {}", usize, usize, String)?;
+
+            if lines > MAX_LINES {
+                return Err(Error::parse(format!(
+                    "frame of {} lines is larger than {} lines",
+                    lines, MAX_LINES
+                )));
+            }
+
+            let stored = Stored {
+                tick,
+                info: Vec::new(),
+                lines,
+                keyframe: match keyframe.as_str() {
+                    "key" => true,
+                    "delta" => false,
+                    _ => return Err(unexpected()),
+                },
+                edits: Vec::new(),
+            };
+
+            apply(current, &stored);
+            recording.frames.push(stored);
+        }
+        "info" => {
+            let (key, value) = rest.split_once(' ').unwrap_or((rest, ""));
+
+            let stored = recording.frames.last_mut().ok_or_else(unexpected)?;
+            stored.info.push((key.to_owned(), value.to_owned()));
+        }
+        "edit" => {
+            let mut parts = rest.splitn(4, ' ');
+            let mut number = || -> Result<usize> {
+                Ok(parts.next().unwrap_or("").parse::<usize>()?)
+            };
+            let (line, start, removed) = (number()?, number()?, number()?);
+            let text = parts.next().unwrap_or("").to_owned();
+
+            let stored = recording.frames.last_mut().ok_or_else(unexpected)?;
+            let target = current.get_mut(line).ok_or_else(|| {
+                Error::parse(format!("line {} is not in the frame", line))
+            })?;
+
+            let end = start
+                .checked_add(removed)
+                .filter(|&end| {
+                    target.is_char_boundary(start)
+                        && target.is_char_boundary(end)
+                })
+                .ok_or_else(|| {
+                    Error::parse(format!(
+                        "edit of line {} is past its end",
+                        line
+                    ))
+                })?;
+
+            target.replace_range(start..end, &text);
+            stored.edits.push(Edit {
+                line,
+                start,
+                removed,
+                text,
+            });
+        }
+        _ => return Err(unexpected()),
+    }
+
+    Ok(())
+}
+
+/// Command given to a `Player`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Control {
+    Forward(usize),
+    Back(usize),
+    /// Jumps to the first frame at or after the tick
+    Jump(usize),
+    First,
+    Last,
+    PlayPause,
+    Faster,
+    Slower,
+    /// Moves the view by rows and columns
+    Scroll(isize, isize),
+    Quit,
+}
+
+impl Control {
+    pub const HELP: &'static str =
+        "enter/n next  b back  p play/pause  <tick> jump  [ first  ] last  \
+         + faster  - slower  h/j/k/l scroll  q quit";
+}
+
+/// Parses a command typed in the viewer, an empty line steps forward
+impl FromStr for Control {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+
+        if let Ok(tick) = s.parse::<usize>() {
+            return Ok(Control::Jump(tick));
+        }
+
+        let (command, count) = match s.split_once(' ') {
+            Some((command, count)) => (command, count.trim().parse()?),
+            None => (s, 1),
+        };
+
+        let control = match command {
+            "" | "n" => Control::Forward(count),
+            "b" => Control::Back(count),
+            "[" => Control::First,
+            "]" => Control::Last,
+            "p" => Control::PlayPause,
+            "+" => Control::Faster,
+            "-" => Control::Slower,
+            "h" => Control::Scroll(0, -8 * count as isize),
+            "l" => Control::Scroll(0, 8 * count as isize),
+            "k" => Control::Scroll(-8 * count as isize, 0),
+            "j" => Control::Scroll(8 * count as isize, 0),
+            "q" => Control::Quit,
+            _ => {
+                return Err(Error::invalid(format!("unknown command '{}'", s)))
+            }
+        };
+
+        Ok(control)
+    }
+}
+
+/// Steps through a recording, showing the part of the frame which fits the
+/// screen
+///
+/// The view follows the changes of each frame unless it has been scrolled
+/// since the last step.
+#[derive(Debug, Clone)]
+pub struct Player<'a> {
+    recording: &'a Recording,
+    index: usize,
+    lines: Vec<String>,
+    playing: bool,
+    delay: Duration,
+    top: usize,
+    left: usize,
+    rows: usize,
+    columns: usize,
+}
+
+impl<'a> Player<'a> {
+    /// Player showing `rows` lines and `columns` characters of each frame
+    pub fn new(
+        recording: &'a Recording,
+        rows: usize,
+        columns: usize,
+    ) -> Result<Self> {
+        if recording.is_empty() {
+            return Err(Error::invalid("the recording has no frames"));
+        }
+
+        Ok(Player {
+            recording,
+            index: 0,
+            lines: recording.lines_at(0),
+            playing: false,
+            delay: Duration::from_millis(100),
+            top: 0,
+            left: 0,
+            rows: rows.max(1),
+            columns: columns.max(1),
+        })
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    /// Time between frames while playing
+    pub fn delay(&self) -> Duration {
+        self.delay
+    }
+
+    /// Applies the control, returns false when the player should quit
+    pub fn apply(&mut self, control: Control) -> bool {
+        let last = self.recording.len() - 1;
+
+        match control {
+            Control::Forward(n) => self.seek((self.index + n).min(last)),
+            Control::Back(n) => self.seek(self.index.saturating_sub(n)),
+            Control::Jump(tick) => {
+                let index = self.recording.index_of_tick(tick).unwrap_or(last);
+                self.seek(index);
+            }
+            Control::First => self.seek(0),
+            Control::Last => self.seek(last),
+            Control::PlayPause => self.playing = !self.playing,
+            Control::Faster => {
+                self.delay = (self.delay / 2).max(Duration::from_millis(5))
+            }
+            Control::Slower => {
+                self.delay = (self.delay * 2).min(Duration::from_secs(2))
+            }
+            Control::Scroll(rows, columns) => {
+                self.top = offset(self.top, rows);
+                self.left = offset(self.left, columns);
+            }
+            Control::Quit => return false,
+        }
+
+        true
+    }
+
+    /// Steps to the next frame while playing, stops playing at the end
+    pub fn advance(&mut self) {
+        if self.index + 1 < self.recording.len() {
+            self.seek(self.index + 1);
+        } else {
+            self.playing = false;
+        }
+    }
+
+    fn seek(&mut self, index: usize) {
+        if index == self.index {
+            return;
+        }
+
+        if index == self.index + 1 {
+            apply(&mut self.lines, &self.recording.frames[index]);
+        } else {
+            self.lines = self.recording.lines_at(index);
+        }
+
+        self.index = index;
+
+        if let Some((first, last, start)) = self.recording.changes(index) {
+            let column = self.lines[first]
+                .get(..start)
+                .map_or(0, |s| s.chars().count());
+
+            self.top = follow(self.top, first, last, self.rows);
+            self.left = follow(self.left, column, column, self.columns);
+        }
+    }
+
+    /// Status line, info line, the visible part of the frame and the help
+    pub fn screen(&self) -> String {
+        let stored = &self.recording.frames[self.index];
+
+        let mut out = format!(
+            "{}  frame {}/{}  tick {}  {} ({} ms)\n",
+            self.recording.title,
+            self.index + 1,
+            self.recording.len(),
+            stored.tick,
+            if self.playing { "playing" } else { "paused" },
+            self.delay.as_millis()
+        );
+
+        let info: Vec<String> = stored
+            .info
+            .iter()
+            .map(|(key, value)| format!("{}: {}", key, value))
+            .collect();
+        out.push_str(&info.join("  "));
+        out.push('\n');
+
+        for line in self.lines.iter().skip(self.top).take(self.rows) {
+            out.extend(line.chars().skip(self.left).take(self.columns));
+            out.push('\n');
+        }
+
+        out.push_str(Control::HELP);
+        out.push('\n');
+
+        out
+    }
+}
+
+fn offset(value: usize, by: isize) -> usize {
+    if by < 0 {
+        value.saturating_sub(by.unsigned_abs())
+    } else {
+        value + by as usize
+    }
+}
+
+/// Start of a view of `size` which shows `first..=last`, or as much of it
+/// as fits
+fn follow(start: usize, first: usize, last: usize, size: usize) -> usize {
+    let len = last - first + 1;
+
+    if first >= start && last < start + size {
+        start
+    } else if len < size {
+        first.saturating_sub((size - len) / 2)
+    } else {
+        first
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(frames: &[&str]) -> Recording {
+        let mut recorder = Recorder::new("test");
+
+        for (tick, text) in frames.iter().enumerate() {
+            recorder.record(|| Frame::new(tick, *text).with("units", tick));
+        }
+
+        recorder.finish()
+    }
+
+    #[test]
+    fn frames_are_rebuilt_from_the_changes() {
+        let frames = [
+            "#####\n#G.E#\n#####",
+            "#####\n#.GE#\n#####",
+            "#####\n#.G.#\n#####\nE died",
+            "##\nä.ö",
+            "##\näöö",
+        ];
+
+        let recording = record(&frames);
+
+        assert_eq!(frames.len(), recording.len());
+        for (index, text) in frames.iter().enumerate() {
+            let frame = recording.frame(index).unwrap();
+            assert_eq!(*text, frame.text);
+            assert_eq!(index, frame.tick);
+            assert_eq!(vec![("units".into(), index.to_string())], frame.info);
+        }
+
+        assert_eq!(None, recording.frame(frames.len()));
+        assert_eq!(Some(2), recording.index_of_tick(2));
+    }
+
+    #[test]
+    fn keyframes_are_stored_when_changes_add_up() {
+        let rows: Vec<String> =
+            (0..20).map(|_| ".".repeat(40)).collect::<Vec<_>>();
+
+        let mut recorder = Recorder::new("water");
+        let mut grid = rows.clone();
+
+        for tick in 0..200 {
+            let row = tick % 20;
+            let column = tick / 20;
+            grid[row].replace_range(column..=column, "~");
+            let text = grid.join("\n");
+
+            recorder.record(|| Frame::new(tick, text));
+        }
+
+        let recording = recorder.finish();
+
+        assert_eq!(200, recording.len());
+        assert!(recording.keyframes() > 1);
+        assert!(recording.keyframes() < 40);
+        assert_eq!(grid.join("\n"), recording.frame(199).unwrap().text);
+    }
+
+    #[test]
+    fn recorder_which_is_off_renders_nothing() {
+        let mut recorder = Recorder::off();
+
+        recorder.record(|| panic!("rendered a frame"));
+
+        assert!(!recorder.is_on());
+        assert!(recorder.finish().is_empty());
+    }
+
+    #[test]
+    fn recordings_are_saved_as_text() {
+        let rest = "#".repeat(60);
+        let frames = [
+            format!("a b\n{}", rest),
+            format!("a  b\n{}", rest),
+            String::new(),
+            "x".to_owned(),
+        ];
+        let frames: Vec<&str> = frames.iter().map(String::as_str).collect();
+        let recording = record(&frames);
+        assert_eq!(3, recording.keyframes());
+
+        let text = recording.to_string();
+        assert!(text.starts_with("aoc-recording 1\ntitle test\nframe 0"));
+        assert_eq!(recording, text.parse().unwrap());
+
+        let broken = text.replace("edit 0 2 0  ", "edit 0 9 0  ");
+        let err = broken.parse::<Recording>().unwrap_err();
+        assert_eq!("line 9: edit of line 0 is past its end", err.to_string());
+
+        let huge = format!("edit 0 2 {}  ", usize::MAX);
+        let err = text.replace("edit 0 2 0  ", &huge).parse::<Recording>();
+        assert!(err.is_err());
+
+        let huge = format!("frame 0 {} key", usize::MAX);
+        let err = text.replace("frame 0 2 key", &huge).parse::<Recording>();
+        assert!(err.unwrap_err().to_string().contains("larger than"));
+    }
+
+    #[test]
+    fn controls() {
+        assert_eq!(Control::Forward(1), "".parse().unwrap());
+        assert_eq!(Control::Back(5), "b 5".parse().unwrap());
+        assert_eq!(Control::Jump(40), " 40 ".parse().unwrap());
+        assert_eq!(Control::Scroll(0, -16), "h 2".parse().unwrap());
+        assert!("x".parse::<Control>().is_err());
+    }
+
+    #[test]
+    fn player_steps_and_follows_the_changes() {
+        let mut frames: Vec<String> = Vec::new();
+        for tick in 0..10 {
+            let mut lines = vec![".".repeat(30); 30];
+            lines[tick * 3].replace_range(20..21, "#");
+            frames.push(lines.join("\n"));
+        }
+        let frames: Vec<&str> = frames.iter().map(String::as_str).collect();
+        let recording = record(&frames);
+
+        let mut player = Player::new(&recording, 4, 10).unwrap();
+        assert!(player.screen().starts_with("test  frame 1/10  tick 0"));
+
+        assert!(player.apply(Control::Forward(3)));
+        assert_eq!(3, player.index());
+        let screen = player.screen();
+        assert!(screen.contains("units: 3"));
+        assert!(screen.contains("\n....#.....\n"), "{}", screen);
+
+        player.apply(Control::Jump(7));
+        assert_eq!(7, player.index());
+        player.apply(Control::Back(10));
+        assert_eq!(0, player.index());
+        player.apply(Control::Last);
+
+        player.apply(Control::PlayPause);
+        assert!(player.is_playing());
+        player.advance();
+        assert!(!player.is_playing());
+
+        assert!(!player.apply(Control::Quit));
+        assert!(Player::new(&Recording::default(), 4, 10).is_err());
+    }
+}
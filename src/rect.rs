@@ -0,0 +1,259 @@
+use std::fmt;
+
+use crate::{Integer, VecN};
+
+/// Box of the points between two corners, both corners included
+///
+/// A box always contains at least one point.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BoxN<T, const N: usize> {
+    min: VecN<T, N>,
+    max: VecN<T, N>,
+}
+
+/// Rectangle on a plane
+pub type Rect<T> = BoxN<T, 2>;
+/// Box in space
+pub type Cuboid<T> = BoxN<T, 3>;
+
+impl<T: Integer, const N: usize> BoxN<T, N> {
+    /// Box with the given opposite corners in any order
+    pub fn new(a: VecN<T, N>, b: VecN<T, N>) -> Self {
+        BoxN {
+            min: a.component_min(b),
+            max: a.component_max(b),
+        }
+    }
+
+    /// Box of `size` points along each axis starting from `min`, `None` if
+    /// any size is not positive
+    pub fn from_size(min: VecN<T, N>, size: VecN<T, N>) -> Option<Self> {
+        if size.0.iter().any(|&s| s < T::one()) {
+            return None;
+        }
+
+        let mut max = min + size;
+        for component in max.0.iter_mut() {
+            *component -= T::one();
+        }
+
+        Some(BoxN { min, max })
+    }
+
+    /// Smallest box containing every point, `None` when there are no points
+    pub fn from_points<I>(points: I) -> Option<Self>
+    where
+        I: IntoIterator,
+        I::Item: Into<VecN<T, N>>,
+    {
+        VecN::bounding_box(points.into_iter().map(Into::into))
+            .map(|(min, max)| BoxN { min, max })
+    }
+
+    pub fn min(&self) -> VecN<T, N> {
+        self.min
+    }
+
+    pub fn max(&self) -> VecN<T, N> {
+        self.max
+    }
+
+    /// Number of points along each axis
+    pub fn size(&self) -> VecN<T, N> {
+        let mut size = self.max - self.min;
+        for component in size.0.iter_mut() {
+            *component += T::one();
+        }
+        size
+    }
+
+    /// Number of points in the box
+    pub fn area(&self) -> T {
+        self.size().0.iter().fold(T::one(), |area, &s| area * s)
+    }
+
+    pub fn contains(&self, point: VecN<T, N>) -> bool {
+        (0..N).all(|i| self.min[i] <= point[i] && point[i] <= self.max[i])
+    }
+
+    /// Point of the box closest to `point`
+    pub fn clamp(&self, point: VecN<T, N>) -> VecN<T, N> {
+        point.component_max(self.min).component_min(self.max)
+    }
+
+    /// Smallest box containing both boxes
+    pub fn union(&self, other: Self) -> Self {
+        BoxN {
+            min: self.min.component_min(other.min),
+            max: self.max.component_max(other.max),
+        }
+    }
+
+    /// Points in both boxes, `None` if the boxes do not overlap
+    pub fn intersection(&self, other: Self) -> Option<Self> {
+        let min = self.min.component_max(other.min);
+        let max = self.max.component_min(other.max);
+
+        if (0..N).all(|i| min[i] <= max[i]) {
+            Some(BoxN { min, max })
+        } else {
+            None
+        }
+    }
+
+    /// Smallest box containing the box and `point`
+    pub fn including(&self, point: VecN<T, N>) -> Self {
+        self.union(BoxN {
+            min: point,
+            max: point,
+        })
+    }
+
+    /// Box grown by `amount` on every side
+    pub fn expand(&self, amount: T) -> Self {
+        let mut grown = *self;
+        for i in 0..N {
+            grown.min[i] -= amount;
+            grown.max[i] += amount;
+        }
+        BoxN::new(grown.min, grown.max)
+    }
+
+    /// Position of `point` relative to the smallest corner
+    pub fn normalize(&self, point: VecN<T, N>) -> VecN<T, N> {
+        point - self.min
+    }
+
+    /// Every point in the box, the first axis changing fastest
+    pub fn points(&self) -> Points<T, N> {
+        Points {
+            bounds: *self,
+            next: Some(self.min),
+        }
+    }
+}
+
+impl<T: Integer> BoxN<T, 2> {
+    pub fn width(&self) -> T {
+        self.size().x()
+    }
+
+    pub fn height(&self) -> T {
+        self.size().y()
+    }
+}
+
+impl<T: fmt::Display, const N: usize> fmt::Debug for BoxN<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}..={:?}", self.min, self.max)
+    }
+}
+
+/// Iterator over the points of a box
+#[derive(Clone)]
+pub struct Points<T, const N: usize> {
+    bounds: BoxN<T, N>,
+    next: Option<VecN<T, N>>,
+}
+
+impl<T: Integer, const N: usize> Iterator for Points<T, N> {
+    type Item = VecN<T, N>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next?;
+
+        let mut next = current;
+        self.next = None;
+
+        for i in 0..N {
+            if next[i] < self.bounds.max[i] {
+                next[i] += T::one();
+                self.next = Some(next);
+                break;
+            }
+            next[i] = self.bounds.min[i];
+        }
+
+        Some(current)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{V2, V3};
+
+    fn rect(a: (i32, i32), b: (i32, i32)) -> Rect<i32> {
+        Rect::new(a.into(), b.into())
+    }
+
+    #[test]
+    fn corners_in_any_order() {
+        let r = rect((3, -1), (0, 4));
+
+        assert_eq!(V2::new([0, -1]), r.min());
+        assert_eq!(V2::new([3, 4]), r.max());
+        assert_eq!((4, 6), (r.width(), r.height()));
+        assert_eq!(24, r.area());
+    }
+
+    #[test]
+    fn from_points_and_size() {
+        let points = vec![V2::new([1, 5]), V2::new([-2, 0]), V2::new([4, 1])];
+
+        assert_eq!(Some(rect((-2, 0), (4, 5))), Rect::from_points(points));
+        assert_eq!(None, Rect::<i32>::from_points(Vec::<V2<i32>>::new()));
+
+        assert_eq!(
+            Some(rect((1, 3), (4, 6))),
+            Rect::from_size((1, 3).into(), (4, 4).into())
+        );
+        assert_eq!(None, Rect::from_size((1, 3).into(), (0, 4).into()));
+    }
+
+    #[test]
+    fn union_and_intersection() {
+        let a = rect((1, 3), (4, 6));
+        let b = rect((3, 1), (6, 4));
+
+        assert_eq!(rect((1, 1), (6, 6)), a.union(b));
+        assert_eq!(Some(rect((3, 3), (4, 4))), a.intersection(b));
+        assert_eq!(None, a.intersection(rect((5, 5), (6, 6))));
+        assert_eq!(rect((1, 3), (4, 8)), a.including((2, 8).into()));
+    }
+
+    #[test]
+    fn containment() {
+        let c = Cuboid::new(V3::new([0, 0, 0]), V3::new([2, 2, 2]));
+
+        assert!(c.contains(V3::new([2, 0, 1])));
+        assert!(!c.contains(V3::new([3, 0, 1])));
+        assert_eq!(V3::new([2, 0, 1]), c.clamp(V3::new([5, -4, 1])));
+        assert_eq!(27, c.area());
+    }
+
+    #[test]
+    fn expand_and_normalize() {
+        let r = rect((1, 3), (4, 6));
+
+        assert_eq!(rect((0, 2), (5, 7)), r.expand(1));
+        assert_eq!(V2::new([1, 2]), r.normalize((2, 5).into()));
+    }
+
+    #[test]
+    fn points_in_reading_order() {
+        let points: Vec<_> = rect((0, 0), (2, 1)).points().collect();
+
+        assert_eq!(
+            vec![(0, 0), (1, 0), (2, 0), (0, 1), (1, 1), (2, 1)]
+                .into_iter()
+                .map(V2::from)
+                .collect::<Vec<_>>(),
+            points
+        );
+        assert_eq!(
+            27,
+            Cuboid::new(V3::zero(), V3::new([2, 2, 2])).points().count()
+        );
+    }
+}
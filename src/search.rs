@@ -0,0 +1,359 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+use crate::Vector2;
+
+/// Nodes reached by a search with their costs and predecessors
+#[derive(Debug, Clone)]
+pub struct Reached<N: Eq + Hash, C> {
+    start: N,
+    costs: HashMap<N, C>,
+    came_from: HashMap<N, N>,
+}
+
+impl<N: Eq + Hash + Clone, C: Copy> Reached<N, C> {
+    fn new(start: N, zero: C) -> Self {
+        let mut costs = HashMap::new();
+        costs.insert(start.clone(), zero);
+
+        Reached {
+            start,
+            costs,
+            came_from: HashMap::new(),
+        }
+    }
+
+    pub fn start(&self) -> &N {
+        &self.start
+    }
+
+    /// Cost of the cheapest path from the start to `node`
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.costs.get(node).cloned()
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.costs.contains_key(node)
+    }
+
+    pub fn len(&self) -> usize {
+        self.costs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.costs.is_empty()
+    }
+
+    /// Reached nodes and their costs in arbitrary order
+    pub fn iter(&self) -> impl Iterator<Item = (&N, C)> {
+        self.costs.iter().map(|(node, cost)| (node, *cost))
+    }
+
+    /// Path from the start to `node`, both included
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        if !self.contains(node) {
+            return None;
+        }
+
+        let mut path = vec![node.clone()];
+        let mut current = node;
+
+        while let Some(prev) = self.came_from.get(current) {
+            path.push(prev.clone());
+            current = prev;
+        }
+
+        path.reverse();
+
+        Some(path)
+    }
+}
+
+/// Breadth-first search over every node reachable from `start`
+///
+/// Each node is reached from the first neighbour that yields it, so the
+/// order of the neighbours decides between paths of equal length.
+pub fn bfs<N, I, F>(start: N, mut neighbors: F) -> Reached<N, usize>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+    F: FnMut(&N) -> I,
+{
+    let mut reached = Reached::new(start.clone(), 0);
+    let mut queue = VecDeque::new();
+    queue.push_back(start);
+
+    while let Some(current) = queue.pop_front() {
+        let distance = reached.costs[&current] + 1;
+
+        for next in neighbors(&current) {
+            if reached.contains(&next) {
+                continue;
+            }
+
+            reached.costs.insert(next.clone(), distance);
+            reached.came_from.insert(next.clone(), current.clone());
+            queue.push_back(next);
+        }
+    }
+
+    reached
+}
+
+/// Finds the closest node for which `is_goal` holds and its distance
+///
+/// Goals at the same distance are ordered by `N`, use `ReadingOrder` to
+/// prefer the top-most, then left-most position.
+pub fn bfs_nearest<N, I, F, G>(
+    start: N,
+    mut neighbors: F,
+    mut is_goal: G,
+) -> Option<(N, usize)>
+where
+    N: Eq + Hash + Ord + Clone,
+    I: IntoIterator<Item = N>,
+    F: FnMut(&N) -> I,
+    G: FnMut(&N) -> bool,
+{
+    let mut distances = HashMap::new();
+    distances.insert(start.clone(), 0);
+
+    let mut queue = VecDeque::new();
+    queue.push_back(start);
+
+    let mut best: Option<(usize, N)> = None;
+
+    while let Some(current) = queue.pop_front() {
+        let distance = distances[&current];
+
+        if let Some((best_distance, _)) = best {
+            if distance > best_distance {
+                break;
+            }
+        }
+
+        if is_goal(&current) {
+            let candidate = (distance, current.clone());
+            if best.as_ref().is_none_or(|b| candidate < *b) {
+                best = Some(candidate);
+            }
+        }
+
+        for next in neighbors(&current) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), distance + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    best.map(|(distance, node)| (node, distance))
+}
+
+/// Dijkstra's search over every node reachable from `start`
+///
+/// `neighbors` yields the neighbouring nodes with the cost of moving to
+/// them. Nodes with equal costs are expanded in the order of `N`.
+pub fn dijkstra<N, C, I, F>(start: N, mut neighbors: F) -> Reached<N, C>
+where
+    N: Eq + Hash + Ord + Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+    F: FnMut(&N) -> I,
+{
+    let mut reached = Reached::new(start.clone(), C::default());
+    let mut frontier = BinaryHeap::new();
+    frontier.push(Reverse((C::default(), start)));
+
+    while let Some(Reverse((cost, current))) = frontier.pop() {
+        if reached.costs[&current] < cost {
+            continue;
+        }
+
+        for (next, step) in neighbors(&current) {
+            let next_cost = cost + step;
+
+            if reached.cost(&next).is_some_and(|c| c <= next_cost) {
+                continue;
+            }
+
+            reached.costs.insert(next.clone(), next_cost);
+            reached.came_from.insert(next.clone(), current.clone());
+            frontier.push(Reverse((next_cost, next)));
+        }
+    }
+
+    reached
+}
+
+/// A* search from `start` to the first node for which `is_goal` holds
+///
+/// `heuristic` must never overestimate the remaining cost. Returns the path,
+/// both ends included, and its cost. Nodes with equal estimates are
+/// expanded by their cost and then in the order of `N`.
+pub fn astar<N, C, I, F, H, G>(
+    start: N,
+    mut neighbors: F,
+    mut heuristic: H,
+    mut is_goal: G,
+) -> Option<(Vec<N>, C)>
+where
+    N: Eq + Hash + Ord + Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+    F: FnMut(&N) -> I,
+    H: FnMut(&N) -> C,
+    G: FnMut(&N) -> bool,
+{
+    let mut reached = Reached::new(start.clone(), C::default());
+    let mut frontier = BinaryHeap::new();
+    frontier.push(Reverse((heuristic(&start), C::default(), start)));
+
+    while let Some(Reverse((_, cost, current))) = frontier.pop() {
+        if reached.costs[&current] < cost {
+            continue;
+        }
+
+        if is_goal(&current) {
+            return reached.path_to(&current).map(|path| (path, cost));
+        }
+
+        for (next, step) in neighbors(&current) {
+            let next_cost = cost + step;
+
+            if reached.cost(&next).is_some_and(|c| c <= next_cost) {
+                continue;
+            }
+
+            reached.costs.insert(next.clone(), next_cost);
+            reached.came_from.insert(next.clone(), current.clone());
+
+            let estimate = next_cost + heuristic(&next);
+            frontier.push(Reverse((estimate, next_cost, next)));
+        }
+    }
+
+    None
+}
+
+/// Orders positions top to bottom, then left to right
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ReadingOrder(pub Vector2);
+
+impl Ord for ReadingOrder {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.0.y, self.0.x).cmp(&(other.0.y, other.0.x))
+    }
+}
+
+impl PartialOrd for ReadingOrder {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Grid;
+
+    const INPUT: &'static str = r"
+#######
+#.....#
+#.###.#
+#.#...#
+#...#.#
+#######
+    ";
+
+    fn maze() -> Grid<bool> {
+        Grid::parse(INPUT.trim(), |_, c| Some(c == '.')).unwrap()
+    }
+
+    fn open(grid: &Grid<bool>, pos: Vector2) -> Vec<Vector2> {
+        grid.neighbors4(pos).filter(|p| grid[*p]).collect()
+    }
+
+    #[test]
+    fn bfs_reaches_every_open_tile() {
+        let grid = maze();
+
+        let reached = bfs(Vector2::new(1, 1), |p| open(&grid, *p));
+
+        assert_eq!(15, reached.len());
+        assert_eq!(Some(5), reached.cost(&Vector2::new(5, 2)));
+        assert_eq!(Some(5), reached.cost(&Vector2::new(3, 4)));
+        assert_eq!(None, reached.cost(&Vector2::new(0, 0)));
+
+        let path = reached.path_to(&Vector2::new(1, 4)).unwrap();
+        assert_eq!(4, path.len());
+        assert_eq!(Vector2::new(1, 1), path[0]);
+        assert_eq!(Vector2::new(1, 4), path[3]);
+    }
+
+    #[test]
+    fn bfs_nearest_breaks_ties_in_reading_order() {
+        let grid = maze();
+        let goals = [Vector2::new(1, 4), Vector2::new(4, 3)];
+
+        // Both goals are 2 steps away
+        let nearest = bfs_nearest(
+            ReadingOrder(Vector2::new(3, 4)),
+            |p| open(&grid, p.0).into_iter().map(ReadingOrder),
+            |p| goals.contains(&p.0),
+        );
+        assert_eq!(Some((ReadingOrder(Vector2::new(4, 3)), 2)), nearest);
+
+        let nearest = bfs_nearest(
+            Vector2::new(3, 4),
+            |p| open(&grid, *p),
+            |p| goals.contains(p),
+        );
+        assert_eq!(Some((Vector2::new(1, 4), 2)), nearest);
+    }
+
+    #[test]
+    fn dijkstra_uses_costs() {
+        // 0 -> 1 -> 3 is cheaper than 0 -> 3
+        let edges = |n: &u32| match n {
+            0 => vec![(1, 1), (2, 5), (3, 10)],
+            1 => vec![(3, 2)],
+            2 => vec![(3, 1)],
+            _ => vec![],
+        };
+
+        let reached = dijkstra(0, edges);
+
+        assert_eq!(Some(3), reached.cost(&3));
+        assert_eq!(Some(vec![0, 1, 3]), reached.path_to(&3));
+        assert_eq!(Some(5), reached.cost(&2));
+    }
+
+    #[test]
+    fn astar_finds_shortest_path() {
+        let grid = maze();
+        let end = Vector2::new(5, 4);
+
+        let (path, cost) = astar(
+            Vector2::new(1, 4),
+            |p| open(&grid, *p).into_iter().map(|n| (n, 1)),
+            |p| ((end.x - p.x).abs() + (end.y - p.y).abs()) as usize,
+            |p| *p == end,
+        )
+        .unwrap();
+
+        assert_eq!(6, cost);
+        assert_eq!(7, path.len());
+        assert_eq!(Some(&end), path.last());
+
+        let unreachable = astar(
+            Vector2::new(1, 4),
+            |p| open(&grid, *p).into_iter().map(|n| (n, 1)),
+            |_| 0,
+            |p| *p == Vector2::new(0, 0),
+        );
+        assert_eq!(None, unreachable);
+    }
+}
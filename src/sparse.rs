@@ -0,0 +1,289 @@
+use std::collections::btree_map::{self, BTreeMap};
+use std::iter::FromIterator;
+
+use crate::{Dir4, Dir8, Grid, Rect, Vector2};
+
+/// Unbounded two dimensional map of cells, only the set cells are stored
+///
+/// The grid grows as cells are inserted and keeps track of the smallest
+/// rectangle covering them, so it can be rendered or turned into a dense
+/// `Grid` once the extent of the puzzle is known.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct SparseGrid<T> {
+    cells: BTreeMap<Vector2, T>,
+    bounds: Option<Rect<i32>>,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        SparseGrid {
+            cells: BTreeMap::new(),
+            bounds: None,
+        }
+    }
+
+    /// Number of set cells
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Removes every cell
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.bounds = None;
+    }
+
+    /// Smallest rectangle covering the set cells, `None` if there are none
+    pub fn bounds(&self) -> Option<Rect<i32>> {
+        self.bounds
+    }
+
+    pub fn contains<P: Into<Vector2>>(&self, pos: P) -> bool {
+        self.cells.contains_key(&pos.into())
+    }
+
+    pub fn get<P: Into<Vector2>>(&self, pos: P) -> Option<&T> {
+        self.cells.get(&pos.into())
+    }
+
+    pub fn get_mut<P: Into<Vector2>>(&mut self, pos: P) -> Option<&mut T> {
+        self.cells.get_mut(&pos.into())
+    }
+
+    /// Sets the cell at `pos`, returns the previous value of the cell
+    pub fn insert<P: Into<Vector2>>(&mut self, pos: P, value: T) -> Option<T> {
+        let pos = pos.into();
+        self.grow(pos);
+        self.cells.insert(pos, value)
+    }
+
+    /// Cell at `pos`, set to `value` first if it is not set
+    pub fn get_or_insert<P: Into<Vector2>>(
+        &mut self,
+        pos: P,
+        value: T,
+    ) -> &mut T {
+        let pos = pos.into();
+        self.grow(pos);
+        self.cells.entry(pos).or_insert(value)
+    }
+
+    /// Unsets the cell at `pos`, returns the value of the cell
+    pub fn remove<P: Into<Vector2>>(&mut self, pos: P) -> Option<T> {
+        let pos = pos.into();
+        let value = self.cells.remove(&pos)?;
+
+        // Only removing a cell on the edge can shrink the bounds
+        let inside = self.bounds.is_some_and(|b| {
+            b.min().x() < pos.x
+                && pos.x < b.max().x()
+                && b.min().y() < pos.y
+                && pos.y < b.max().y()
+        });
+
+        if !inside {
+            self.bounds = Rect::from_points(self.cells.keys().cloned());
+        }
+
+        Some(value)
+    }
+
+    /// Neighbouring positions with a set cell in reading order,
+    /// up, left, right and down
+    pub fn neighbors4<P: Into<Vector2>>(
+        &self,
+        pos: P,
+    ) -> impl Iterator<Item = Vector2> + '_ {
+        let pos = pos.into();
+
+        let around = Dir4::READING_ORDER.map(|dir| pos + dir);
+
+        IntoIterator::into_iter(around).filter(move |p| self.contains(*p))
+    }
+
+    /// Neighbouring positions, including diagonals, with a set cell in
+    /// reading order
+    pub fn neighbors8<P: Into<Vector2>>(
+        &self,
+        pos: P,
+    ) -> impl Iterator<Item = Vector2> + '_ {
+        let pos = pos.into();
+
+        let around = Dir8::READING_ORDER.map(|dir| pos + dir);
+
+        IntoIterator::into_iter(around).filter(move |p| self.contains(*p))
+    }
+
+    /// Positions of the set cells in the order of `Vector2`
+    pub fn positions(&self) -> impl Iterator<Item = Vector2> + '_ {
+        self.cells.keys().cloned()
+    }
+
+    /// Positions and set cells in the order of `Vector2`
+    pub fn iter(&self) -> impl Iterator<Item = (Vector2, &T)> {
+        self.cells.iter().map(|(pos, cell)| (*pos, cell))
+    }
+
+    /// Renders the cells within the bounds one line per row, `f` gives the
+    /// character of a set cell and `empty` is used for the rest
+    pub fn render<F: Fn(&T) -> char>(&self, empty: char, f: F) -> String {
+        let bounds = match self.bounds {
+            Some(bounds) => bounds,
+            None => return String::new(),
+        };
+
+        let mut buf = String::with_capacity(
+            (bounds.width() as usize + 1) * bounds.height() as usize,
+        );
+
+        for y in bounds.min().y()..=bounds.max().y() {
+            for x in bounds.min().x()..=bounds.max().x() {
+                buf.push(self.get((x, y)).map_or(empty, &f));
+            }
+            buf.push('\n');
+        }
+
+        buf
+    }
+
+    fn grow(&mut self, pos: Vector2) {
+        self.bounds = Some(match self.bounds {
+            Some(bounds) => bounds.including(pos.into()),
+            None => Rect::new(pos.into(), pos.into()),
+        });
+    }
+}
+
+impl<T: Clone> SparseGrid<T> {
+    /// Dense grid covering the bounds, the cells keep their positions
+    /// through the offset of the grid and the unset ones are `empty`
+    pub fn to_grid(&self, empty: T) -> Grid<T> {
+        match self.bounds {
+            Some(bounds) => self.to_grid_covering(bounds, empty),
+            None => Grid::new(0, 0, empty),
+        }
+    }
+
+    /// Dense grid covering `rect`, cells outside of it are left out
+    pub fn to_grid_covering(&self, rect: Rect<i32>, empty: T) -> Grid<T> {
+        let mut grid = Grid::covering(rect, empty);
+
+        for (pos, cell) in self.iter() {
+            if let Some(c) = grid.get_mut(pos) {
+                *c = cell.clone();
+            }
+        }
+
+        grid
+    }
+}
+
+impl<T> FromIterator<(Vector2, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Vector2, T)>>(iter: I) -> Self {
+        let mut grid = SparseGrid::new();
+        for (pos, value) in iter {
+            grid.insert(pos, value);
+        }
+        grid
+    }
+}
+
+impl<T> IntoIterator for SparseGrid<T> {
+    type Item = (Vector2, T);
+    type IntoIter = btree_map::IntoIter<Vector2, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cells.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(a: (i32, i32), b: (i32, i32)) -> Rect<i32> {
+        Rect::new(a.into(), b.into())
+    }
+
+    #[test]
+    fn grows_with_inserted_cells() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(None, grid.bounds());
+
+        grid.insert((2, 3), 'a');
+        assert_eq!(Some(rect((2, 3), (2, 3))), grid.bounds());
+
+        grid.insert((-4, 5), 'b');
+        assert_eq!(None, grid.insert((0, -1), 'c'));
+        assert_eq!(Some('c'), grid.insert((0, -1), 'd'));
+
+        assert_eq!(Some(rect((-4, -1), (2, 5))), grid.bounds());
+        assert_eq!(3, grid.len());
+        assert_eq!(Some(&'d'), grid.get((0, -1)));
+        assert_eq!(None, grid.get((0, 0)));
+    }
+
+    #[test]
+    fn removing_edge_cells_shrinks_bounds() {
+        let mut grid: SparseGrid<u8> =
+            vec![((0, 0).into(), 1), ((1, 1).into(), 2), ((3, 2).into(), 3)]
+                .into_iter()
+                .collect();
+
+        assert_eq!(Some(2), grid.remove((1, 1)));
+        assert_eq!(Some(rect((0, 0), (3, 2))), grid.bounds());
+
+        assert_eq!(Some(3), grid.remove((3, 2)));
+        assert_eq!(Some(rect((0, 0), (0, 0))), grid.bounds());
+
+        assert_eq!(None, grid.remove((3, 2)));
+        grid.remove((0, 0));
+        assert_eq!(None, grid.bounds());
+        assert!(grid.is_empty());
+    }
+
+    #[test]
+    fn neighbors_are_set_cells() {
+        let grid: SparseGrid<()> = vec![(0, -1), (-1, 0), (1, 1), (5, 5)]
+            .into_iter()
+            .map(|p| (p.into(), ()))
+            .collect();
+
+        let n4: Vec<_> = grid.neighbors4((0, 0)).collect();
+        let n8: Vec<_> = grid.neighbors8((0, 0)).collect();
+
+        assert_eq!(n4, vec![(0, -1), (-1, 0)]);
+        assert_eq!(n8, vec![(0, -1), (-1, 0), (1, 1)]);
+    }
+
+    #[test]
+    fn render_and_dense_grid() {
+        let mut grid = SparseGrid::new();
+        grid.insert((-1, 2), true);
+        grid.insert((1, 3), false);
+
+        assert_eq!(
+            "#..\n..o\n",
+            grid.render('.', |&b| if b { '#' } else { 'o' })
+        );
+
+        let dense = grid.to_grid(false);
+        assert_eq!((3, 2), (dense.width(), dense.height()));
+        assert_eq!(Vector2::new(-1, 2), dense.offset());
+        assert_eq!(Some(&true), dense.get((-1, 2)));
+        assert_eq!(1, dense.values().filter(|&&b| b).count());
+
+        let clipped = grid.to_grid_covering(rect((0, 2), (1, 2)), true);
+        assert_eq!(
+            vec![true, true],
+            clipped.values().cloned().collect::<Vec<_>>()
+        );
+
+        assert_eq!("", SparseGrid::<bool>::new().render('.', |_| '#'));
+        assert!(SparseGrid::<bool>::new().to_grid(false).is_empty());
+    }
+}
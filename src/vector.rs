@@ -0,0 +1,361 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+use crate::{Error, Result, Vector2};
+
+/// Signed integer type usable as the component of a `VecN`
+pub trait Integer:
+    Copy
+    + Ord
+    + Default
+    + fmt::Debug
+    + fmt::Display
+    + FromStr
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Neg<Output = Self>
+    + AddAssign
+    + SubAssign
+{
+    fn abs(self) -> Self;
+
+    fn one() -> Self;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                fn abs(self) -> Self {
+                    <$t>::abs(self)
+                }
+
+                fn one() -> Self {
+                    1
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(i8, i16, i32, i64, i128, isize);
+
+/// Vector of `N` integer components
+///
+/// Orders by the first component, then the second and so on.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub struct VecN<T, const N: usize>(pub [T; N]);
+
+pub type V2<T> = VecN<T, 2>;
+pub type V3<T> = VecN<T, 3>;
+pub type V4<T> = VecN<T, 4>;
+
+impl<T: Integer, const N: usize> VecN<T, N> {
+    pub fn new(components: [T; N]) -> Self {
+        VecN(components)
+    }
+
+    pub fn zero() -> Self {
+        VecN([T::default(); N])
+    }
+
+    /// Sum of the absolute differences of the components
+    pub fn manhattan(self, other: Self) -> T {
+        (self - other)
+            .0
+            .iter()
+            .fold(T::default(), |acc, &d| acc + d.abs())
+    }
+
+    /// Largest absolute difference of the components
+    pub fn chebyshev(self, other: Self) -> T {
+        (self - other)
+            .0
+            .iter()
+            .map(|&d| d.abs())
+            .max()
+            .unwrap_or_default()
+    }
+
+    pub fn component_min(self, other: Self) -> Self {
+        self.zip(other, |a, b| a.min(b))
+    }
+
+    pub fn component_max(self, other: Self) -> Self {
+        self.zip(other, |a, b| a.max(b))
+    }
+
+    /// Smallest and largest corner of the box containing every point,
+    /// `None` when there are no points
+    pub fn bounding_box<I>(points: I) -> Option<(Self, Self)>
+    where
+        I: IntoIterator<Item = Self>,
+    {
+        points.into_iter().fold(None, |bounds, p| match bounds {
+            None => Some((p, p)),
+            Some((min, max)) => {
+                Some((min.component_min(p), max.component_max(p)))
+            }
+        })
+    }
+
+    fn zip<F: Fn(T, T) -> T>(self, other: Self, f: F) -> Self {
+        let mut result = self;
+
+        for (a, &b) in result.0.iter_mut().zip(other.0.iter()) {
+            *a = f(*a, b);
+        }
+
+        result
+    }
+}
+
+impl<T: Copy> VecN<T, 2> {
+    pub fn x(&self) -> T {
+        self.0[0]
+    }
+
+    pub fn y(&self) -> T {
+        self.0[1]
+    }
+}
+
+impl<T: Copy> VecN<T, 3> {
+    pub fn x(&self) -> T {
+        self.0[0]
+    }
+
+    pub fn y(&self) -> T {
+        self.0[1]
+    }
+
+    pub fn z(&self) -> T {
+        self.0[2]
+    }
+}
+
+impl<T: Copy> VecN<T, 4> {
+    pub fn x(&self) -> T {
+        self.0[0]
+    }
+
+    pub fn y(&self) -> T {
+        self.0[1]
+    }
+
+    pub fn z(&self) -> T {
+        self.0[2]
+    }
+
+    pub fn w(&self) -> T {
+        self.0[3]
+    }
+}
+
+impl<T: Integer, const N: usize> Default for VecN<T, N> {
+    fn default() -> Self {
+        VecN::zero()
+    }
+}
+
+impl<T: fmt::Display, const N: usize> fmt::Debug for VecN<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "(")?;
+        for (i, v) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", v)?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl<T: fmt::Display, const N: usize> fmt::Display for VecN<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// Parses comma separated components such as `1,-2, 3`
+impl<T: Integer, const N: usize> FromStr for VecN<T, N> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut result = VecN::zero();
+        let mut parts = s.trim().split(',');
+
+        for component in result.0.iter_mut() {
+            let part = parts.next().map(str::trim).ok_or_else(|| {
+                Error::parse(format!("expected {} components in '{}'", N, s))
+            })?;
+
+            *component = part.parse::<T>().map_err(|_| {
+                Error::parse(format!("invalid component '{}'", part))
+                    .at_token(s, part)
+            })?;
+        }
+
+        if parts.next().is_some() {
+            return Err(Error::parse(format!(
+                "expected {} components in '{}'",
+                N, s
+            )));
+        }
+
+        Ok(result)
+    }
+}
+
+impl<T, const N: usize> Index<usize> for VecN<T, N> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        &self.0[index]
+    }
+}
+
+impl<T, const N: usize> IndexMut<usize> for VecN<T, N> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        &mut self.0[index]
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for VecN<T, N> {
+    fn from(components: [T; N]) -> Self {
+        VecN(components)
+    }
+}
+
+impl<T> From<(T, T)> for VecN<T, 2> {
+    fn from(v: (T, T)) -> Self {
+        VecN([v.0, v.1])
+    }
+}
+
+impl<T> From<(T, T, T)> for VecN<T, 3> {
+    fn from(v: (T, T, T)) -> Self {
+        VecN([v.0, v.1, v.2])
+    }
+}
+
+impl<T> From<(T, T, T, T)> for VecN<T, 4> {
+    fn from(v: (T, T, T, T)) -> Self {
+        VecN([v.0, v.1, v.2, v.3])
+    }
+}
+
+impl From<Vector2> for VecN<i32, 2> {
+    fn from(v: Vector2) -> Self {
+        VecN([v.x, v.y])
+    }
+}
+
+impl From<VecN<i32, 2>> for Vector2 {
+    fn from(v: VecN<i32, 2>) -> Self {
+        Vector2::new(v.0[0], v.0[1])
+    }
+}
+
+impl<T: Integer, const N: usize> Add for VecN<T, N> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        self.zip(other, |a, b| a + b)
+    }
+}
+
+impl<T: Integer, const N: usize> Sub for VecN<T, N> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self.zip(other, |a, b| a - b)
+    }
+}
+
+impl<T: Integer, const N: usize> Mul<T> for VecN<T, N> {
+    type Output = Self;
+
+    fn mul(mut self, other: T) -> Self {
+        for v in self.0.iter_mut() {
+            *v = *v * other;
+        }
+
+        self
+    }
+}
+
+impl<T: Integer, const N: usize> Neg for VecN<T, N> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        VecN::zero() - self
+    }
+}
+
+impl<T: Integer, const N: usize> AddAssign for VecN<T, N> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Integer, const N: usize> SubAssign for VecN<T, N> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distances() {
+        let a = V3::new([1, -2, 3]);
+        let b = V3::new([-1, 2, 4]);
+
+        assert_eq!(7, a.manhattan(b));
+        assert_eq!(4, a.chebyshev(b));
+        assert_eq!(0i64, V4::zero().manhattan(V4::zero()));
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a: V3<i64> = (1, 2, 3).into();
+        let b = V3::new([3, 2, 1]);
+
+        assert_eq!(V3::new([4, 4, 4]), a + b);
+        assert_eq!(V3::new([-2, 0, 2]), a - b);
+        assert_eq!(V3::new([2, 4, 6]), a * 2);
+        assert_eq!(V3::new([-1, -2, -3]), -a);
+        assert_eq!(3, a.z());
+        assert_eq!("(1, 2, 3)", a.to_string());
+    }
+
+    #[test]
+    fn parse_comma_separated() {
+        assert_eq!(V4::new([0, -3, 12, 1]), " 0,-3, 12,1".parse().unwrap());
+        assert_eq!(V2::new([1, 6]), "1, 6".parse::<V2<i32>>().unwrap());
+
+        assert!("1,2,3".parse::<V4<i32>>().is_err());
+        assert!("1,2,3,4,5".parse::<V4<i32>>().is_err());
+
+        let err = "1, x".parse::<V2<i32>>().unwrap_err();
+        assert_eq!("invalid component 'x'", err.to_string());
+        assert_eq!(Some(4), err.position().and_then(|p| p.column));
+    }
+
+    #[test]
+    fn bounding_box_of_points() {
+        let points =
+            vec![V3::new([1, 5, -1]), V3::new([-2, 0, 3]), V3::new([4, 1, 0])];
+
+        assert_eq!(
+            Some((V3::new([-2, 0, -1]), V3::new([4, 5, 3]))),
+            V3::bounding_box(points)
+        );
+        assert_eq!(None, V2::<i32>::bounding_box(vec![]));
+    }
+}